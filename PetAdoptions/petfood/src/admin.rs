@@ -0,0 +1,298 @@
+use std::future::Future;
+
+use tokio::sync::Mutex;
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{CreateFoodRequest, FoodType, PetType};
+use crate::service::{BatchSeedResult, CartService, FoodService};
+
+/// A handful of starter foods inserted by `seed_database`, covering a
+/// couple of pet types so a freshly-seeded catalog has something to
+/// recommend against.
+const SAMPLE_FOODS: &[(&str, &str, i64, PetType, FoodType)] = &[
+    ("Chicken Kibble", "Crunchy chicken-based kibble", 1299, PetType::Dog, FoodType::Dry),
+    ("Salmon Pate", "Smooth salmon wet food", 899, PetType::Cat, FoodType::Wet),
+    ("Seed Mix", "Mixed seed blend for songbirds", 499, PetType::Bird, FoodType::Dry),
+];
+
+/// Serializes the admin maintenance operations (`seed_database`,
+/// `cleanup_database`, `setup_tables`) so two concurrent calls can't
+/// interleave and double-count errors. Only one of these operations may be
+/// in flight at a time, process-wide.
+#[derive(Default)]
+pub struct AdminState {
+    operation_lock: Mutex<()>,
+}
+
+impl AdminState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `operation` while holding the exclusive admin-operation lock,
+    /// or returns `ApiError::Conflict` immediately if another admin
+    /// operation already holds it — callers should never block waiting for
+    /// the lock, since a caller that's waiting is a caller double-firing a
+    /// maintenance job.
+    async fn run_exclusive<F, Fut, T>(&self, operation: F) -> ApiResult<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ApiResult<T>>,
+    {
+        let _guard = self
+            .operation_lock
+            .try_lock()
+            .map_err(|_| ApiError::Conflict("an admin operation is already in progress".to_string()))?;
+        operation().await
+    }
+
+    /// Inserts the starter catalog, returning how many foods were created
+    /// and which (if any) tripped the content-quality gate. `concurrency`
+    /// bounds how many `BatchWriteItem`-sized chunks are written at once,
+    /// and `min_description_length`/`banned_placeholder_substrings` back the
+    /// gate itself — see `FoodService::create_foods_batch`. Rejects with
+    /// `ApiError::Validation` before taking the operation lock if the seed
+    /// input has more than `max_items` records, the same pre-flight check
+    /// `cleanup_database` applies.
+    pub async fn seed_database(
+        &self,
+        food_service: &FoodService,
+        tenant_id: Option<&str>,
+        concurrency: usize,
+        min_description_length: usize,
+        banned_placeholder_substrings: &[String],
+        max_items: usize,
+    ) -> ApiResult<BatchSeedResult> {
+        if SAMPLE_FOODS.len() > max_items {
+            return Err(ApiError::Validation(format!(
+                "seed input has {} items, exceeding the configured maximum of {max_items}",
+                SAMPLE_FOODS.len()
+            )));
+        }
+
+        self.run_exclusive(|| {
+            seed_sample_foods(food_service, tenant_id, concurrency, min_description_length, banned_placeholder_substrings)
+        })
+        .await
+    }
+
+    /// Removes carts untouched for `older_than_days`, returning how many
+    /// were deleted. Rejects with `ApiError::Validation` before deleting
+    /// anything if more than `max_items` carts are due for cleanup, so a
+    /// surprisingly large backlog doesn't get silently deleted in one
+    /// invocation.
+    pub async fn cleanup_database(&self, cart_service: &CartService, older_than_days: i64, max_items: usize) -> ApiResult<usize> {
+        self.run_exclusive(|| cart_service.cleanup_stale_carts(older_than_days, max_items)).await
+    }
+
+    /// Tables are provisioned by infrastructure-as-code, not the service
+    /// itself — this exists so the three maintenance operations share one
+    /// mutual-exclusion story, and is a no-op until a local/dev bootstrap
+    /// path needs it.
+    pub async fn setup_tables(&self) -> ApiResult<()> {
+        self.run_exclusive(|| async {
+            tracing::info!("setup_tables invoked; table provisioning is handled by infrastructure-as-code");
+            Ok(())
+        })
+        .await
+    }
+}
+
+async fn seed_sample_foods(
+    food_service: &FoodService,
+    tenant_id: Option<&str>,
+    concurrency: usize,
+    min_description_length: usize,
+    banned_placeholder_substrings: &[String],
+) -> ApiResult<BatchSeedResult> {
+    let requests = SAMPLE_FOODS
+        .iter()
+        .map(|(name, description, price_cents, pet_type, food_type)| CreateFoodRequest {
+            name: name.to_string(),
+            description: description.to_string(),
+            ingredients: Vec::new(),
+            price_cents: *price_cents,
+            stock_quantity: 10,
+            pet_type: *pet_type,
+            food_type: *food_type,
+            image_path: String::new(),
+            categories: Vec::new(),
+            prices: std::collections::HashMap::new(),
+        })
+        .collect();
+
+    food_service
+        .create_foods_batch(requests, tenant_id, concurrency, min_description_length, banned_placeholder_substrings)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex as StdMutex};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::events::EventEmitter;
+    use crate::models::Food;
+    use crate::repository::{DynamoDbAuditRepository, FoodRepository};
+    use crate::service::AuditLogger;
+
+    /// Delays every `put_food` so a `seed_database` call stays in flight
+    /// long enough for a concurrent second call to observe the lock held.
+    #[derive(Default)]
+    struct SlowFoodRepository(StdMutex<HashMap<String, Food>>);
+
+    #[async_trait::async_trait]
+    impl FoodRepository for SlowFoodRepository {
+        async fn get_food(&self, food_id: &str, _tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            Ok(self.0.lock().unwrap().get(food_id).cloned())
+        }
+
+        async fn list_foods(&self, _tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            Ok(self.0.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn put_food(&self, food: &Food, _tenant_id: Option<&str>) -> ApiResult<()> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            self.0.lock().unwrap().insert(food.food_id.clone(), food.clone());
+            Ok(())
+        }
+
+        async fn delete_food(&self, food_id: &str, _tenant_id: Option<&str>) -> ApiResult<()> {
+            self.0.lock().unwrap().remove(food_id);
+            Ok(())
+        }
+    }
+
+    fn dummy_event_emitter() -> Arc<EventEmitter> {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        Arc::new(EventEmitter::with_concurrency_limit(
+            aws_sdk_eventbridge::Client::new(&sdk_config),
+            "test-bus".to_string(),
+            None,
+            false,
+        ))
+    }
+
+    fn dummy_audit_logger() -> Arc<AuditLogger> {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        Arc::new(AuditLogger::new(Arc::new(DynamoDbAuditRepository::new(
+            aws_sdk_dynamodb::Client::new(&sdk_config),
+            "test-audit".to_string(),
+        ))))
+    }
+
+    #[tokio::test]
+    async fn seed_database_rejects_a_concurrent_seed_with_conflict() {
+        let food_service = Arc::new(FoodService::new(
+            Arc::new(SlowFoodRepository::default()),
+            dummy_event_emitter(),
+            dummy_audit_logger(),
+        ));
+        let admin_state = Arc::new(AdminState::new());
+
+        let first = {
+            let admin_state = admin_state.clone();
+            let food_service = food_service.clone();
+            tokio::spawn(async move { admin_state.seed_database(&food_service, None, 2, 0, &[], 100).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let second = admin_state.seed_database(&food_service, None, 2, 0, &[], 100).await;
+        assert!(matches!(second, Err(ApiError::Conflict(_))));
+
+        let first_result = first.await.unwrap();
+        assert_eq!(first_result.unwrap().created, SAMPLE_FOODS.len());
+    }
+
+    #[tokio::test]
+    async fn seed_database_inserts_every_sample_food() {
+        let food_service = Arc::new(FoodService::new(
+            Arc::new(SlowFoodRepository::default()),
+            dummy_event_emitter(),
+            dummy_audit_logger(),
+        ));
+        let admin_state = AdminState::new();
+
+        let result = admin_state.seed_database(&food_service, None, 2, 0, &[], 100).await.unwrap();
+
+        assert_eq!(result.created, SAMPLE_FOODS.len());
+        assert!(result.rejected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn seed_database_inserts_every_sample_food_under_the_default_quality_gate() {
+        let food_service = Arc::new(FoodService::new(
+            Arc::new(SlowFoodRepository::default()),
+            dummy_event_emitter(),
+            dummy_audit_logger(),
+        ));
+        let admin_state = AdminState::new();
+        let banned = vec!["TODO".to_string(), "lorem ipsum".to_string()];
+
+        let result = admin_state.seed_database(&food_service, None, 2, 15, &banned, 100).await.unwrap();
+
+        assert_eq!(result.created, SAMPLE_FOODS.len());
+        assert!(result.rejected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn seed_database_succeeds_when_the_seed_size_is_exactly_at_the_limit() {
+        let food_service = Arc::new(FoodService::new(
+            Arc::new(SlowFoodRepository::default()),
+            dummy_event_emitter(),
+            dummy_audit_logger(),
+        ));
+        let admin_state = AdminState::new();
+
+        let result = admin_state
+            .seed_database(&food_service, None, 2, 0, &[], SAMPLE_FOODS.len())
+            .await
+            .unwrap();
+
+        assert_eq!(result.created, SAMPLE_FOODS.len());
+    }
+
+    #[tokio::test]
+    async fn seed_database_rejects_a_seed_size_over_the_limit_without_writing_anything() {
+        let food_repository = Arc::new(SlowFoodRepository::default());
+        let food_service = Arc::new(FoodService::new(food_repository.clone(), dummy_event_emitter(), dummy_audit_logger()));
+        let admin_state = AdminState::new();
+
+        let result = admin_state
+            .seed_database(&food_service, None, 2, 0, &[], SAMPLE_FOODS.len() - 1)
+            .await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+        assert!(food_repository.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn setup_tables_and_seed_database_are_mutually_exclusive() {
+        let food_service = Arc::new(FoodService::new(
+            Arc::new(SlowFoodRepository::default()),
+            dummy_event_emitter(),
+            dummy_audit_logger(),
+        ));
+        let admin_state = Arc::new(AdminState::new());
+
+        let first = {
+            let admin_state = admin_state.clone();
+            let food_service = food_service.clone();
+            tokio::spawn(async move { admin_state.seed_database(&food_service, None, 2, 0, &[], 100).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let result = admin_state.setup_tables().await;
+        assert!(matches!(result, Err(ApiError::Conflict(_))));
+
+        first.await.unwrap().unwrap();
+    }
+}
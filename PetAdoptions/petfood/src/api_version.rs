@@ -0,0 +1,128 @@
+use axum::http::{header, HeaderMap};
+use serde::Serialize;
+
+use crate::filters::parse_multi_value_query;
+
+/// Response envelope version a caller selects via the `Accept` header
+/// (`application/vnd.petfood.v1+json` / `application/vnd.petfood.v2+json`)
+/// or the `?api_version=1`/`?api_version=2` query parameter, the header
+/// taking precedence when both are present. `V1` is the implicit default,
+/// so a client that negotiates nothing sees today's flat body unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    pub fn resolve(headers: &HeaderMap, query: &str) -> Self {
+        Self::from_accept_header(headers).or_else(|| Self::from_query(query)).unwrap_or(ApiVersion::V1)
+    }
+
+    fn from_accept_header(headers: &HeaderMap) -> Option<Self> {
+        let accept = headers.get(header::ACCEPT)?.to_str().ok()?;
+        if accept.contains("application/vnd.petfood.v2+json") {
+            Some(ApiVersion::V2)
+        } else if accept.contains("application/vnd.petfood.v1+json") {
+            Some(ApiVersion::V1)
+        } else {
+            None
+        }
+    }
+
+    fn from_query(query: &str) -> Option<Self> {
+        match parse_multi_value_query(query, "api_version").first().map(String::as_str) {
+            Some("2") => Some(ApiVersion::V2),
+            Some("1") => Some(ApiVersion::V1),
+            _ => None,
+        }
+    }
+}
+
+/// `meta` carried alongside `data` in a v2 envelope. Just the version for
+/// now — the plumbing any later breaking shape change will extend.
+#[derive(Serialize)]
+pub struct Meta {
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct V2Envelope<T> {
+    data: T,
+    meta: Meta,
+}
+
+/// A response body shaped per the negotiated [`ApiVersion`]: `V1` serializes
+/// as `value` always has; `V2` nests it under `data` with a `meta` sibling.
+/// Plays the same role for response shape that [`crate::canonical_json`]
+/// plays for key ordering, and composes with it — wrap a value with this
+/// first, then hand the result to `CanonicalJson` or `Json` as usual.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Versioned<T: Serialize> {
+    V1(T),
+    V2(V2Envelope<T>),
+}
+
+pub fn wrap<T: Serialize>(version: ApiVersion, value: T) -> Versioned<T> {
+    match version {
+        ApiVersion::V1 => Versioned::V1(value),
+        ApiVersion::V2 => Versioned::V2(V2Envelope { data: value, meta: Meta { version: "v2" } }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Widget {
+        id: String,
+    }
+
+    #[test]
+    fn resolve_defaults_to_v1_when_nothing_is_negotiated() {
+        assert_eq!(ApiVersion::resolve(&HeaderMap::new(), ""), ApiVersion::V1);
+    }
+
+    #[test]
+    fn resolve_reads_the_version_from_the_accept_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/vnd.petfood.v2+json"));
+
+        assert_eq!(ApiVersion::resolve(&headers, ""), ApiVersion::V2);
+    }
+
+    #[test]
+    fn resolve_reads_the_version_from_the_query_parameter() {
+        assert_eq!(ApiVersion::resolve(&HeaderMap::new(), "api_version=2"), ApiVersion::V2);
+    }
+
+    #[test]
+    fn resolve_prefers_the_accept_header_over_the_query_parameter() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/vnd.petfood.v1+json"));
+
+        assert_eq!(ApiVersion::resolve(&headers, "api_version=2"), ApiVersion::V1);
+    }
+
+    #[test]
+    fn wrap_v1_serializes_the_bare_value() {
+        let body = wrap(ApiVersion::V1, Widget { id: "widget-1".to_string() });
+
+        assert_eq!(serde_json::to_value(body).unwrap(), serde_json::json!({"id": "widget-1"}));
+    }
+
+    #[test]
+    fn wrap_v2_nests_the_value_under_data_with_a_meta_sibling() {
+        let body = wrap(ApiVersion::V2, Widget { id: "widget-1".to_string() });
+
+        assert_eq!(
+            serde_json::to_value(body).unwrap(),
+            serde_json::json!({"data": {"id": "widget-1"}, "meta": {"version": "v2"}})
+        );
+    }
+}
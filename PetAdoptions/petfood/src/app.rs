@@ -0,0 +1,465 @@
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{delete, get, patch, post, put};
+use axum::Router;
+
+use crate::cors::cors_middleware;
+use crate::handlers;
+use crate::https_enforcement::enforce_https_middleware;
+use crate::middleware::{observability_middleware, rate_limit_middleware, track_in_flight_requests};
+use crate::state::AppState;
+
+/// Builds the axum router shared by the real server and tests, so route
+/// wiring only lives in one place.
+pub fn create_app(state: AppState) -> Router {
+    let max_request_body_bytes = state.config.max_request_body_bytes;
+
+    Router::new()
+        .route("/health", get(handlers::health))
+        .route("/health/live", get(handlers::health))
+        .route("/health/ready", get(handlers::ready))
+        .route("/health/status", get(handlers::ready))
+        .route(
+            "/metrics",
+            get(handlers::metrics)
+                .head(handlers::metrics_head)
+                .options(handlers::metrics_options),
+        )
+        .route("/api/foods", get(handlers::list_foods))
+        .route("/api/foods/count", get(handlers::count_foods))
+        .route("/api/foods/ingredients", get(handlers::list_ingredients))
+        .route("/api/foods/batch", post(handlers::get_foods_batch))
+        .route("/api/admin/foods", post(handlers::create_food))
+        .route("/api/admin/foods/bulk", post(handlers::bulk_create_foods))
+        .route("/api/admin/foods/validate", post(handlers::validate_food))
+        .route("/api/admin/foods/changes", get(handlers::list_food_changes))
+        .route("/api/admin/foods/:food_id/price", put(handlers::update_price))
+        .route("/api/admin/foods/:food_id/stock", patch(handlers::adjust_stock))
+        .route("/api/admin/foods/:food_id", delete(handlers::delete_food))
+        .route("/api/admin/foods/prices", post(handlers::bulk_update_prices))
+        .route("/api/admin/foods/:food_id/history", get(handlers::get_food_history))
+        .route("/api/foods/:food_id", get(handlers::get_food))
+        .route("/api/cart/:user_id/items", post(handlers::add_item))
+        .route("/api/cart/:user_id/bulk-add", post(handlers::bulk_add_items))
+        .route("/api/cart/:user_id/coupon", post(handlers::apply_coupon))
+        .route("/api/cart/:user_id/validate", get(handlers::validate_cart))
+        .route("/api/cart/:user_id/checkout", post(handlers::checkout_cart))
+        .route("/api/admin/carts/cleanup", post(handlers::cleanup_carts))
+        .route("/api/admin/config", get(handlers::effective_config))
+        .route("/api/admin/metrics/reset", post(handlers::reset_metrics))
+        .route("/api/admin/metrics/snapshot", get(handlers::metrics_snapshot))
+        .route("/api/admin/seed", post(handlers::seed_database))
+        .route("/api/admin/cleanup", post(handlers::cleanup_database))
+        .route("/api/admin/setup-tables", post(handlers::setup_tables))
+        .route("/api/recommendations/:pet_type", get(handlers::recommend))
+        .route(
+            "/api/recommendations/:pet_type/stats",
+            get(handlers::recommendation_stats),
+        )
+        .route(
+            "/api/recommendations/stats",
+            get(handlers::recommendation_stats_for_all_pet_types),
+        )
+        .fallback(handlers::not_found)
+        .layer(axum::middleware::from_fn(observability_middleware))
+        .layer(axum::middleware::from_fn(rate_limit_middleware))
+        .layer(axum::middleware::from_fn(enforce_https_middleware))
+        .layer(axum::middleware::from_fn(cors_middleware))
+        .layer(DefaultBodyLimit::max(max_request_body_bytes))
+        .layer(axum::middleware::from_fn(track_in_flight_requests))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::admin::AdminState;
+    use crate::config::ServerConfig;
+    use crate::events::EventEmitter;
+    use crate::models::SortOrder;
+    use crate::readiness::ReadinessChecker;
+    use crate::repository::{
+        DynamoDbAuditRepository, DynamoDbCartRepository, DynamoDbDiscountRepository, DynamoDbFoodRepository,
+        DynamoDbOrderRepository,
+    };
+    use crate::service::{AuditLogger, CartService, FoodService, RecommendationService};
+
+    fn test_state() -> AppState {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        let dynamodb_client = aws_sdk_dynamodb::Client::new(&sdk_config);
+        let eventbridge_client = aws_sdk_eventbridge::Client::new(&sdk_config);
+        let ssm_client = aws_sdk_ssm::Client::new(&sdk_config);
+
+        let food_repository = Arc::new(DynamoDbFoodRepository::new(
+            dynamodb_client.clone(),
+            "test-foods".to_string(),
+            false,
+        ));
+        let cart_repository = Arc::new(DynamoDbCartRepository::new(
+            dynamodb_client.clone(),
+            "test-carts".to_string(),
+            30,
+            false,
+        ));
+        let order_repository = Arc::new(DynamoDbOrderRepository::new(dynamodb_client.clone(), "test-orders".to_string()));
+        let discount_repository = Arc::new(DynamoDbDiscountRepository::new(
+            dynamodb_client.clone(),
+            "test-discounts".to_string(),
+        ));
+        let event_emitter = Arc::new(EventEmitter::with_concurrency_limit(
+            eventbridge_client,
+            "test-bus".to_string(),
+            None,
+            false,
+        ));
+        let audit_logger = Arc::new(AuditLogger::new(Arc::new(DynamoDbAuditRepository::new(
+            dynamodb_client.clone(),
+            "test-audit".to_string(),
+        ))));
+
+        AppState {
+            food_service: Arc::new(FoodService::new(food_repository.clone(), event_emitter.clone(), audit_logger)),
+            cart_service: Arc::new(CartService::new(
+                cart_repository,
+                food_repository.clone(),
+                order_repository,
+                discount_repository,
+                event_emitter.clone(),
+                None,
+            )),
+            recommendation_service: Arc::new(RecommendationService::new(food_repository, SortOrder::None, false, None)),
+            config: Arc::new(ServerConfig {
+                foods_table_name: "test-foods".to_string(),
+                ..ServerConfig::from_env()
+            }),
+            admin_state: Arc::new(AdminState::new()),
+            readiness_checker: Arc::new(ReadinessChecker::new(
+                dynamodb_client,
+                ssm_client,
+                "test-foods".to_string(),
+                "test-carts".to_string(),
+                std::time::Duration::from_millis(200),
+                None,
+            )),
+            event_emitter,
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_route_returns_a_problem_json_envelope() {
+        let app = create_app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/does/not/exist")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers().get(axum::http::header::CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["title"], "Not Found");
+        assert_eq!(json["code"], "NOT_FOUND");
+        assert_eq!(json["instance"], "/does/not/exist");
+    }
+
+    #[tokio::test]
+    async fn head_metrics_returns_the_content_type_header_with_no_body() {
+        let app = create_app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("HEAD")
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; version=0.0.4");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn options_metrics_returns_the_allowed_methods() {
+        let app = create_app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("OPTIONS")
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.headers().get("allow").unwrap(), "GET, HEAD, OPTIONS");
+    }
+
+    #[tokio::test]
+    async fn metrics_route_reports_request_duration_labeled_by_route_template_not_raw_path() {
+        let app = create_app(test_state());
+
+        app.clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/foods/food-1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(text.contains(r#"http_request_duration_seconds_count{method="GET",route="/api/foods/:food_id"}"#));
+        assert!(
+            !text.contains(r#"http_request_duration_seconds_count{method="GET",route="/api/foods/food-1"}"#),
+            "the raw path should not appear as a route label value on this metric"
+        );
+    }
+
+    #[tokio::test]
+    async fn effective_config_route_returns_the_redacted_config() {
+        let app = create_app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/admin/config")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["foods_table_name"], "test-foods");
+    }
+
+    #[tokio::test]
+    async fn metrics_reset_zeroes_the_snapshot_returned_afterward() {
+        let app = create_app(test_state());
+        crate::metrics::observe_catalog_size_alert();
+
+        let reset_response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/admin/metrics/reset")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(reset_response.status(), StatusCode::OK);
+
+        let snapshot_response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/admin/metrics/snapshot")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(snapshot_response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(snapshot_response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["catalog_size_alerts"], 0);
+    }
+
+    #[tokio::test]
+    async fn ready_route_is_always_ok_when_the_otlp_dependency_is_not_required() {
+        let app = create_app(test_state());
+
+        let response = app
+            .oneshot(Request::builder().uri("/health/ready").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn ready_route_fails_when_the_otlp_dependency_is_required_and_unreachable() {
+        let mut state = test_state();
+        state.config = Arc::new(ServerConfig {
+            ready_requires_otlp: true,
+            otlp_endpoint: "127.0.0.1:1".to_string(),
+            otlp_probe_timeout_ms: 200,
+            ..(*state.config).clone()
+        });
+        let app = create_app(state);
+
+        let response = app
+            .oneshot(Request::builder().uri("/health/ready").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["otlp_reachable"], false);
+    }
+
+    #[tokio::test]
+    async fn ready_route_reports_events_as_healthy_by_default() {
+        let app = create_app(test_state());
+
+        let response = app
+            .oneshot(Request::builder().uri("/health/ready").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["events"]["status"], "healthy");
+        assert_eq!(json["events"]["failure_count"], 0);
+    }
+
+    #[tokio::test]
+    async fn health_live_route_behaves_like_health() {
+        let app = create_app(test_state());
+
+        let response = app
+            .oneshot(Request::builder().uri("/health/live").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], "ok");
+    }
+
+    #[tokio::test]
+    async fn health_status_route_delegates_to_the_same_readiness_logic_as_ready() {
+        let mut state = test_state();
+        state.config = Arc::new(ServerConfig {
+            ready_requires_otlp: true,
+            otlp_endpoint: "127.0.0.1:1".to_string(),
+            otlp_probe_timeout_ms: 200,
+            ..(*state.config).clone()
+        });
+        let app = create_app(state);
+
+        let response = app
+            .oneshot(Request::builder().uri("/health/status").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["otlp_reachable"], false);
+    }
+
+    #[tokio::test]
+    async fn ready_route_fails_when_aws_dependencies_are_required_and_unreachable() {
+        let mut state = test_state();
+        state.config = Arc::new(ServerConfig { ready_requires_aws: true, ..(*state.config).clone() });
+        let app = create_app(state);
+
+        let response = app
+            .oneshot(Request::builder().uri("/health/ready").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["dynamodb_reachable"], false);
+        assert_eq!(json["ssm_reachable"], false);
+    }
+
+    #[tokio::test]
+    async fn posting_a_request_body_records_its_size_in_the_histogram() {
+        let app = create_app(test_state());
+        let payload = "x".repeat(123);
+        let metric = crate::metrics::REQUEST_BODY_BYTES.with_label_values(&["/api/admin/foods"]);
+        let sum_before = metric.get_sample_sum();
+        let count_before = metric.get_sample_count();
+
+        let _ = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/admin/foods")
+                    .header("content-type", "application/json")
+                    .header("content-length", payload.len().to_string())
+                    .body(Body::from(payload.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(metric.get_sample_count(), count_before + 1);
+        assert_eq!(metric.get_sample_sum(), sum_before + payload.len() as f64);
+    }
+
+    #[tokio::test]
+    async fn a_body_over_the_configured_limit_is_rejected_with_413() {
+        let mut state = test_state();
+        state.config = Arc::new(ServerConfig {
+            max_request_body_bytes: 16,
+            ..(*state.config).clone()
+        });
+        let app = create_app(state);
+        let payload = "x".repeat(17);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/admin/foods")
+                    .header("content-type", "application/json")
+                    .header("content-length", payload.len().to_string())
+                    .body(Body::from(payload))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
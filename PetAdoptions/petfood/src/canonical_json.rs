@@ -0,0 +1,90 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+
+use crate::error::ApiError;
+
+/// Toggled once at startup from `PETFOOD_CANONICAL_JSON_ENABLED`; read by
+/// handlers deciding whether to respond via [`CanonicalJson`] or the
+/// default `Json`.
+static CANONICAL_JSON_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_canonical_json_enabled(enabled: bool) {
+    CANONICAL_JSON_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn canonical_json_enabled() -> bool {
+    CANONICAL_JSON_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Serializes `value` as canonical JSON: object keys come out sorted, so the
+/// output is byte-stable regardless of struct field order or map insertion
+/// order — useful for ETag-based caching, where a refactor or a HashMap's
+/// random iteration order shouldn't change the response's hash. This works
+/// by round-tripping through `serde_json::Value`, whose `Map` is
+/// `BTreeMap`-backed (the default when the `preserve_order` feature isn't
+/// enabled), so every object along the way comes out key-sorted.
+pub fn to_canonical_string<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&serde_json::to_value(value)?)
+}
+
+/// Wraps a response body to serialize as canonical JSON instead of axum's
+/// default `Json`, for cacheable read endpoints where a stable byte
+/// representation matters.
+pub struct CanonicalJson<T>(pub T);
+
+impl<T: Serialize> IntoResponse for CanonicalJson<T> {
+    fn into_response(self) -> Response {
+        match to_canonical_string(&self.0) {
+            Ok(body) => ([(header::CONTENT_TYPE, "application/json")], body).into_response(),
+            Err(err) => ApiError::Internal(err.to_string()).into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct FoodWithNutritionalInfo {
+        food_id: String,
+        nutritional_info: HashMap<String, f64>,
+    }
+
+    #[test]
+    fn canonical_output_is_stable_regardless_of_map_insertion_order() {
+        let mut first_order = HashMap::new();
+        first_order.insert("protein_pct".to_string(), 24.0);
+        first_order.insert("fat_pct".to_string(), 12.0);
+        first_order.insert("fiber_pct".to_string(), 3.5);
+
+        let mut second_order = HashMap::new();
+        second_order.insert("fiber_pct".to_string(), 3.5);
+        second_order.insert("protein_pct".to_string(), 24.0);
+        second_order.insert("fat_pct".to_string(), 12.0);
+
+        let first = to_canonical_string(&FoodWithNutritionalInfo {
+            food_id: "food-1".to_string(),
+            nutritional_info: first_order,
+        })
+        .unwrap();
+        let second = to_canonical_string(&FoodWithNutritionalInfo {
+            food_id: "food-1".to_string(),
+            nutritional_info: second_order,
+        })
+        .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(
+            first,
+            r#"{"food_id":"food-1","nutritional_info":{"fat_pct":12.0,"fiber_pct":3.5,"protein_pct":24.0}}"#
+        );
+    }
+}
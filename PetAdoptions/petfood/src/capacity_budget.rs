@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::ApiError;
+
+/// Tracks cumulative DynamoDB `ConsumedCapacity` (RCU) spent by one logical
+/// request, so a single deeply filtered scan can't quietly burn through the
+/// table's capacity. `f64` has no native atomic type, so the running total
+/// is stored as the bit pattern of an `f64` inside an `AtomicU64` and
+/// updated with a CAS loop.
+pub struct CapacityBudget {
+    cap: f64,
+    consumed_bits: AtomicU64,
+}
+
+impl CapacityBudget {
+    pub fn new(cap: f64) -> Self {
+        Self { cap, consumed_bits: AtomicU64::new(0f64.to_bits()) }
+    }
+
+    /// Adds `units` to the running total, failing with
+    /// `ApiError::BudgetExceeded` once the cumulative total exceeds the cap
+    /// — including on the call that tips it over, so the caller should
+    /// discard whatever it just read and abort the request rather than
+    /// return a partial result.
+    pub fn record(&self, units: f64) -> Result<(), ApiError> {
+        let mut current = self.consumed_bits.load(Ordering::Relaxed);
+        let total = loop {
+            let total = f64::from_bits(current) + units;
+            match self.consumed_bits.compare_exchange_weak(current, total.to_bits(), Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break total,
+                Err(observed) => current = observed,
+            }
+        };
+
+        if total > self.cap {
+            return Err(ApiError::BudgetExceeded(format!(
+                "request consumed {total:.2} RCU, exceeding the budget of {:.2} RCU",
+                self.cap
+            )));
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn consumed(&self) -> f64 {
+        f64::from_bits(self.consumed_bits.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_succeeds_while_cumulative_consumption_stays_under_the_cap() {
+        let budget = CapacityBudget::new(10.0);
+
+        assert!(budget.record(4.0).is_ok());
+        assert!(budget.record(4.0).is_ok());
+        assert_eq!(budget.consumed(), 8.0);
+    }
+
+    #[test]
+    fn record_fails_once_cumulative_consumption_exceeds_the_cap() {
+        let budget = CapacityBudget::new(10.0);
+        budget.record(6.0).unwrap();
+
+        let result = budget.record(6.0);
+
+        assert!(matches!(result, Err(ApiError::BudgetExceeded(_))));
+    }
+}
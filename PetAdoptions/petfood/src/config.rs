@@ -0,0 +1,927 @@
+use std::env;
+
+use crate::https_enforcement::HttpsEnforcementMode;
+use crate::models::{SortOrder, StockVisibility};
+use crate::trust::TrustedProxyAllowList;
+
+/// Matches axum's own built-in `DefaultBodyLimit`, so leaving
+/// `PETFOOD_MAX_REQUEST_BODY_BYTES` unset doesn't change behavior from
+/// before this limit was made configurable.
+const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Runtime configuration for the petfood service, resolved from environment
+/// variables with SSM Parameter Store as the deployment-time source of truth
+/// (parameters live under the `/petfood/` path, mirroring the `/eks/petsite/`
+/// convention used by the rest of the stack).
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub port: u16,
+    pub foods_table_name: String,
+    pub carts_table_name: String,
+    /// `PETFOOD_CART_TTL_DAYS`: how many days of inactivity `DynamoDbCartRepository`
+    /// keeps a cart before treating it as expired. Refreshed on every
+    /// `put_cart`, so only carts that truly sit untouched expire.
+    pub cart_ttl_days: i64,
+    pub orders_table_name: String,
+    /// Backs `AuditLogger`: every food field change (e.g. a price update) is
+    /// recorded here, queryable by `food_id` via a GSI, for `GET
+    /// /api/admin/foods/:food_id/history`.
+    pub audit_table_name: String,
+    /// Backs `DiscountRepository`: coupon codes `CartService::apply_coupon`
+    /// validates against, keyed by `code`.
+    pub discounts_table_name: String,
+    pub event_bus_name: String,
+    pub aws_region: Option<String>,
+    pub recommendation_default_sort: SortOrder,
+    /// When enabled, an empty `GET /api/recommendations/:pet_type` result
+    /// carries a `reason` field instead of a bare empty array.
+    pub recommendation_empty_reason_enabled: bool,
+    pub otel_metrics_enabled: bool,
+    pub multi_tenant_tables_enabled: bool,
+    pub warm_connections_enabled: bool,
+    /// Consulted by `middleware::client_ip` to decide whether an inbound
+    /// `X-Forwarded-For` came from a trusted reverse proxy.
+    pub trusted_proxy_allow_list: TrustedProxyAllowList,
+    /// `PETFOOD_CORS_ALLOWED_ORIGIN`: the value `cors_middleware` stamps
+    /// onto `Access-Control-Allow-Origin`. Defaults to `*`, matching this
+    /// being a public, unauthenticated demo API with no cookies to protect.
+    pub cors_allowed_origin: String,
+    pub price_as_string: bool,
+    pub slow_request_threshold_ms: u64,
+    pub require_https: bool,
+    pub https_enforcement_mode: HttpsEnforcementMode,
+    /// `None` leaves concurrent event emission unbounded.
+    pub event_max_concurrency: Option<usize>,
+    pub event_shed_when_saturated: bool,
+    /// When enabled, cacheable read endpoints respond with canonical
+    /// (sorted-key) JSON so ETags stay stable across deploys and map field
+    /// reorderings.
+    pub canonical_json_enabled: bool,
+    /// Caps how many `pet_type` values `GET /api/foods` accepts in one
+    /// request, bounding the fan-out of per-value filtering.
+    pub max_pet_type_filters: usize,
+    /// Caps how many `exclude_ingredients` values `GET /api/foods` accepts
+    /// in one request, for the same reason as `max_pet_type_filters`.
+    pub max_exclude_ingredients_filters: usize,
+    /// Caps how many `category` values `GET /api/foods` accepts in one
+    /// request, for the same reason as `max_pet_type_filters`.
+    pub max_category_filters: usize,
+    /// Hard cap on `?limit=` for `GET /api/foods/ingredients`, regardless of
+    /// what the caller asks for.
+    pub max_ingredients_list_limit: usize,
+    /// How often the background task polls `describe_table` for the
+    /// `petfood_foods_table_item_count` / `petfood_carts_table_item_count`
+    /// gauges. DynamoDB only recomputes `ItemCount` roughly every six
+    /// hours, so polling much more often than that just repeats the same
+    /// number.
+    pub table_item_count_interval_secs: u64,
+    /// When set, a repeat `POST /api/cart/:user_id/items` for the same food
+    /// within this window returns the earlier call's result instead of
+    /// adding again, collapsing double-clicks on "add to cart". `None`
+    /// (the default) disables de-duplication.
+    pub add_dedupe_window_ms: Option<u64>,
+    /// When set, `GET /api/foods/:food_id` and `GET /api/foods` fire
+    /// `FoodEvent::missing_image_viewed` for an image-less food at most once
+    /// per food within this window, instead of on every read. `None` (the
+    /// default) disables the event entirely.
+    pub missing_image_emit_window_ms: Option<u64>,
+    /// When set, `CartService::add_item` caches a food lookup for this long,
+    /// so adding several items in quick succession doesn't re-fetch the same
+    /// food on every add. Kept short and separate from
+    /// `checkout_cart`'s consistent read, which always bypasses this cache.
+    /// `None` (the default) disables the cache entirely.
+    pub cart_food_lookup_cache_ttl_ms: Option<u64>,
+    /// When set, `GET /api/recommendations/:pet_type` results are cached
+    /// per `(pet_type, sort, tenant_id)` for this long before being
+    /// recomputed from the repository. `None` (the default) disables
+    /// caching entirely.
+    pub recommendation_cache_ttl_ms: Option<u64>,
+    /// How many `get_recommendation_stats_for_all_pet_types` per-pet-type
+    /// queries may run concurrently via `buffer_unordered`, bounding the
+    /// fan-out a single dashboard request can push onto the repository.
+    pub recommendation_stats_fanout_concurrency: usize,
+    /// Total time `get_recommendation_stats_for_all_pet_types` allows
+    /// itself before returning whatever pet types finished in time with
+    /// `partial: true`, rather than waiting indefinitely on a slow query.
+    /// `None` (the default) disables the budget — the call waits for every
+    /// pet type.
+    pub recommendation_stats_time_budget_ms: Option<u64>,
+    /// Not yet consulted by anything — reserved for the internal service
+    /// calls (e.g. a future PetSite-to-petfood admin path) that will need to
+    /// authenticate with something other than network placement alone.
+    /// Resolved from SSM Parameter Store at deploy time, so `GET
+    /// /api/admin/config` must mask it rather than echo it back.
+    pub internal_api_key: Option<String>,
+    /// When enabled, `GET /api/foods` hides out-of-stock foods unless the
+    /// caller explicitly passes `?in_stock_only=false`. Disabled by default
+    /// so existing storefronts keep seeing the full catalog.
+    pub hide_out_of_stock_by_default: bool,
+    /// Bounds each startup connectivity probe (SSM, DynamoDB) so a hung
+    /// endpoint fails fast instead of riding the SDK's 60s global operation
+    /// timeout and delaying boot.
+    pub startup_probe_timeout_ms: u64,
+    /// When the configured `event_bus_name` is confirmed missing at
+    /// startup: `true` fails startup outright; `false` (the default) logs a
+    /// warning and disables event emission for the life of the process,
+    /// turning what would otherwise be a per-request
+    /// `ResourceNotFoundException` into a one-time, obvious signal.
+    pub event_bus_strict: bool,
+    /// How many `BatchWriteItem`-sized chunks `POST /api/admin/seed` writes
+    /// concurrently. Higher values finish a large seed faster at the cost of
+    /// more in-flight DynamoDB requests.
+    pub seed_batch_concurrency: usize,
+    /// The content-quality gate `POST /api/admin/seed` applies to each
+    /// record before writing it: a description shorter than this many
+    /// characters is rejected and reported in the seed response instead of
+    /// being written.
+    pub seed_min_description_length: usize,
+    /// Case-insensitive substrings (e.g. "TODO", "lorem ipsum") that, if
+    /// found anywhere in a seed record's description, reject it the same
+    /// way `seed_min_description_length` does — external seed files
+    /// sometimes carry placeholder junk that a non-blank description alone
+    /// wouldn't catch.
+    pub seed_banned_placeholder_substrings: Vec<String>,
+    /// When enabled, `DELETE /api/admin/foods/:food_id` refuses to remove
+    /// the last remaining food instead of emptying the catalog. A safety
+    /// net for demo environments; off by default so scripted teardowns
+    /// that intentionally clear the catalog keep working.
+    pub prevent_empty_catalog: bool,
+    /// Domains `CreateFoodRequest::validate` allows an absolute
+    /// `image_path` URL to point at. Empty means nothing is allow-listed,
+    /// so every absolute URL is rejected; relative paths are always
+    /// allowed regardless of this list.
+    pub allowed_image_domains: Vec<String>,
+    /// When set, `DELETE /api/admin/foods/:food_id` fires a
+    /// `CatalogSizeAlert` event (and increments a metric) whenever that one
+    /// deletion drops the active food count by more than this percentage.
+    /// `None` (the default) disables the alert.
+    pub catalog_size_alert_drop_threshold_percent: Option<f64>,
+    /// When enabled, `GET /health/ready` fails readiness unless the OTLP
+    /// collector at `otlp_endpoint` is TCP-reachable, so early traces
+    /// aren't dropped by routing traffic to an instance that can't export
+    /// them yet. Disabled by default, since most deployments don't run a
+    /// collector at all.
+    pub ready_requires_otlp: bool,
+    /// `host:port` the OTLP readiness probe TCP-connects to.
+    pub otlp_endpoint: String,
+    /// Bounds the `ready_requires_otlp` connectivity probe, same rationale
+    /// as `startup_probe_timeout_ms`.
+    pub otlp_probe_timeout_ms: u64,
+    /// When enabled, `GET /health/ready` (and `/health/status`) also fails
+    /// readiness unless DynamoDB (`describe_table` against both
+    /// `foods_table_name` and `carts_table_name`) and SSM
+    /// (`describe_parameters`) are reachable. Disabled by default, same
+    /// rationale as `ready_requires_otlp` — most deployments would rather a
+    /// transient AWS blip not turn into a traffic black hole.
+    pub ready_requires_aws: bool,
+    /// Bounds each `ready_requires_aws` dependency probe, same rationale as
+    /// `startup_probe_timeout_ms`.
+    pub readiness_probe_timeout_ms: u64,
+    /// When set, `ready_requires_aws`'s DynamoDB/SSM checks are cached for
+    /// this long, so a load balancer polling `/health/ready` every few
+    /// seconds doesn't repeat the same AWS calls on every poll. `None` (the
+    /// default) probes on every request.
+    pub readiness_cache_ttl_ms: Option<u64>,
+    /// When set, `GET /api/foods` aborts with `ApiError::BudgetExceeded`
+    /// (HTTP 429) once the scan backing it reports cumulative consumed RCU
+    /// above this cap — a backstop against a single deeply filtered request
+    /// blowing the table's capacity budget. `None` (the default) disables
+    /// enforcement.
+    pub capacity_budget_rcu: Option<f64>,
+    /// How `GET /api/foods` and `GET /api/foods/:food_id` render
+    /// `stock_quantity`: `exact` (the default) passes it through unchanged,
+    /// `coarse` replaces it with an in_stock/low_stock/out bucket, and
+    /// `hidden` omits it entirely. Admin endpoints always return the exact
+    /// count regardless of this setting.
+    pub stock_visibility: StockVisibility,
+    /// The `stock_quantity` at or below which `coarse` `stock_visibility`
+    /// reports `low_stock` instead of `in_stock`.
+    pub low_stock_threshold: u32,
+    /// Hard cap on how many items `POST /api/admin/seed` or `POST
+    /// /api/admin/cleanup` will process in one invocation. Both operations
+    /// check this before doing any writes, rejecting the whole call with
+    /// `ApiError::Validation` rather than partially processing an
+    /// over-limit batch.
+    pub max_seed_items: usize,
+    /// When enabled, `FoodService::create_food` skips field validation for
+    /// `CreationSource::Seeding` records, trusting the built-in seed data's
+    /// correctness instead of re-checking it on every seed. Records created
+    /// via the API always validate regardless of this setting. Off by
+    /// default.
+    pub trust_seed: bool,
+    /// When set, `CartService::add_item` emits `FoodEvent::high_value_cart`
+    /// (and increments a metric) the first time a cart's total crosses this
+    /// many cents — fired once per crossing, not on every subsequent add
+    /// that stays above it. `None` (the default) disables the check.
+    pub high_value_cart_threshold_cents: Option<i64>,
+    /// The currency `Food::price_cents` (and `CreateFoodRequest::price_cents`)
+    /// is denominated in. `Food::to_response` selects this when the caller's
+    /// `?currency=`/`Accept-Language` doesn't resolve to a currency the food
+    /// has a `prices` entry for.
+    pub default_currency: String,
+    /// `PETFOOD_ANALYTICS_EVENTS`: when enabled, `FoodService::get_food`,
+    /// `CartService::add_item`, and `CartService::checkout_cart` each fire a
+    /// lightweight analytics event (distinct from the domain events those
+    /// same calls already emit) on success, for a funnel-analytics demo.
+    /// `false` by default.
+    pub analytics_events_enabled: bool,
+    /// Caps how many distinct values a single metrics label (e.g.
+    /// `petfood_request_body_bytes`'s `route`) is allowed to accumulate
+    /// before further unseen values collapse into an `other` bucket instead
+    /// of their own series — guards against an unbounded label (like a raw,
+    /// per-entity path) silently exploding `/metrics`'s cardinality.
+    pub metrics_max_label_values: usize,
+    /// `PETFOOD_EVENT_IDEMPOTENCY_TABLE`: when set, `EventEmitter` dedupes
+    /// emissions by `food_id` + event type against this DynamoDB table (see
+    /// `EventEmitter::with_idempotency_table`). `None` falls back to
+    /// always-emit.
+    pub event_idempotency_table_name: Option<String>,
+    /// `PETFOOD_EVENT_RETRY_ATTEMPTS`: how many times `EventEmitter` retries
+    /// a `put_events` call that fails with a retryable (throttling /
+    /// internal) error before giving up. `0` preserves the original
+    /// single-attempt behavior.
+    pub event_retry_attempts: u32,
+    /// `PETFOOD_EVENT_RETRY_TIMEOUT_SECONDS`: total wall-clock budget for a
+    /// single event's retries, including backoff sleeps — once exceeded,
+    /// `emit_event` stops retrying even if attempts remain.
+    pub event_retry_timeout_seconds: u64,
+    /// `PETFOOD_RATE_LIMIT_RPS`: sustained requests per second
+    /// `rate_limit_middleware` allows per client IP before rejecting with
+    /// `429`. `None` (the default) disables rate limiting entirely.
+    pub rate_limit_rps: Option<f64>,
+    /// `PETFOOD_RATE_LIMIT_BURST`: the token bucket's capacity, i.e. how many
+    /// requests a client can fire in a quick burst before being throttled
+    /// down to the steady `rate_limit_rps` rate. Only consulted when
+    /// `rate_limit_rps` is set.
+    pub rate_limit_burst: u32,
+    /// `PETFOOD_MAX_REQUEST_BODY_BYTES`: the largest request body axum will
+    /// read before rejecting with `413 Payload Too Large`, enforced against
+    /// `Content-Length` (or the streamed byte count for chunked bodies) by
+    /// axum's own `DefaultBodyLimit` layer, so an oversized body is rejected
+    /// without ever being buffered into memory.
+    pub max_request_body_bytes: usize,
+    /// `PETFOOD_SSM_CACHE_TTL_SECONDS`: how long an
+    /// `ssm_cache::SsmParameterCache` entry stays valid before the next
+    /// lookup re-fetches it from SSM.
+    pub ssm_cache_ttl_seconds: u64,
+    /// `PETFOOD_SHUTDOWN_DRAIN_SECONDS`: once a shutdown signal arrives, how
+    /// long `shutdown::serve_with_graceful_shutdown` waits for in-flight
+    /// requests to finish before dropping them and exiting anyway.
+    pub shutdown_drain_seconds: u64,
+}
+
+/// Resolves the DynamoDB table a tenant's data lives in. Disabled by
+/// default so a single table backs every caller; when enabled, each tenant
+/// gets an isolated `{base_table_name}-{tenant_id}` table.
+pub fn resolve_table_name(base_table_name: &str, tenant_id: Option<&str>, enabled: bool) -> String {
+    match (enabled, tenant_id) {
+        (true, Some(tenant_id)) if !tenant_id.is_empty() => format!("{base_table_name}-{tenant_id}"),
+        _ => base_table_name.to_string(),
+    }
+}
+
+impl ServerConfig {
+    pub fn from_env() -> Self {
+        Self {
+            port: env::var("PETFOOD_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(8080),
+            foods_table_name: env::var("PETFOOD_FOODS_TABLE_NAME")
+                .unwrap_or_else(|_| "petfood-foods".to_string()),
+            carts_table_name: env::var("PETFOOD_CARTS_TABLE_NAME")
+                .unwrap_or_else(|_| "petfood-carts".to_string()),
+            cart_ttl_days: env::var("PETFOOD_CART_TTL_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            orders_table_name: env::var("PETFOOD_ORDERS_TABLE_NAME")
+                .unwrap_or_else(|_| "petfood-orders".to_string()),
+            audit_table_name: env::var("PETFOOD_AUDIT_TABLE_NAME")
+                .unwrap_or_else(|_| "petfood-audit".to_string()),
+            discounts_table_name: env::var("PETFOOD_DISCOUNTS_TABLE_NAME")
+                .unwrap_or_else(|_| "petfood-discounts".to_string()),
+            event_bus_name: env::var("PETFOOD_EVENT_BUS_NAME")
+                .unwrap_or_else(|_| "petfood-events".to_string()),
+            aws_region: env::var("AWS_REGION").ok(),
+            recommendation_default_sort: env::var("PETFOOD_RECOMMENDATION_DEFAULT_SORT")
+                .ok()
+                .and_then(|v| SortOrder::parse(&v).ok())
+                .unwrap_or(SortOrder::None),
+            recommendation_empty_reason_enabled: env::var("PETFOOD_RECOMMENDATION_EMPTY_REASON_ENABLED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            otel_metrics_enabled: env::var("PETFOOD_OTEL_METRICS_ENABLED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            multi_tenant_tables_enabled: env::var("PETFOOD_MULTI_TENANT_TABLES_ENABLED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            warm_connections_enabled: env::var("PETFOOD_WARM_CONNECTIONS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            trusted_proxy_allow_list: trusted_proxy_allow_list_from_env(),
+            cors_allowed_origin: env::var("PETFOOD_CORS_ALLOWED_ORIGIN").unwrap_or_else(|_| "*".to_string()),
+            price_as_string: env::var("PETFOOD_PRICE_AS_STRING")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            slow_request_threshold_ms: env::var("PETFOOD_SLOW_REQUEST_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
+            require_https: env::var("PETFOOD_REQUIRE_HTTPS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            https_enforcement_mode: env::var("PETFOOD_HTTPS_ENFORCEMENT_MODE")
+                .ok()
+                .map(|v| HttpsEnforcementMode::parse(&v))
+                .unwrap_or(HttpsEnforcementMode::Reject),
+            event_max_concurrency: env::var("PETFOOD_EVENT_MAX_CONCURRENCY")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            event_shed_when_saturated: env::var("PETFOOD_EVENT_SHED_WHEN_SATURATED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            canonical_json_enabled: env::var("PETFOOD_CANONICAL_JSON_ENABLED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            max_pet_type_filters: env::var("PETFOOD_MAX_PET_TYPE_FILTERS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            max_exclude_ingredients_filters: env::var("PETFOOD_MAX_EXCLUDE_INGREDIENTS_FILTERS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(20),
+            max_category_filters: env::var("PETFOOD_MAX_CATEGORY_FILTERS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            max_ingredients_list_limit: env::var("PETFOOD_MAX_INGREDIENTS_LIST_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100),
+            table_item_count_interval_secs: env::var("PETFOOD_TABLE_ITEM_COUNT_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(21_600),
+            add_dedupe_window_ms: env::var("PETFOOD_ADD_DEDUPE_MS").ok().and_then(|v| v.parse().ok()),
+            missing_image_emit_window_ms: env::var("PETFOOD_MISSING_IMAGE_EMIT_WINDOW_MS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            cart_food_lookup_cache_ttl_ms: env::var("PETFOOD_CART_FOOD_LOOKUP_CACHE_TTL_MS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            recommendation_cache_ttl_ms: env::var("PETFOOD_RECOMMENDATION_CACHE_TTL_MS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            recommendation_stats_fanout_concurrency: env::var("PETFOOD_RECOMMENDATION_STATS_FANOUT_CONCURRENCY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4),
+            recommendation_stats_time_budget_ms: env::var("PETFOOD_RECOMMENDATION_STATS_TIME_BUDGET_MS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            internal_api_key: env::var("PETFOOD_INTERNAL_API_KEY").ok(),
+            hide_out_of_stock_by_default: env::var("PETFOOD_HIDE_OUT_OF_STOCK_DEFAULT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            startup_probe_timeout_ms: env::var("PETFOOD_STARTUP_PROBE_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5000),
+            event_bus_strict: env::var("PETFOOD_EVENT_BUS_STRICT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            seed_batch_concurrency: env::var("PETFOOD_SEED_BATCH_CONCURRENCY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4),
+            seed_min_description_length: env::var("PETFOOD_SEED_MIN_DESCRIPTION_LENGTH")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(15),
+            seed_banned_placeholder_substrings: seed_banned_placeholder_substrings_from_env(),
+            prevent_empty_catalog: env::var("PETFOOD_PREVENT_EMPTY_CATALOG")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            allowed_image_domains: allowed_image_domains_from_env(),
+            catalog_size_alert_drop_threshold_percent: env::var("PETFOOD_CATALOG_SIZE_ALERT_DROP_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            ready_requires_otlp: env::var("PETFOOD_READY_REQUIRES_OTLP")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            otlp_endpoint: env::var("PETFOOD_OTLP_ENDPOINT").unwrap_or_else(|_| "localhost:4317".to_string()),
+            otlp_probe_timeout_ms: env::var("PETFOOD_OTLP_PROBE_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
+            ready_requires_aws: env::var("PETFOOD_READY_REQUIRES_AWS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            readiness_probe_timeout_ms: env::var("PETFOOD_READINESS_PROBE_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2000),
+            readiness_cache_ttl_ms: env::var("PETFOOD_READINESS_CACHE_TTL_MS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            capacity_budget_rcu: env::var("PETFOOD_CAPACITY_BUDGET_RCU").ok().and_then(|v| v.parse().ok()),
+            stock_visibility: env::var("PETFOOD_STOCK_VISIBILITY")
+                .ok()
+                .map(|v| StockVisibility::parse(&v))
+                .unwrap_or(StockVisibility::Exact),
+            low_stock_threshold: env::var("PETFOOD_LOW_STOCK_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            max_seed_items: env::var("PETFOOD_MAX_SEED_ITEMS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5000),
+            trust_seed: env::var("PETFOOD_TRUST_SEED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            high_value_cart_threshold_cents: env::var("PETFOOD_HIGH_VALUE_CART_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            default_currency: env::var("PETFOOD_DEFAULT_CURRENCY")
+                .unwrap_or_else(|_| "USD".to_string())
+                .to_ascii_uppercase(),
+            analytics_events_enabled: env::var("PETFOOD_ANALYTICS_EVENTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            metrics_max_label_values: env::var("PETFOOD_METRICS_MAX_LABEL_VALUES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(200),
+            event_idempotency_table_name: env::var("PETFOOD_EVENT_IDEMPOTENCY_TABLE").ok(),
+            event_retry_attempts: env::var("PETFOOD_EVENT_RETRY_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            event_retry_timeout_seconds: env::var("PETFOOD_EVENT_RETRY_TIMEOUT_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            rate_limit_rps: env::var("PETFOOD_RATE_LIMIT_RPS").ok().and_then(|v| v.parse().ok()),
+            rate_limit_burst: env::var("PETFOOD_RATE_LIMIT_BURST")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            max_request_body_bytes: env::var("PETFOOD_MAX_REQUEST_BODY_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_REQUEST_BODY_BYTES),
+            ssm_cache_ttl_seconds: env::var("PETFOOD_SSM_CACHE_TTL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            shutdown_drain_seconds: env::var("PETFOOD_SHUTDOWN_DRAIN_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        }
+    }
+
+    /// Which SSM-backed parameters resolved from their environment
+    /// variable, for structured startup logging via
+    /// `log_ssm_parameter_resolutions` — deliberately never carries the
+    /// resolved value itself, only whether one was present, mirroring the
+    /// masking `redacted()` applies for `GET /api/admin/config`.
+    pub fn ssm_parameter_resolutions(&self) -> Vec<SsmParameterResolution> {
+        vec![SsmParameterResolution {
+            parameter: "internal_api_key",
+            resolved_path: "/petfood/internal_api_key",
+            env_var: "PETFOOD_INTERNAL_API_KEY",
+            resolved: self.internal_api_key.is_some(),
+        }]
+    }
+
+    /// The effective config with anything resolved from a secret store
+    /// masked, for `GET /api/admin/config` — safe to hand to an instructor
+    /// debugging a deployment without leaking credentials.
+    pub fn redacted(&self) -> RedactedConfig {
+        RedactedConfig {
+            port: self.port,
+            foods_table_name: self.foods_table_name.clone(),
+            carts_table_name: self.carts_table_name.clone(),
+            cart_ttl_days: self.cart_ttl_days,
+            orders_table_name: self.orders_table_name.clone(),
+            audit_table_name: self.audit_table_name.clone(),
+            discounts_table_name: self.discounts_table_name.clone(),
+            event_bus_name: self.event_bus_name.clone(),
+            aws_region: self.aws_region.clone(),
+            recommendation_default_sort: format!("{:?}", self.recommendation_default_sort),
+            recommendation_empty_reason_enabled: self.recommendation_empty_reason_enabled,
+            otel_metrics_enabled: self.otel_metrics_enabled,
+            multi_tenant_tables_enabled: self.multi_tenant_tables_enabled,
+            warm_connections_enabled: self.warm_connections_enabled,
+            cors_allowed_origin: self.cors_allowed_origin.clone(),
+            price_as_string: self.price_as_string,
+            slow_request_threshold_ms: self.slow_request_threshold_ms,
+            require_https: self.require_https,
+            https_enforcement_mode: format!("{:?}", self.https_enforcement_mode),
+            event_max_concurrency: self.event_max_concurrency,
+            event_shed_when_saturated: self.event_shed_when_saturated,
+            canonical_json_enabled: self.canonical_json_enabled,
+            max_pet_type_filters: self.max_pet_type_filters,
+            max_exclude_ingredients_filters: self.max_exclude_ingredients_filters,
+            max_category_filters: self.max_category_filters,
+            max_ingredients_list_limit: self.max_ingredients_list_limit,
+            table_item_count_interval_secs: self.table_item_count_interval_secs,
+            add_dedupe_window_ms: self.add_dedupe_window_ms,
+            missing_image_emit_window_ms: self.missing_image_emit_window_ms,
+            cart_food_lookup_cache_ttl_ms: self.cart_food_lookup_cache_ttl_ms,
+            recommendation_cache_ttl_ms: self.recommendation_cache_ttl_ms,
+            recommendation_stats_fanout_concurrency: self.recommendation_stats_fanout_concurrency,
+            recommendation_stats_time_budget_ms: self.recommendation_stats_time_budget_ms,
+            internal_api_key: self.internal_api_key.as_ref().map(|_| REDACTED_PLACEHOLDER.to_string()),
+            event_bus_strict: self.event_bus_strict,
+            seed_batch_concurrency: self.seed_batch_concurrency,
+            seed_min_description_length: self.seed_min_description_length,
+            seed_banned_placeholder_substrings: self.seed_banned_placeholder_substrings.clone(),
+            prevent_empty_catalog: self.prevent_empty_catalog,
+            allowed_image_domains: self.allowed_image_domains.clone(),
+            catalog_size_alert_drop_threshold_percent: self.catalog_size_alert_drop_threshold_percent,
+            ready_requires_otlp: self.ready_requires_otlp,
+            otlp_endpoint: self.otlp_endpoint.clone(),
+            otlp_probe_timeout_ms: self.otlp_probe_timeout_ms,
+            ready_requires_aws: self.ready_requires_aws,
+            readiness_probe_timeout_ms: self.readiness_probe_timeout_ms,
+            readiness_cache_ttl_ms: self.readiness_cache_ttl_ms,
+            capacity_budget_rcu: self.capacity_budget_rcu,
+            stock_visibility: format!("{:?}", self.stock_visibility),
+            low_stock_threshold: self.low_stock_threshold,
+            max_seed_items: self.max_seed_items,
+            trust_seed: self.trust_seed,
+            high_value_cart_threshold_cents: self.high_value_cart_threshold_cents,
+            default_currency: self.default_currency.clone(),
+            analytics_events_enabled: self.analytics_events_enabled,
+            metrics_max_label_values: self.metrics_max_label_values,
+            event_idempotency_table_name: self.event_idempotency_table_name.clone(),
+            event_retry_attempts: self.event_retry_attempts,
+            event_retry_timeout_seconds: self.event_retry_timeout_seconds,
+            rate_limit_rps: self.rate_limit_rps,
+            rate_limit_burst: self.rate_limit_burst,
+            max_request_body_bytes: self.max_request_body_bytes,
+            ssm_cache_ttl_seconds: self.ssm_cache_ttl_seconds,
+            shutdown_drain_seconds: self.shutdown_drain_seconds,
+        }
+    }
+
+    /// Builds the payload for the one-time `FoodEvent::service_started`
+    /// emitted at startup — see `emit_service_started_event` in `main.rs`.
+    /// Only the handful of boolean toggles a fleet-wide drift collector
+    /// would care about are included, not every field `redacted()` exposes.
+    pub fn service_started_summary(&self) -> crate::events::ServiceStartedSummary {
+        crate::events::ServiceStartedSummary {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            region: self.aws_region.clone(),
+            feature_flags: std::collections::BTreeMap::from([
+                ("analytics_events_enabled".to_string(), self.analytics_events_enabled),
+                ("canonical_json_enabled".to_string(), self.canonical_json_enabled),
+                ("multi_tenant_tables_enabled".to_string(), self.multi_tenant_tables_enabled),
+                ("otel_metrics_enabled".to_string(), self.otel_metrics_enabled),
+                ("require_https".to_string(), self.require_https),
+                ("warm_connections_enabled".to_string(), self.warm_connections_enabled),
+            ]),
+            redacted_table_names: std::collections::BTreeMap::from([
+                ("audit_table_name".to_string(), self.audit_table_name.clone()),
+                ("carts_table_name".to_string(), self.carts_table_name.clone()),
+                ("foods_table_name".to_string(), self.foods_table_name.clone()),
+                ("orders_table_name".to_string(), self.orders_table_name.clone()),
+            ]),
+        }
+    }
+}
+
+/// One SSM-backed parameter's resolution outcome, for structured startup
+/// logging — see `ServerConfig::ssm_parameter_resolutions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsmParameterResolution {
+    pub parameter: &'static str,
+    pub resolved_path: &'static str,
+    pub env_var: &'static str,
+    pub resolved: bool,
+}
+
+/// Reports each SSM-backed parameter's resolution as a structured `info!`
+/// event, once the tracing subscriber is up, in place of a `println!` that
+/// would bypass it and never reach CloudWatch as JSON. Never logs the
+/// resolved value itself — only `resolved_path`/`env_var`/whether a value
+/// was present, the same masking `ServerConfig::redacted` applies for `GET
+/// /api/admin/config`.
+pub fn log_ssm_parameter_resolutions(config: &ServerConfig) {
+    for resolution in config.ssm_parameter_resolutions() {
+        tracing::info!(
+            parameter = resolution.parameter,
+            resolved_path = resolution.resolved_path,
+            env_var = resolution.env_var,
+            resolved = resolution.resolved,
+            "ssm_parameter_resolved"
+        );
+    }
+}
+
+const REDACTED_PLACEHOLDER: &str = "***redacted***";
+
+/// `GET /api/admin/config`'s response shape: every structural field of
+/// `ServerConfig` passed through as-is, with anything resolved from a
+/// secret store masked to [`REDACTED_PLACEHOLDER`] instead of its real
+/// value. Enum fields are rendered via `Debug` rather than given their own
+/// `Serialize` impl, since nothing else in the codebase needs them
+/// serialized.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RedactedConfig {
+    pub port: u16,
+    pub foods_table_name: String,
+    pub carts_table_name: String,
+    pub cart_ttl_days: i64,
+    pub orders_table_name: String,
+    pub audit_table_name: String,
+    /// Backs `DiscountRepository`: coupon codes `CartService::apply_coupon`
+    /// validates against, keyed by `code`.
+    pub discounts_table_name: String,
+    pub event_bus_name: String,
+    pub aws_region: Option<String>,
+    pub recommendation_default_sort: String,
+    pub recommendation_empty_reason_enabled: bool,
+    pub otel_metrics_enabled: bool,
+    pub multi_tenant_tables_enabled: bool,
+    pub warm_connections_enabled: bool,
+    pub cors_allowed_origin: String,
+    pub price_as_string: bool,
+    pub slow_request_threshold_ms: u64,
+    pub require_https: bool,
+    pub https_enforcement_mode: String,
+    pub event_max_concurrency: Option<usize>,
+    pub event_shed_when_saturated: bool,
+    pub canonical_json_enabled: bool,
+    pub max_pet_type_filters: usize,
+    pub max_exclude_ingredients_filters: usize,
+    pub max_category_filters: usize,
+    pub max_ingredients_list_limit: usize,
+    pub table_item_count_interval_secs: u64,
+    pub add_dedupe_window_ms: Option<u64>,
+    pub missing_image_emit_window_ms: Option<u64>,
+    pub cart_food_lookup_cache_ttl_ms: Option<u64>,
+    pub recommendation_cache_ttl_ms: Option<u64>,
+    pub recommendation_stats_fanout_concurrency: usize,
+    pub recommendation_stats_time_budget_ms: Option<u64>,
+    pub internal_api_key: Option<String>,
+    pub event_bus_strict: bool,
+    pub seed_batch_concurrency: usize,
+    pub seed_min_description_length: usize,
+    pub seed_banned_placeholder_substrings: Vec<String>,
+    pub prevent_empty_catalog: bool,
+    pub allowed_image_domains: Vec<String>,
+    pub catalog_size_alert_drop_threshold_percent: Option<f64>,
+    pub ready_requires_otlp: bool,
+    pub otlp_endpoint: String,
+    pub otlp_probe_timeout_ms: u64,
+    pub ready_requires_aws: bool,
+    pub readiness_probe_timeout_ms: u64,
+    pub readiness_cache_ttl_ms: Option<u64>,
+    pub capacity_budget_rcu: Option<f64>,
+    pub stock_visibility: String,
+    pub low_stock_threshold: u32,
+    pub max_seed_items: usize,
+    pub trust_seed: bool,
+    pub high_value_cart_threshold_cents: Option<i64>,
+    pub default_currency: String,
+    pub analytics_events_enabled: bool,
+    pub metrics_max_label_values: usize,
+    pub event_idempotency_table_name: Option<String>,
+    pub event_retry_attempts: u32,
+    pub event_retry_timeout_seconds: u64,
+    pub rate_limit_rps: Option<f64>,
+    pub rate_limit_burst: u32,
+    pub max_request_body_bytes: usize,
+    pub ssm_cache_ttl_seconds: u64,
+    pub shutdown_drain_seconds: u64,
+}
+
+/// Parses `PETFOOD_TRUSTED_PROXY_CIDRS` (a comma-separated list of `a.b.c.d/n`
+/// entries). Falls back to an allow-list that trusts nothing, both when the
+/// variable is unset and when it fails to parse — `middleware::client_ip`
+/// should never honor an inbound `X-Forwarded-For` on the strength of a
+/// misconfigured value.
+fn trusted_proxy_allow_list_from_env() -> TrustedProxyAllowList {
+    let Ok(raw) = env::var("PETFOOD_TRUSTED_PROXY_CIDRS") else {
+        return TrustedProxyAllowList::default();
+    };
+
+    let entries: Vec<String> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    TrustedProxyAllowList::parse(&entries).unwrap_or_else(|err| {
+        tracing::warn!(error = %err, "ignoring invalid PETFOOD_TRUSTED_PROXY_CIDRS");
+        TrustedProxyAllowList::default()
+    })
+}
+
+/// Parses `PETFOOD_ALLOWED_IMAGE_DOMAINS` (a comma-separated list of
+/// hostnames). Unset or empty means nothing is allow-listed, so absolute
+/// image URLs are rejected outright.
+fn allowed_image_domains_from_env() -> Vec<String> {
+    env::var("PETFOOD_ALLOWED_IMAGE_DOMAINS")
+        .ok()
+        .map(|raw| {
+            raw.split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parses `PETFOOD_SEED_BANNED_PLACEHOLDER_SUBSTRINGS` (a comma-separated
+/// list). Unset falls back to a couple of common placeholder markers so the
+/// quality gate does something useful out of the box.
+fn seed_banned_placeholder_substrings_from_env() -> Vec<String> {
+    env::var("PETFOOD_SEED_BANNED_PLACEHOLDER_SUBSTRINGS")
+        .ok()
+        .map(|raw| {
+            raw.split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_else(|| vec!["TODO".to_string(), "lorem ipsum".to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_table_name_falls_back_to_base_when_disabled() {
+        assert_eq!(
+            resolve_table_name("petfood-foods", Some("acme"), false),
+            "petfood-foods"
+        );
+    }
+
+    #[test]
+    fn resolve_table_name_suffixes_with_tenant_when_enabled() {
+        assert_eq!(
+            resolve_table_name("petfood-foods", Some("acme"), true),
+            "petfood-foods-acme"
+        );
+    }
+
+    #[test]
+    fn resolve_table_name_falls_back_when_no_tenant_given() {
+        assert_eq!(resolve_table_name("petfood-foods", None, true), "petfood-foods");
+    }
+
+    fn sample_config(internal_api_key: Option<String>) -> ServerConfig {
+        ServerConfig {
+            port: 8080,
+            foods_table_name: "petfood-foods".to_string(),
+            carts_table_name: "petfood-carts".to_string(),
+            cart_ttl_days: 30,
+            orders_table_name: "petfood-orders".to_string(),
+            audit_table_name: "petfood-audit".to_string(),
+            discounts_table_name: "petfood-discounts".to_string(),
+            event_bus_name: "petfood-events".to_string(),
+            aws_region: Some("us-east-1".to_string()),
+            recommendation_default_sort: SortOrder::None,
+            recommendation_empty_reason_enabled: false,
+            otel_metrics_enabled: false,
+            multi_tenant_tables_enabled: false,
+            warm_connections_enabled: false,
+            trusted_proxy_allow_list: TrustedProxyAllowList::default(),
+            cors_allowed_origin: "*".to_string(),
+            price_as_string: false,
+            slow_request_threshold_ms: 1000,
+            require_https: false,
+            https_enforcement_mode: HttpsEnforcementMode::Reject,
+            event_max_concurrency: None,
+            event_shed_when_saturated: false,
+            canonical_json_enabled: false,
+            max_pet_type_filters: 10,
+            max_exclude_ingredients_filters: 20,
+            max_category_filters: 10,
+            max_ingredients_list_limit: 100,
+            table_item_count_interval_secs: 21_600,
+            add_dedupe_window_ms: None,
+            missing_image_emit_window_ms: None,
+            cart_food_lookup_cache_ttl_ms: None,
+            recommendation_cache_ttl_ms: None,
+            recommendation_stats_fanout_concurrency: 4,
+            recommendation_stats_time_budget_ms: None,
+            internal_api_key,
+            hide_out_of_stock_by_default: false,
+            startup_probe_timeout_ms: 5000,
+            event_bus_strict: false,
+            seed_batch_concurrency: 4,
+            seed_min_description_length: 15,
+            seed_banned_placeholder_substrings: vec!["TODO".to_string(), "lorem ipsum".to_string()],
+            prevent_empty_catalog: false,
+            allowed_image_domains: Vec::new(),
+            catalog_size_alert_drop_threshold_percent: None,
+            ready_requires_otlp: false,
+            otlp_endpoint: "localhost:4317".to_string(),
+            otlp_probe_timeout_ms: 1000,
+            ready_requires_aws: false,
+            readiness_probe_timeout_ms: 2000,
+            readiness_cache_ttl_ms: None,
+            capacity_budget_rcu: None,
+            stock_visibility: StockVisibility::Exact,
+            low_stock_threshold: 5,
+            max_seed_items: 5000,
+            trust_seed: false,
+            high_value_cart_threshold_cents: None,
+            default_currency: "USD".to_string(),
+            analytics_events_enabled: false,
+            metrics_max_label_values: 200,
+            event_idempotency_table_name: None,
+            event_retry_attempts: 0,
+            event_retry_timeout_seconds: 10,
+            rate_limit_rps: None,
+            rate_limit_burst: 10,
+            max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
+            ssm_cache_ttl_seconds: 300,
+            shutdown_drain_seconds: 30,
+        }
+    }
+
+    #[test]
+    fn redacted_masks_the_internal_api_key_when_present() {
+        let config = sample_config(Some("top-secret".to_string()));
+
+        let redacted = config.redacted();
+
+        assert_eq!(redacted.internal_api_key.as_deref(), Some(REDACTED_PLACEHOLDER));
+        assert_eq!(redacted.foods_table_name, "petfood-foods");
+        assert_eq!(redacted.aws_region.as_deref(), Some("us-east-1"));
+    }
+
+    #[test]
+    fn redacted_leaves_the_internal_api_key_absent_when_unset() {
+        let config = sample_config(None);
+
+        let redacted = config.redacted();
+
+        assert_eq!(redacted.internal_api_key, None);
+    }
+
+    #[test]
+    fn redacted_serializes_structural_fields_visibly() {
+        let config = sample_config(Some("top-secret".to_string()));
+
+        let value = serde_json::to_value(config.redacted()).unwrap();
+
+        assert_eq!(value["foods_table_name"], "petfood-foods");
+        assert_eq!(value["port"], 8080);
+        assert_eq!(value["https_enforcement_mode"], "Reject");
+        assert_eq!(value["internal_api_key"], REDACTED_PLACEHOLDER);
+        assert!(!value.to_string().contains("top-secret"));
+    }
+
+    #[test]
+    fn ssm_parameter_resolutions_reports_resolved_when_the_value_is_present() {
+        let config = sample_config(Some("top-secret".to_string()));
+
+        let resolutions = config.ssm_parameter_resolutions();
+
+        assert_eq!(resolutions.len(), 1);
+        assert_eq!(resolutions[0].parameter, "internal_api_key");
+        assert_eq!(resolutions[0].resolved_path, "/petfood/internal_api_key");
+        assert_eq!(resolutions[0].env_var, "PETFOOD_INTERNAL_API_KEY");
+        assert!(resolutions[0].resolved);
+    }
+
+    #[test]
+    fn ssm_parameter_resolutions_reports_unresolved_when_the_value_is_absent() {
+        let config = sample_config(None);
+
+        let resolutions = config.ssm_parameter_resolutions();
+
+        assert!(!resolutions[0].resolved);
+    }
+}
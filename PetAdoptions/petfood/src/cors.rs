@@ -0,0 +1,206 @@
+use std::sync::Mutex;
+
+use axum::extract::Request;
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use once_cell::sync::Lazy;
+
+/// `PETFOOD_CORS_ALLOWED_ORIGIN` — see `ServerConfig::cors_allowed_origin`.
+/// Defaults to `*` until `main` calls [`set_cors_allowed_origin`] with the
+/// configured value.
+static ALLOWED_ORIGIN: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new("*".to_string()));
+
+pub fn set_cors_allowed_origin(origin: String) {
+    *ALLOWED_ORIGIN.lock().unwrap() = origin;
+}
+
+/// The route groups browsers need distinct preflight answers for: read
+/// routes only ever `GET`, cart routes mutate, and admin routes get the
+/// write verbs `POST`/`PUT`/`DELETE` (their `GET` and `PATCH` routes are
+/// not in that set).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RouteGroup {
+    Read,
+    Cart,
+    Admin,
+}
+
+impl RouteGroup {
+    /// Unmatched paths (e.g. `/health`, `/metrics`) get no CORS headers at
+    /// all, since they aren't meant to be called cross-origin.
+    fn for_path(path: &str) -> Option<Self> {
+        if path.starts_with("/api/admin") {
+            Some(Self::Admin)
+        } else if path.starts_with("/api/cart") {
+            Some(Self::Cart)
+        } else if path.starts_with("/api/foods") || path.starts_with("/api/recommendations") {
+            Some(Self::Read)
+        } else {
+            None
+        }
+    }
+
+    fn allowed_methods(self) -> &'static str {
+        match self {
+            Self::Read => "GET",
+            Self::Cart => "GET, POST, PUT, DELETE",
+            Self::Admin => "POST, PUT, DELETE",
+        }
+    }
+}
+
+/// Answers CORS preflights with the method set appropriate to the requested
+/// route's group, and stamps `Access-Control-Allow-Methods` and
+/// `Access-Control-Allow-Origin` onto real responses too so non-preflighted
+/// requests still carry them — a browser ignores every other CORS header
+/// when `Access-Control-Allow-Origin` is missing, so that one has to be on
+/// both the preflight and the real response. Cart and admin routes also get
+/// `Access-Control-Allow-Headers: content-type`, since their POST/PUT
+/// bodies are JSON and a cross-origin `fetch` sending that content type
+/// triggers a preflight that needs the header allow-listed back.
+pub async fn cors_middleware(request: Request, next: Next) -> Response {
+    let Some(group) = RouteGroup::for_path(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let allowed_methods = HeaderValue::from_static(group.allowed_methods());
+    let allowed_origin = HeaderValue::from_str(&ALLOWED_ORIGIN.lock().unwrap()).unwrap_or(HeaderValue::from_static("*"));
+
+    if request.method() == Method::OPTIONS {
+        let mut response = StatusCode::NO_CONTENT.into_response();
+        let headers = response.headers_mut();
+        headers.insert("access-control-allow-methods", allowed_methods);
+        headers.insert("access-control-allow-origin", allowed_origin);
+        if group != RouteGroup::Read {
+            headers.insert("access-control-allow-headers", HeaderValue::from_static("content-type"));
+        }
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert("access-control-allow-methods", allowed_methods);
+    headers.insert("access-control-allow-origin", allowed_origin);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Request as HttpRequest;
+    use axum::routing::{get, post};
+    use axum::Router;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn ok_handler() -> &'static str {
+        "ok"
+    }
+
+    fn test_router() -> Router {
+        Router::new()
+            .route("/api/foods", get(ok_handler))
+            .route("/api/cart/:user_id/items", post(ok_handler))
+            .route("/api/admin/foods", post(ok_handler))
+            .route("/health", get(ok_handler))
+            .layer(axum::middleware::from_fn(cors_middleware))
+    }
+
+    fn preflight(path: &str) -> HttpRequest<Body> {
+        HttpRequest::builder()
+            .method(Method::OPTIONS)
+            .uri(path)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn preflight_for_a_cart_route_allows_the_cart_method_set() {
+        let response = test_router()
+            .oneshot(preflight("/api/cart/user-1/items"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers().get("access-control-allow-methods").unwrap(),
+            "GET, POST, PUT, DELETE"
+        );
+    }
+
+    #[tokio::test]
+    async fn preflight_for_an_admin_route_allows_the_admin_method_set() {
+        let response = test_router()
+            .oneshot(preflight("/api/admin/foods"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers().get("access-control-allow-methods").unwrap(),
+            "POST, PUT, DELETE"
+        );
+    }
+
+    #[tokio::test]
+    async fn preflight_for_a_read_route_only_allows_get() {
+        let response = test_router().oneshot(preflight("/api/foods")).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers().get("access-control-allow-methods").unwrap(),
+            "GET"
+        );
+    }
+
+    #[tokio::test]
+    async fn routes_outside_any_group_get_no_cors_header() {
+        let response = test_router()
+            .oneshot(HttpRequest::builder().uri("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get("access-control-allow-methods").is_none());
+    }
+
+    #[tokio::test]
+    async fn preflight_for_a_cart_route_carries_the_allowed_origin_and_headers() {
+        let response = test_router()
+            .oneshot(preflight("/api/cart/user-1/items"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers().get("access-control-allow-origin").unwrap(), "*");
+        assert_eq!(response.headers().get("access-control-allow-headers").unwrap(), "content-type");
+    }
+
+    #[tokio::test]
+    async fn a_read_route_preflight_gets_no_allow_headers_since_get_never_needs_one() {
+        let response = test_router().oneshot(preflight("/api/foods")).await.unwrap();
+
+        assert!(response.headers().get("access-control-allow-headers").is_none());
+    }
+
+    #[tokio::test]
+    async fn a_real_response_also_carries_the_allowed_origin() {
+        let response = test_router()
+            .oneshot(HttpRequest::builder().uri("/api/foods").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers().get("access-control-allow-origin").unwrap(), "*");
+    }
+
+    #[tokio::test]
+    async fn the_configured_origin_is_echoed_instead_of_the_default() {
+        set_cors_allowed_origin("https://example.com".to_string());
+
+        let response = test_router().oneshot(preflight("/api/foods")).await.unwrap();
+
+        assert_eq!(response.headers().get("access-control-allow-origin").unwrap(), "https://example.com");
+
+        set_cors_allowed_origin("*".to_string());
+    }
+}
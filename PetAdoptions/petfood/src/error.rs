@@ -0,0 +1,175 @@
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+/// Errors surfaced by the service layer and translated into HTTP responses
+/// by handlers. Each variant maps to a fixed status code so callers get a
+/// consistent envelope regardless of which handler produced it.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Validation(String),
+    #[error("internal error: {0}")]
+    Internal(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
+    BudgetExceeded(String),
+    #[error("{0}")]
+    InvalidCoupon(String),
+    #[error("{0}")]
+    PayloadTooLarge(String),
+    #[error("{0}")]
+    RateLimited(String),
+}
+
+impl ApiError {
+    /// Stable, machine-readable code for this error, included in the
+    /// envelope as `code` alongside the human-readable `message`. Unlike
+    /// `message`, this never changes wording and is safe for clients to
+    /// branch on instead of parsing the HTTP status or message text.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::Validation(_) => "VALIDATION_ERROR",
+            ApiError::Internal(_) => "INTERNAL_ERROR",
+            ApiError::Conflict(_) => "CONFLICT",
+            ApiError::BudgetExceeded(_) => "BUDGET_EXCEEDED",
+            ApiError::InvalidCoupon(_) => "INVALID_COUPON",
+            ApiError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            ApiError::RateLimited(_) => "RATE_LIMITED",
+        }
+    }
+}
+
+/// RFC 7807 `application/problem+json` error body. Replaces the old ad-hoc
+/// per-handler envelopes (`api` and `admin` handlers had each grown their
+/// own shape) with one document every `ApiError` converts to, via
+/// `From<&ApiError>`.
+#[derive(Debug, Serialize)]
+pub struct ProblemDetails {
+    /// A URI identifying the problem type. We don't maintain per-error
+    /// documentation pages, so this is always `about:blank`, RFC 7807's
+    /// defined default for "no more specific type than the HTTP status".
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Extension member (RFC 7807 §3.2 permits additional members): the
+    /// same stable `ApiError::code()` the old envelope exposed, so clients
+    /// can keep branching on a code instead of parsing `detail`.
+    pub code: String,
+}
+
+impl ProblemDetails {
+    pub fn new(title: &str, status: StatusCode, detail: String, code: &str) -> Self {
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: title.to_string(),
+            status: status.as_u16(),
+            detail,
+            instance: None,
+            code: code.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, [(header::CONTENT_TYPE, "application/problem+json")], Json(self)).into_response()
+    }
+}
+
+impl From<&ApiError> for ProblemDetails {
+    fn from(error: &ApiError) -> Self {
+        let (status, title) = match error {
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "Not Found"),
+            ApiError::Validation(_) => (StatusCode::BAD_REQUEST, "Validation Error"),
+            ApiError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal Error"),
+            ApiError::Conflict(_) => (StatusCode::CONFLICT, "Conflict"),
+            ApiError::BudgetExceeded(_) => (StatusCode::TOO_MANY_REQUESTS, "Budget Exceeded"),
+            ApiError::InvalidCoupon(_) => (StatusCode::BAD_REQUEST, "Invalid Coupon"),
+            ApiError::PayloadTooLarge(_) => (StatusCode::PAYLOAD_TOO_LARGE, "Payload Too Large"),
+            ApiError::RateLimited(_) => (StatusCode::TOO_MANY_REQUESTS, "Rate Limited"),
+        };
+        ProblemDetails::new(title, status, error.to_string(), error.code())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if let ApiError::Internal(msg) = &self {
+            tracing::error!(error = %msg, "internal error");
+        }
+        ProblemDetails::from(&self).into_response()
+    }
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// One instance of every `ApiError` variant, so a variant added later
+    /// without a matching `code()` arm fails to compile here rather than
+    /// slipping through untested.
+    fn every_variant() -> Vec<ApiError> {
+        vec![
+            ApiError::NotFound("x".to_string()),
+            ApiError::Validation("x".to_string()),
+            ApiError::Internal("x".to_string()),
+            ApiError::Conflict("x".to_string()),
+            ApiError::BudgetExceeded("x".to_string()),
+            ApiError::InvalidCoupon("x".to_string()),
+            ApiError::PayloadTooLarge("x".to_string()),
+            ApiError::RateLimited("x".to_string()),
+        ]
+    }
+
+    #[test]
+    fn every_variant_has_a_unique_code() {
+        let codes: Vec<&str> = every_variant().iter().map(ApiError::code).collect();
+        let unique: HashSet<&str> = codes.iter().copied().collect();
+        assert_eq!(codes.len(), unique.len(), "duplicate error code among {codes:?}");
+    }
+
+    #[tokio::test]
+    async fn not_found_produces_a_404_problem_document() {
+        let response = ApiError::NotFound("no such food".to_string()).into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["type"], "about:blank");
+        assert_eq!(json["title"], "Not Found");
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["detail"], "no such food");
+        assert_eq!(json["code"], "NOT_FOUND");
+    }
+
+    #[test]
+    fn every_code_is_upper_snake_case() {
+        for error in every_variant() {
+            let code = error.code();
+            assert!(
+                code.chars().all(|c| c.is_ascii_uppercase() || c == '_'),
+                "code {code} is not upper snake case"
+            );
+        }
+    }
+}
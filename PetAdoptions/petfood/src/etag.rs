@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+
+/// Weak `ETag` for a resource versioned the way `Food` is: bumped on every
+/// write via its `version` field, with `updated_at` recording when. Pairs
+/// naturally with the optimistic-locking version field already used for
+/// conditional writes — no extra hashing of the body is needed, and the tag
+/// stays stable across serializations since it never reads the body at all.
+pub fn weak_etag(version: u64, updated_at: DateTime<Utc>) -> String {
+    format!(r#"W/"{version}-{}""#, updated_at.timestamp())
+}
+
+/// Whether `if_none_match` (the raw `If-None-Match` header value, if any)
+/// already has `etag`, making the request a no-op conditional `GET` that
+/// should short-circuit to `304 Not Modified`. Handles the comma-separated
+/// multi-value form and the `*` wildcard; weak (`W/`) and strong
+/// comparison are treated the same, since weak comparison is always
+/// correct for `GET`.
+pub fn if_none_match_satisfied(if_none_match: Option<&str>, etag: &str) -> bool {
+    let Some(header) = if_none_match else {
+        return false;
+    };
+
+    let opaque_tag = |tag: &str| tag.strip_prefix("W/").unwrap_or(tag).to_string();
+    let etag = opaque_tag(etag);
+    header
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || opaque_tag(candidate) == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn sample_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn weak_etag_is_stable_across_repeated_calls_with_the_same_inputs() {
+        assert_eq!(weak_etag(3, sample_time()), weak_etag(3, sample_time()));
+    }
+
+    #[test]
+    fn weak_etag_changes_when_version_changes() {
+        assert_ne!(weak_etag(3, sample_time()), weak_etag(4, sample_time()));
+    }
+
+    #[test]
+    fn weak_etag_changes_when_updated_at_changes() {
+        let later = sample_time() + chrono::Duration::seconds(1);
+        assert_ne!(weak_etag(3, sample_time()), weak_etag(3, later));
+    }
+
+    #[test]
+    fn if_none_match_is_false_when_the_header_is_absent() {
+        assert!(!if_none_match_satisfied(None, r#"W/"3-1""#));
+    }
+
+    #[test]
+    fn if_none_match_matches_an_exact_tag() {
+        assert!(if_none_match_satisfied(Some(r#"W/"3-1""#), r#"W/"3-1""#));
+    }
+
+    #[test]
+    fn if_none_match_matches_one_of_several_comma_separated_tags() {
+        assert!(if_none_match_satisfied(Some(r#"W/"1-1", W/"3-1""#), r#"W/"3-1""#));
+    }
+
+    #[test]
+    fn if_none_match_matches_the_wildcard() {
+        assert!(if_none_match_satisfied(Some("*"), r#"W/"3-1""#));
+    }
+
+    #[test]
+    fn if_none_match_is_false_for_a_non_matching_tag() {
+        assert!(!if_none_match_satisfied(Some(r#"W/"1-1""#), r#"W/"3-1""#));
+    }
+}
@@ -0,0 +1,694 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use aws_sdk_eventbridge::error::ProvideErrorMetadata;
+use aws_sdk_eventbridge::operation::describe_event_bus::DescribeEventBusError;
+use aws_sdk_eventbridge::operation::put_events::PutEventsError;
+use aws_sdk_eventbridge::types::PutEventsRequestEntry;
+use aws_sdk_eventbridge::Client as EventBridgeClient;
+use chrono::Utc;
+use rand::Rng;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use super::food_event::FoodEvent;
+use super::idempotency::{DynamoDbIdempotencyStore, IdempotencyStore};
+use crate::metrics;
+
+/// EventBridge's hard cap per `PutEvents` entry.
+const ENTRY_SIZE_LIMIT_BYTES: usize = 256 * 1024;
+
+/// How far a string field gets truncated when summarizing an oversized event.
+const TRUNCATED_FIELD_LEN: usize = 256;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventEmitterError {
+    #[error("event detail is {actual_bytes} bytes, exceeding the {limit_bytes} byte EventBridge entry limit")]
+    PayloadTooLarge { actual_bytes: usize, limit_bytes: usize },
+    #[error("failed to verify EventBridge bus {event_bus_name:?} exists: {message}")]
+    BusVerificationFailed { event_bus_name: String, message: String },
+}
+
+/// Checks whether `event_bus_name` exists, so a typo'd or deleted bus is
+/// caught at startup instead of as a `ResourceNotFoundException` buried in
+/// per-request logs. `Ok(false)` means the bus was confirmed absent;
+/// `Err(_)` means the check itself couldn't be completed (e.g. missing
+/// permissions or a network error reaching EventBridge at all), which
+/// callers should treat as "unknown" rather than "missing".
+pub async fn verify_event_bus_exists(
+    client: &EventBridgeClient,
+    event_bus_name: &str,
+) -> Result<bool, EventEmitterError> {
+    match client.describe_event_bus().name(event_bus_name).send().await {
+        Ok(_) => Ok(true),
+        Err(err) => match err.as_service_error() {
+            Some(service_err) => classify_describe_event_bus_error(event_bus_name, service_err),
+            None => Err(EventEmitterError::BusVerificationFailed {
+                event_bus_name: event_bus_name.to_string(),
+                message: err.to_string(),
+            }),
+        },
+    }
+}
+
+fn classify_describe_event_bus_error(
+    event_bus_name: &str,
+    err: &DescribeEventBusError,
+) -> Result<bool, EventEmitterError> {
+    if err.is_resource_not_found_exception() {
+        Ok(false)
+    } else {
+        Err(EventEmitterError::BusVerificationFailed {
+            event_bus_name: event_bus_name.to_string(),
+            message: err.to_string(),
+        })
+    }
+}
+
+/// Publishes domain events to the configured EventBridge bus. Emission
+/// failures are logged rather than propagated, since losing an analytics
+/// event should never fail the request that triggered it — the one
+/// exception is an oversized payload, which is surfaced as a typed error so
+/// callers can decide whether truncation is acceptable for their event.
+pub struct EventEmitter {
+    client: EventBridgeClient,
+    event_bus_name: String,
+    truncate_oversized: bool,
+    /// Bounds concurrent in-flight `put_events` calls. `None` means
+    /// unbounded, matching the default before this limit existed.
+    concurrency_limiter: Option<Arc<Semaphore>>,
+    /// When the limiter is saturated: `true` drops the event (and counts it
+    /// as shed) instead of emitting it; `false` queues until a permit frees
+    /// up.
+    shed_when_saturated: bool,
+    /// `false` once the configured bus has been confirmed missing and
+    /// `PETFOOD_EVENT_BUS_STRICT` is unset, turning `emit_event` into a
+    /// warning instead of a per-call `ResourceNotFoundException`.
+    enabled: bool,
+    /// The `source` field on emitted `PutEventsRequestEntry`s. Defaults to
+    /// `"petfood"`; a second emitter constructed with
+    /// [`with_source`](Self::with_source) can use a distinct value (e.g.
+    /// `"petfood.analytics"`) so downstream EventBridge rules can route on
+    /// it separately from domain events.
+    source: String,
+    /// How many `put_events` calls have failed since this emitter was
+    /// constructed, for [`EventEmitter::health`]. Never reset — a process
+    /// that has seen any failure stays `degraded` until it restarts.
+    failure_count: AtomicU64,
+    /// The most recent `put_events` failure's message, for the same
+    /// diagnostics surface as `failure_count`.
+    last_error: Mutex<Option<String>>,
+    /// `PETFOOD_EVENT_IDEMPOTENCY_TABLE`: when set, `emit_event` skips
+    /// re-emitting an event whose `idempotency_key` (`food_id` + event
+    /// type) was already claimed within the last 24h. `None` falls back to
+    /// always-emit, matching behavior before this existed.
+    idempotency_store: Option<Arc<dyn IdempotencyStore>>,
+    /// How many times a retryable `put_events` failure (throttling /
+    /// internal error) is retried before giving up. `0` preserves the
+    /// original single-attempt behavior.
+    retry_attempts: u32,
+    /// Total wall-clock budget for a single event's retries, including
+    /// backoff sleeps.
+    retry_timeout: Duration,
+}
+
+impl EventEmitter {
+    /// `truncate_oversized` controls what happens when a serialized event
+    /// exceeds the EventBridge entry size limit: `true` summarizes the
+    /// oversized fields and still emits; `false` returns
+    /// `EventEmitterError::PayloadTooLarge` instead of sending anything.
+    pub fn with_truncation(client: EventBridgeClient, event_bus_name: String, truncate_oversized: bool) -> Self {
+        Self {
+            client,
+            event_bus_name,
+            truncate_oversized,
+            concurrency_limiter: None,
+            shed_when_saturated: false,
+            enabled: true,
+            source: "petfood".to_string(),
+            failure_count: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+            idempotency_store: None,
+            retry_attempts: 0,
+            retry_timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// `max_concurrency` bounds how many `put_events` calls can be in flight
+    /// at once; `None` leaves emission unbounded. `shed_when_saturated`
+    /// chooses what happens when the bound is reached: shed (drop the event,
+    /// counted via `petfood_events_shed_total`) or queue (wait for a slot).
+    pub fn with_concurrency_limit(
+        client: EventBridgeClient,
+        event_bus_name: String,
+        max_concurrency: Option<usize>,
+        shed_when_saturated: bool,
+    ) -> Self {
+        Self {
+            concurrency_limiter: max_concurrency.map(|permits| Arc::new(Semaphore::new(permits))),
+            shed_when_saturated,
+            ..Self::with_truncation(client, event_bus_name, true)
+        }
+    }
+
+    /// Disables emission outright, so `emit_event` warns and returns
+    /// instead of calling EventBridge. Set after [`verify_event_bus_exists`]
+    /// reports the configured bus is missing and startup isn't configured
+    /// to fail in that case.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Overrides the `source` this emitter stamps on outgoing entries,
+    /// e.g. so an analytics-events emitter can be distinguished from the
+    /// domain-events one on the same bus.
+    pub fn with_source(mut self, source: String) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Sets `PETFOOD_EVENT_IDEMPOTENCY_TABLE` — see `idempotency_store`.
+    pub fn with_idempotency_table(mut self, client: aws_sdk_dynamodb::Client, table_name: String) -> Self {
+        self.idempotency_store = Some(Arc::new(DynamoDbIdempotencyStore::new(client, table_name)));
+        self
+    }
+
+    /// Test-only hook so `emit_event`'s idempotency behavior can be
+    /// exercised without a live DynamoDB endpoint — see
+    /// `InMemoryIdempotencyStore`.
+    #[cfg(test)]
+    fn with_idempotency_store(mut self, store: Arc<dyn IdempotencyStore>) -> Self {
+        self.idempotency_store = Some(store);
+        self
+    }
+
+    /// Sets `PETFOOD_EVENT_RETRY_ATTEMPTS` / `PETFOOD_EVENT_RETRY_TIMEOUT_SECONDS`
+    /// — see `retry_attempts`/`retry_timeout`.
+    pub fn with_retry(mut self, attempts: u32, timeout: Duration) -> Self {
+        self.retry_attempts = attempts;
+        self.retry_timeout = timeout;
+        self
+    }
+
+    /// Acquires a concurrency permit per the configured policy. `Ok(None)`
+    /// means the emitter is unbounded; `Ok(Some(_))` holds a slot until
+    /// dropped; `Err(())` means the limiter is saturated and shedding is
+    /// enabled, so the caller should drop the event without emitting it.
+    async fn acquire_permit(&self) -> Result<Option<OwnedSemaphorePermit>, ()> {
+        let Some(semaphore) = &self.concurrency_limiter else {
+            return Ok(None);
+        };
+
+        if self.shed_when_saturated {
+            semaphore.clone().try_acquire_owned().map(Some).map_err(|_| ())
+        } else {
+            Ok(Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed"),
+            ))
+        }
+    }
+
+    pub async fn emit_event(&self, event: &FoodEvent) -> Result<(), EventEmitterError> {
+        if !self.enabled {
+            tracing::warn!(
+                event_bus_name = %self.event_bus_name,
+                "dropping FoodEvent: configured EventBridge bus does not exist"
+            );
+            metrics::observe_event_shed();
+            return Ok(());
+        }
+
+        if let Some(store) = &self.idempotency_store {
+            if let Some(key) = idempotency_key(event) {
+                match store.try_claim(&key).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        tracing::debug!(idempotency_key = %key, "skipping duplicate FoodEvent emission");
+                        return Ok(());
+                    }
+                    Err(err) => {
+                        tracing::warn!(error = %err, idempotency_key = %key, "failed to check event idempotency, emitting anyway");
+                    }
+                }
+            }
+        }
+
+        let _permit = match self.acquire_permit().await {
+            Ok(permit) => permit,
+            Err(()) => {
+                tracing::warn!("dropping FoodEvent: concurrent emission limit reached");
+                metrics::observe_event_shed();
+                return Ok(());
+            }
+        };
+
+        let age_seconds = (Utc::now() - event.emitted_at).num_milliseconds() as f64 / 1000.0;
+        metrics::observe_event_emit_age_seconds(age_seconds.max(0.0));
+
+        let detail = match serde_json::to_string(event) {
+            Ok(detail) => detail,
+            Err(err) => {
+                tracing::error!(error = %err, "failed to serialize FoodEvent");
+                return Ok(());
+            }
+        };
+
+        let detail = if detail.len() > ENTRY_SIZE_LIMIT_BYTES {
+            if !self.truncate_oversized {
+                return Err(EventEmitterError::PayloadTooLarge {
+                    actual_bytes: detail.len(),
+                    limit_bytes: ENTRY_SIZE_LIMIT_BYTES,
+                });
+            }
+            tracing::warn!(
+                actual_bytes = detail.len(),
+                limit_bytes = ENTRY_SIZE_LIMIT_BYTES,
+                "FoodEvent exceeds the EventBridge entry size limit, summarizing before emit"
+            );
+            summarize(event)
+        } else {
+            detail
+        };
+
+        let entry = PutEventsRequestEntry::builder()
+            .source(self.source.clone())
+            .detail_type(event.event_type.clone())
+            .detail(detail)
+            .event_bus_name(&self.event_bus_name)
+            .build();
+
+        self.put_events_with_retry(entry).await;
+
+        Ok(())
+    }
+
+    /// Sends `entry`, retrying a retryable failure (throttling / internal
+    /// error) with exponential backoff and full jitter up to
+    /// `retry_attempts` times, bounded overall by `retry_timeout`.
+    /// Non-retryable errors (validation, access denied) and attempts/budget
+    /// exhaustion are logged and recorded on `failure_count` without
+    /// propagating — see the type-level doc comment on why emission
+    /// failures never fail the caller.
+    async fn put_events_with_retry(&self, entry: PutEventsRequestEntry) {
+        let deadline = Instant::now() + self.retry_timeout;
+        let mut attempt = 0u32;
+        loop {
+            match self.client.put_events().entries(entry.clone()).send().await {
+                Ok(_) => return,
+                Err(err) => {
+                    // No service error at all (timeout, dispatch failure, DNS) means the
+                    // request never got a response from EventBridge to classify — treat
+                    // that as transient and retry, same as a modeled retryable error.
+                    let retryable = err.as_service_error().map(is_retryable_put_events_error).unwrap_or(true);
+                    let now = Instant::now();
+                    if !retryable || attempt >= self.retry_attempts || now >= deadline {
+                        tracing::error!(error = %err, attempt, "failed to emit event to EventBridge");
+                        self.failure_count.fetch_add(1, Ordering::Relaxed);
+                        *self.last_error.lock().unwrap() = Some(err.to_string());
+                        return;
+                    }
+
+                    attempt += 1;
+                    let backoff = backoff_with_full_jitter(attempt).min(deadline - now);
+                    tracing::warn!(
+                        error = %err,
+                        attempt,
+                        backoff_ms = backoff.as_millis() as u64,
+                        "retrying EventBridge put_events after a retryable failure"
+                    );
+                    metrics::observe_eventbridge_retry();
+                    tokio::time::sleep(backoff).await;
+                }
+            }
+        }
+    }
+
+    /// Derives this emitter's health for `GET /health/ready`'s `events`
+    /// field: `disabled` when emission is turned off (see
+    /// [`with_enabled`](Self::with_enabled)), `degraded` once any
+    /// `put_events` call has failed, `healthy` otherwise. Unlike
+    /// `ready_requires_otlp`/`ready_requires_aws`, this never fails
+    /// readiness itself — it's diagnostic context for an operator, not a
+    /// traffic-routing decision.
+    pub fn health(&self) -> EventsHealth {
+        let failure_count = self.failure_count.load(Ordering::Relaxed);
+        let last_error = self.last_error.lock().unwrap().clone();
+        let status = if !self.enabled {
+            EventsHealthStatus::Disabled
+        } else if failure_count > 0 {
+            EventsHealthStatus::Degraded
+        } else {
+            EventsHealthStatus::Healthy
+        };
+        EventsHealth { status, failure_count, last_error }
+    }
+}
+
+/// [`EventEmitter::health`]'s return value.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct EventsHealth {
+    pub status: EventsHealthStatus,
+    pub failure_count: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventsHealthStatus {
+    Disabled,
+    Healthy,
+    Degraded,
+}
+
+/// Decides whether a `put_events` failure is worth retrying: throttling and
+/// the service's own internal errors are transient, but validation and
+/// access-denied errors will fail identically on every retry. `PutEvents`
+/// only models `InternalException` directly — everything else (including
+/// `ThrottlingException`) arrives as `Unhandled`, so those are told apart by
+/// error code.
+fn is_retryable_put_events_error(err: &PutEventsError) -> bool {
+    match err {
+        PutEventsError::InternalException(_) => true,
+        _ => !matches!(err.code(), Some("ValidationException") | Some("AccessDeniedException")),
+    }
+}
+
+/// Exponential backoff with full jitter (capped at 5s): picks a uniformly
+/// random delay between 0 and `min(cap, base * 2^(attempt - 1))`, so
+/// concurrent retries from multiple requests don't all retry in lockstep.
+fn backoff_with_full_jitter(attempt: u32) -> Duration {
+    const BASE: Duration = Duration::from_millis(100);
+    const CAP: Duration = Duration::from_secs(5);
+
+    let exp = BASE.saturating_mul(1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX));
+    let upper_ms = exp.min(CAP).as_millis() as u64;
+    Duration::from_millis(rand::rng().random_range(0..=upper_ms))
+}
+
+/// Derives the key `EventEmitter::with_idempotency_table` dedupes on:
+/// `food_id` plus event type, so the same food repeatedly firing the same
+/// event (e.g. `FoodMissingImageViewed` on every catalog listing) is only
+/// emitted once per [`IdempotencyStore`]'s TTL window. `None` for event
+/// types that don't carry a `food_id` — there's nothing meaningful to dedupe
+/// on, so those always emit.
+fn idempotency_key(event: &FoodEvent) -> Option<String> {
+    let food_id = event.food_id.as_deref().or(event.missing_image_food_id.as_deref())?;
+    Some(format!("{food_id}:{}", event.event_type))
+}
+
+/// Rebuilds the event detail with its string fields capped to
+/// `TRUNCATED_FIELD_LEN`, so an oversized event still gets emitted with
+/// enough context to be useful.
+fn summarize(event: &FoodEvent) -> String {
+    let truncated = FoodEvent {
+        event_type: event.event_type.clone(),
+        order_id: event.order_id.as_deref().map(truncate_field),
+        user_id: event.user_id.as_deref().map(truncate_field),
+        total_cents: event.total_cents,
+        food_id: event.food_id.as_deref().map(truncate_field),
+        old_price_cents: event.old_price_cents,
+        new_price_cents: event.new_price_cents,
+        change_percentage: event.change_percentage,
+        catalog_count_before: event.catalog_count_before,
+        catalog_count_after: event.catalog_count_after,
+        catalog_drop_percentage: event.catalog_drop_percentage,
+        cart_total_cents: event.cart_total_cents,
+        high_value_cart_threshold_cents: event.high_value_cart_threshold_cents,
+        missing_image_food_id: event.missing_image_food_id.as_deref().map(truncate_field),
+        correlation_id: event.correlation_id.clone(),
+        service_summary: event.service_summary.clone(),
+        emitted_at: event.emitted_at,
+    };
+    serde_json::to_string(&truncated).unwrap_or_default()
+}
+
+fn truncate_field(value: &str) -> String {
+    if value.len() <= TRUNCATED_FIELD_LEN {
+        return value.to_string();
+    }
+    format!("{}...(truncated)", &value[..TRUNCATED_FIELD_LEN])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dummy_client() -> EventBridgeClient {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        EventBridgeClient::new(&sdk_config)
+    }
+
+    #[tokio::test]
+    async fn emit_event_observes_the_emit_age_histogram() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false);
+        let before = metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count();
+
+        let event = FoodEvent::order_placed("order-1".to_string(), "user-1".to_string(), 1000, None);
+        emitter.emit_event(&event).await.unwrap();
+
+        assert_eq!(metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count(), before + 1);
+    }
+
+    fn oversized_event() -> FoodEvent {
+        let huge_order_id = "x".repeat(ENTRY_SIZE_LIMIT_BYTES + 1024);
+        FoodEvent::order_placed(huge_order_id, "user-1".to_string(), 1000, None)
+    }
+
+    #[tokio::test]
+    async fn emit_event_truncates_oversized_events_by_default() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false);
+
+        let result = emitter.emit_event(&oversized_event()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn emit_event_rejects_oversized_events_when_truncation_is_disabled() {
+        let emitter = EventEmitter::with_truncation(dummy_client(), "test-bus".to_string(), false);
+
+        let result = emitter.emit_event(&oversized_event()).await;
+
+        assert!(matches!(
+            result,
+            Err(EventEmitterError::PayloadTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn summarize_caps_string_fields_to_the_truncated_length() {
+        let event = oversized_event();
+        let summarized = summarize(&event);
+
+        assert!(summarized.len() < ENTRY_SIZE_LIMIT_BYTES);
+        assert!(summarized.contains("...(truncated)"));
+    }
+
+    #[tokio::test]
+    async fn acquire_permit_never_exceeds_the_configured_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let emitter = Arc::new(EventEmitter::with_concurrency_limit(
+            dummy_client(),
+            "test-bus".to_string(),
+            Some(2),
+            false,
+        ));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_observed = Arc::new(AtomicUsize::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..10 {
+            let emitter = emitter.clone();
+            let in_flight = in_flight.clone();
+            let max_observed = max_observed.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = emitter.acquire_permit().await.unwrap();
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_observed.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert!(max_observed.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn acquire_permit_sheds_when_saturated_and_shedding_is_enabled() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), Some(1), true);
+
+        let _held = emitter.acquire_permit().await.unwrap();
+        let result = emitter.acquire_permit().await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn emit_event_sheds_without_erroring_when_saturated() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), Some(1), true);
+        let before_shed = metrics::EVENTS_SHED_TOTAL.get();
+
+        let _held = emitter.acquire_permit().await.unwrap();
+        let event = FoodEvent::order_placed("order-1".to_string(), "user-1".to_string(), 1000, None);
+        let result = emitter.emit_event(&event).await;
+
+        assert!(result.is_ok());
+        assert_eq!(metrics::EVENTS_SHED_TOTAL.get(), before_shed + 1);
+    }
+
+    #[tokio::test]
+    async fn emit_event_skips_a_second_emit_of_the_same_idempotency_key() {
+        use super::super::idempotency::InMemoryIdempotencyStore;
+
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false)
+            .with_idempotency_store(Arc::new(InMemoryIdempotencyStore::default()));
+        let before = metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count();
+
+        let event = FoodEvent::missing_image_viewed("food-1".to_string(), None);
+        emitter.emit_event(&event).await.unwrap();
+        emitter.emit_event(&event).await.unwrap();
+
+        assert_eq!(
+            metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count(),
+            before + 1,
+            "the second emit of the same food_id + event_type should be skipped as a duplicate"
+        );
+    }
+
+    #[tokio::test]
+    async fn emit_event_always_emits_when_no_idempotency_store_is_configured() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false);
+        let before = metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count();
+
+        let event = FoodEvent::missing_image_viewed("food-1".to_string(), None);
+        emitter.emit_event(&event).await.unwrap();
+        emitter.emit_event(&event).await.unwrap();
+
+        assert_eq!(metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count(), before + 2);
+    }
+
+    #[tokio::test]
+    async fn emit_event_retries_a_failing_send_up_to_the_configured_attempts() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false)
+            .with_retry(2, Duration::from_secs(5));
+        let before_retries = metrics::EVENTBRIDGE_RETRIES_TOTAL.get();
+
+        let event = FoodEvent::order_placed("order-1".to_string(), "user-1".to_string(), 1000, None);
+        let result = emitter.emit_event(&event).await;
+
+        assert!(result.is_ok());
+        assert_eq!(metrics::EVENTBRIDGE_RETRIES_TOTAL.get(), before_retries + 2);
+        assert_eq!(emitter.health().failure_count, 1);
+    }
+
+    #[test]
+    fn is_retryable_put_events_error_retries_internal_and_unmodeled_errors() {
+        let internal = PutEventsError::InternalException(
+            aws_sdk_eventbridge::types::error::InternalException::builder().build(),
+        );
+        assert!(is_retryable_put_events_error(&internal));
+
+        let dispatch_failure = PutEventsError::unhandled("connection reset");
+        assert!(is_retryable_put_events_error(&dispatch_failure));
+    }
+
+    #[test]
+    fn backoff_with_full_jitter_never_exceeds_the_cap() {
+        for attempt in 1..=10 {
+            let backoff = backoff_with_full_jitter(attempt);
+            assert!(backoff <= Duration::from_secs(5));
+        }
+    }
+
+    #[tokio::test]
+    async fn emit_event_warns_and_no_ops_when_disabled() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false)
+            .with_enabled(false);
+        let before_shed = metrics::EVENTS_SHED_TOTAL.get();
+
+        let event = FoodEvent::order_placed("order-1".to_string(), "user-1".to_string(), 1000, None);
+        let result = emitter.emit_event(&event).await;
+
+        assert!(result.is_ok());
+        assert_eq!(metrics::EVENTS_SHED_TOTAL.get(), before_shed + 1);
+    }
+
+    fn resource_not_found_error() -> DescribeEventBusError {
+        DescribeEventBusError::ResourceNotFoundException(
+            aws_sdk_eventbridge::types::error::ResourceNotFoundException::builder().build(),
+        )
+    }
+
+    fn internal_error() -> DescribeEventBusError {
+        DescribeEventBusError::InternalException(
+            aws_sdk_eventbridge::types::error::InternalException::builder().build(),
+        )
+    }
+
+    #[test]
+    fn classify_describe_event_bus_error_reports_missing_for_resource_not_found() {
+        let result = classify_describe_event_bus_error("test-bus", &resource_not_found_error());
+
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn classify_describe_event_bus_error_surfaces_other_failures_instead_of_guessing() {
+        let result = classify_describe_event_bus_error("test-bus", &internal_error());
+
+        assert!(matches!(
+            result,
+            Err(EventEmitterError::BusVerificationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn health_reports_disabled_when_the_emitter_is_disabled() {
+        let emitter =
+            EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false).with_enabled(false);
+
+        let health = emitter.health();
+
+        assert_eq!(health.status, EventsHealthStatus::Disabled);
+        assert_eq!(health.failure_count, 0);
+        assert_eq!(health.last_error, None);
+    }
+
+    #[test]
+    fn health_reports_healthy_before_any_emission_has_failed() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false);
+
+        let health = emitter.health();
+
+        assert_eq!(health.status, EventsHealthStatus::Healthy);
+        assert_eq!(health.failure_count, 0);
+    }
+
+    #[tokio::test]
+    async fn health_reports_degraded_with_the_last_error_after_a_failed_emission() {
+        let emitter = EventEmitter::with_concurrency_limit(dummy_client(), "test-bus".to_string(), None, false);
+
+        let event = FoodEvent::order_placed("order-1".to_string(), "user-1".to_string(), 1000, None);
+        emitter.emit_event(&event).await.unwrap();
+
+        let health = emitter.health();
+
+        assert_eq!(health.status, EventsHealthStatus::Degraded);
+        assert_eq!(health.failure_count, 1);
+        assert!(health.last_error.is_some());
+    }
+}
@@ -0,0 +1,456 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Domain event published to EventBridge: an order placed, a price change,
+/// a catalog-size alert, a high-value cart, a missing-image read, a funnel
+/// analytics signal, or service startup. Each constructor only populates the fields its
+/// event type carries, leaving the rest `None` so the wire format stays
+/// lean per event type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FoodEvent {
+    pub event_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_cents: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub food_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_price_cents: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_price_cents: Option<i64>,
+    /// The price change relative to `old_price_cents`, as a percentage
+    /// (e.g. `-10.0` for a 10% drop). `0.0` when the old price was 0, to
+    /// avoid dividing by zero.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_percentage: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog_count_before: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog_count_after: Option<usize>,
+    /// How much the active food count dropped, as a percentage of
+    /// `catalog_count_before`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog_drop_percentage: Option<f64>,
+    /// The cart total (in cents) that crossed `high_value_cart_threshold_cents`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cart_total_cents: Option<i64>,
+    /// The configured `PETFOOD_HIGH_VALUE_CART_THRESHOLD` the cart crossed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_value_cart_threshold_cents: Option<i64>,
+    /// The catalog entry read without an `image_path` set, for
+    /// `FoodEvent::missing_image_viewed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_image_food_id: Option<String>,
+    /// The originating request's `X-Request-Id`, if the caller sent one, so
+    /// operators can correlate this event back to the API request that
+    /// triggered it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// Config-summary payload for `FoodEvent::service_started` — not given
+    /// its own top-level fields like the events above since its shape
+    /// (feature flags, table names) is deployment metadata, not domain data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_summary: Option<ServiceStartedSummary>,
+    /// When this event was created, used by the emitter to measure how long
+    /// it sat queued before being handed to EventBridge.
+    pub emitted_at: DateTime<Utc>,
+}
+
+/// `FoodEvent::service_started`'s payload: a fleet-wide config-drift
+/// collector's view of what's deployed where. `redacted_table_names`/`feature_flags` use
+/// a `BTreeMap` so the wire format (and any diffing a collector does on it)
+/// doesn't depend on `ServerConfig`'s field declaration order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceStartedSummary {
+    pub version: String,
+    pub region: Option<String>,
+    pub feature_flags: BTreeMap<String, bool>,
+    /// Table names as exposed by `ServerConfig::redacted` — not secrets, but
+    /// grouped under a name that matches the rest of the redacted config
+    /// surface this summary is drawn from.
+    pub redacted_table_names: BTreeMap<String, String>,
+}
+
+impl FoodEvent {
+    pub fn order_placed(order_id: String, user_id: String, total_cents: i64, correlation_id: Option<String>) -> Self {
+        Self {
+            event_type: "OrderPlaced".to_string(),
+            order_id: Some(order_id),
+            user_id: Some(user_id),
+            total_cents: Some(total_cents),
+            food_id: None,
+            old_price_cents: None,
+            new_price_cents: None,
+            change_percentage: None,
+            catalog_count_before: None,
+            catalog_count_after: None,
+            catalog_drop_percentage: None,
+            cart_total_cents: None,
+            high_value_cart_threshold_cents: None,
+            missing_image_food_id: None,
+            correlation_id,
+            service_summary: None,
+            emitted_at: Utc::now(),
+        }
+    }
+
+    /// Fires from `FoodService::update_price`/`bulk_update_prices` whenever
+    /// a food's price actually changes, carrying old and new price so
+    /// price-intelligence consumers don't need to re-read the catalog.
+    pub fn price_changed(
+        food_id: String,
+        old_price_cents: i64,
+        new_price_cents: i64,
+        correlation_id: Option<String>,
+    ) -> Self {
+        let change_percentage = if old_price_cents == 0 {
+            0.0
+        } else {
+            (new_price_cents - old_price_cents) as f64 / old_price_cents as f64 * 100.0
+        };
+        Self {
+            event_type: "FoodPriceChanged".to_string(),
+            order_id: None,
+            user_id: None,
+            total_cents: None,
+            food_id: Some(food_id),
+            old_price_cents: Some(old_price_cents),
+            new_price_cents: Some(new_price_cents),
+            change_percentage: Some(change_percentage),
+            catalog_count_before: None,
+            catalog_count_after: None,
+            catalog_drop_percentage: None,
+            cart_total_cents: None,
+            high_value_cart_threshold_cents: None,
+            missing_image_food_id: None,
+            correlation_id,
+            service_summary: None,
+            emitted_at: Utc::now(),
+        }
+    }
+
+    /// Fires from `FoodService::delete_food` when a single deletion drops the
+    /// active food count by more than `PETFOOD_CATALOG_SIZE_ALERT_DROP_PERCENT`,
+    /// so ops can catch an accidental mass-deletion before the storefront
+    /// goes empty.
+    pub fn catalog_size_alert(
+        count_before: usize,
+        count_after: usize,
+        drop_percentage: f64,
+        correlation_id: Option<String>,
+    ) -> Self {
+        Self {
+            event_type: "CatalogSizeAlert".to_string(),
+            order_id: None,
+            user_id: None,
+            total_cents: None,
+            food_id: None,
+            old_price_cents: None,
+            new_price_cents: None,
+            change_percentage: None,
+            catalog_count_before: Some(count_before),
+            catalog_count_after: Some(count_after),
+            catalog_drop_percentage: Some(drop_percentage),
+            cart_total_cents: None,
+            high_value_cart_threshold_cents: None,
+            missing_image_food_id: None,
+            correlation_id,
+            service_summary: None,
+            emitted_at: Utc::now(),
+        }
+    }
+
+    /// Fires from `CartService::add_item` the first time a cart's total
+    /// crosses `threshold_cents` (`PETFOOD_HIGH_VALUE_CART_THRESHOLD`) —
+    /// once per crossing, not on every subsequent add that stays above it.
+    pub fn high_value_cart(user_id: String, cart_total_cents: i64, threshold_cents: i64, correlation_id: Option<String>) -> Self {
+        Self {
+            event_type: "HighValueCart".to_string(),
+            order_id: None,
+            user_id: Some(user_id),
+            total_cents: None,
+            food_id: None,
+            old_price_cents: None,
+            new_price_cents: None,
+            change_percentage: None,
+            catalog_count_before: None,
+            catalog_count_after: None,
+            catalog_drop_percentage: None,
+            cart_total_cents: Some(cart_total_cents),
+            high_value_cart_threshold_cents: Some(threshold_cents),
+            missing_image_food_id: None,
+            correlation_id,
+            service_summary: None,
+            emitted_at: Utc::now(),
+        }
+    }
+
+    /// Fires from `FoodService::get_food`/`list_foods_within_budget` when a
+    /// food without an `image_path` is read, throttled to at most once per
+    /// `PETFOOD_MISSING_IMAGE_EMIT_WINDOW_MS` per food so repeated reads of
+    /// the same image-less food don't flood the pipeline with duplicates.
+    pub fn missing_image_viewed(food_id: String, correlation_id: Option<String>) -> Self {
+        Self {
+            event_type: "FoodMissingImageViewed".to_string(),
+            order_id: None,
+            user_id: None,
+            total_cents: None,
+            food_id: None,
+            old_price_cents: None,
+            new_price_cents: None,
+            change_percentage: None,
+            catalog_count_before: None,
+            catalog_count_after: None,
+            catalog_drop_percentage: None,
+            cart_total_cents: None,
+            high_value_cart_threshold_cents: None,
+            missing_image_food_id: Some(food_id),
+            correlation_id,
+            service_summary: None,
+            emitted_at: Utc::now(),
+        }
+    }
+
+    /// Fires from `FoodService::get_food` on a successful read, when an
+    /// analytics emitter is configured — see
+    /// [`EventEmitter::with_source`](crate::events::EventEmitter::with_source).
+    /// Distinct from `missing_image_viewed`: this fires on every read a
+    /// funnel-analytics consumer cares about, not just ones missing an image.
+    pub fn food_viewed(food_id: String, correlation_id: Option<String>) -> Self {
+        Self {
+            event_type: "FoodViewed".to_string(),
+            order_id: None,
+            user_id: None,
+            total_cents: None,
+            food_id: Some(food_id),
+            old_price_cents: None,
+            new_price_cents: None,
+            change_percentage: None,
+            catalog_count_before: None,
+            catalog_count_after: None,
+            catalog_drop_percentage: None,
+            cart_total_cents: None,
+            high_value_cart_threshold_cents: None,
+            missing_image_food_id: None,
+            correlation_id,
+            service_summary: None,
+            emitted_at: Utc::now(),
+        }
+    }
+
+    /// Fires from `CartService::add_item` on success, for the same
+    /// funnel-analytics emitter as [`FoodEvent::food_viewed`].
+    pub fn item_added_to_cart(user_id: String, food_id: String, correlation_id: Option<String>) -> Self {
+        Self {
+            event_type: "ItemAddedToCart".to_string(),
+            order_id: None,
+            user_id: Some(user_id),
+            total_cents: None,
+            food_id: Some(food_id),
+            old_price_cents: None,
+            new_price_cents: None,
+            change_percentage: None,
+            catalog_count_before: None,
+            catalog_count_after: None,
+            catalog_drop_percentage: None,
+            cart_total_cents: None,
+            high_value_cart_threshold_cents: None,
+            missing_image_food_id: None,
+            correlation_id,
+            service_summary: None,
+            emitted_at: Utc::now(),
+        }
+    }
+
+    /// Fires from `CartService::checkout_cart` on success, for the same
+    /// funnel-analytics emitter as [`FoodEvent::food_viewed`]. Unlike
+    /// `order_placed`, this is purely an analytics signal — it carries no
+    /// guarantee downstream fulfillment systems consume it.
+    pub fn order_checked_out(order_id: String, user_id: String, total_cents: i64, correlation_id: Option<String>) -> Self {
+        Self {
+            event_type: "OrderCheckedOut".to_string(),
+            order_id: Some(order_id),
+            user_id: Some(user_id),
+            total_cents: Some(total_cents),
+            food_id: None,
+            old_price_cents: None,
+            new_price_cents: None,
+            change_percentage: None,
+            catalog_count_before: None,
+            catalog_count_after: None,
+            catalog_drop_percentage: None,
+            cart_total_cents: None,
+            high_value_cart_threshold_cents: None,
+            missing_image_food_id: None,
+            correlation_id,
+            service_summary: None,
+            emitted_at: Utc::now(),
+        }
+    }
+
+    /// Fires once at startup — see `emit_service_started_event` in
+    /// `main.rs` — carrying a fleet-wide config-drift collector's view of
+    /// what's deployed where. Unlike the other event types, this one has no
+    /// `correlation_id`: it isn't a response to any particular request.
+    pub fn service_started(summary: ServiceStartedSummary) -> Self {
+        Self {
+            event_type: "ServiceStarted".to_string(),
+            order_id: None,
+            user_id: None,
+            total_cents: None,
+            food_id: None,
+            old_price_cents: None,
+            new_price_cents: None,
+            change_percentage: None,
+            catalog_count_before: None,
+            catalog_count_after: None,
+            catalog_drop_percentage: None,
+            cart_total_cents: None,
+            high_value_cart_threshold_cents: None,
+            missing_image_food_id: None,
+            correlation_id: None,
+            service_summary: Some(summary),
+            emitted_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_placed_carries_the_correlation_id_through() {
+        let event = FoodEvent::order_placed(
+            "order-1".to_string(),
+            "user-1".to_string(),
+            1000,
+            Some("req-123".to_string()),
+        );
+
+        assert_eq!(event.correlation_id, Some("req-123".to_string()));
+
+        let serialized = serde_json::to_string(&event).unwrap();
+        assert!(serialized.contains("\"correlation_id\":\"req-123\""));
+    }
+
+    #[test]
+    fn order_placed_omits_correlation_id_from_the_wire_format_when_absent() {
+        let event = FoodEvent::order_placed("order-1".to_string(), "user-1".to_string(), 1000, None);
+
+        let serialized = serde_json::to_string(&event).unwrap();
+        assert!(!serialized.contains("correlation_id"));
+    }
+
+    #[test]
+    fn price_changed_computes_the_change_percentage() {
+        let event = FoodEvent::price_changed("food-1".to_string(), 1000, 900, None);
+
+        assert_eq!(event.event_type, "FoodPriceChanged");
+        assert_eq!(event.old_price_cents, Some(1000));
+        assert_eq!(event.new_price_cents, Some(900));
+        assert_eq!(event.change_percentage, Some(-10.0));
+    }
+
+    #[test]
+    fn price_changed_reports_zero_percent_when_the_old_price_was_zero() {
+        let event = FoodEvent::price_changed("food-1".to_string(), 0, 500, None);
+
+        assert_eq!(event.change_percentage, Some(0.0));
+    }
+
+    #[test]
+    fn price_changed_omits_order_fields_from_the_wire_format() {
+        let event = FoodEvent::price_changed("food-1".to_string(), 1000, 900, None);
+
+        let serialized = serde_json::to_string(&event).unwrap();
+        assert!(!serialized.contains("order_id"));
+        assert!(!serialized.contains("user_id"));
+        assert!(serialized.contains("\"food_id\":\"food-1\""));
+    }
+
+    #[test]
+    fn high_value_cart_carries_the_total_and_threshold_that_crossed() {
+        let event = FoodEvent::high_value_cart("user-1".to_string(), 15000, 10000, None);
+
+        assert_eq!(event.event_type, "HighValueCart");
+        assert_eq!(event.user_id, Some("user-1".to_string()));
+        assert_eq!(event.cart_total_cents, Some(15000));
+        assert_eq!(event.high_value_cart_threshold_cents, Some(10000));
+    }
+
+    #[test]
+    fn missing_image_viewed_carries_the_food_id() {
+        let event = FoodEvent::missing_image_viewed("food-1".to_string(), None);
+
+        assert_eq!(event.event_type, "FoodMissingImageViewed");
+        assert_eq!(event.missing_image_food_id, Some("food-1".to_string()));
+    }
+
+    #[test]
+    fn food_viewed_carries_the_food_id_and_correlation_id() {
+        let event = FoodEvent::food_viewed("food-1".to_string(), Some("req-123".to_string()));
+
+        assert_eq!(event.event_type, "FoodViewed");
+        assert_eq!(event.food_id, Some("food-1".to_string()));
+        assert_eq!(event.correlation_id, Some("req-123".to_string()));
+    }
+
+    #[test]
+    fn item_added_to_cart_carries_the_user_and_food_id() {
+        let event = FoodEvent::item_added_to_cart("user-1".to_string(), "food-1".to_string(), None);
+
+        assert_eq!(event.event_type, "ItemAddedToCart");
+        assert_eq!(event.user_id, Some("user-1".to_string()));
+        assert_eq!(event.food_id, Some("food-1".to_string()));
+    }
+
+    #[test]
+    fn order_checked_out_carries_the_order_user_and_total() {
+        let event = FoodEvent::order_checked_out("order-1".to_string(), "user-1".to_string(), 2500, None);
+
+        assert_eq!(event.event_type, "OrderCheckedOut");
+        assert_eq!(event.order_id, Some("order-1".to_string()));
+        assert_eq!(event.user_id, Some("user-1".to_string()));
+        assert_eq!(event.total_cents, Some(2500));
+    }
+
+    #[test]
+    fn service_started_carries_the_config_summary_and_no_correlation_id() {
+        let summary = ServiceStartedSummary {
+            version: "1.2.3".to_string(),
+            region: Some("us-east-1".to_string()),
+            feature_flags: BTreeMap::from([("otel_metrics_enabled".to_string(), true)]),
+            redacted_table_names: BTreeMap::from([("foods_table_name".to_string(), "petfood-foods".to_string())]),
+        };
+
+        let event = FoodEvent::service_started(summary.clone());
+
+        assert_eq!(event.event_type, "ServiceStarted");
+        assert_eq!(event.correlation_id, None);
+        assert_eq!(event.service_summary, Some(summary));
+    }
+
+    #[test]
+    fn service_started_wire_format_contains_the_redacted_fields() {
+        let summary = ServiceStartedSummary {
+            version: "1.2.3".to_string(),
+            region: Some("us-east-1".to_string()),
+            feature_flags: BTreeMap::from([("otel_metrics_enabled".to_string(), true)]),
+            redacted_table_names: BTreeMap::from([("foods_table_name".to_string(), "petfood-foods".to_string())]),
+        };
+
+        let serialized = serde_json::to_string(&FoodEvent::service_started(summary)).unwrap();
+
+        assert!(serialized.contains("\"version\":\"1.2.3\""));
+        assert!(serialized.contains("\"region\":\"us-east-1\""));
+        assert!(serialized.contains("\"otel_metrics_enabled\":true"));
+        assert!(serialized.contains("\"foods_table_name\":\"petfood-foods\""));
+        assert!(!serialized.contains("correlation_id"));
+    }
+}
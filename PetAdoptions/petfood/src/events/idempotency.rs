@@ -0,0 +1,125 @@
+#[cfg(test)]
+use std::collections::HashSet;
+#[cfg(test)]
+use std::sync::Mutex;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use chrono::{Duration, Utc};
+use tracing::Instrument;
+
+use crate::repository::tracing::{client_region, dynamodb_span};
+use crate::ttl::compute_expiry;
+
+/// How long a claimed `idempotency_key` blocks a repeat emission before
+/// DynamoDB's TTL sweep reclaims the item and the key becomes emittable
+/// again.
+const IDEMPOTENCY_TTL: Duration = Duration::hours(24);
+
+/// Sane bounds applied to [`IDEMPOTENCY_TTL`] by [`compute_expiry`] — guards
+/// against a clock-skewed instance computing a `ttl` in the past (the claim
+/// expires the instant it's written, so a duplicate sneaks through
+/// immediately) or implausibly far in the future.
+const MIN_IDEMPOTENCY_TTL: Duration = Duration::minutes(1);
+const MAX_IDEMPOTENCY_TTL: Duration = Duration::days(7);
+
+/// Backs `EventEmitter::with_idempotency_table`. Implemented by
+/// [`DynamoDbIdempotencyStore`] for real deployments and an in-memory
+/// fake in this file's tests, matching the `FoodRepository`/`CartRepository`
+/// trait-plus-DynamoDB-impl split used elsewhere in this crate.
+#[async_trait::async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Returns `Ok(true)` the first time `key` is claimed (the caller should
+    /// go ahead and emit), `Ok(false)` when `key` was already claimed by an
+    /// earlier call (the caller should skip the emit as a duplicate).
+    /// `Err(_)` means the store call itself failed — callers should fail
+    /// open (emit anyway) rather than let a store outage suppress events.
+    async fn try_claim(&self, key: &str) -> Result<bool, String>;
+}
+
+pub struct DynamoDbIdempotencyStore {
+    client: DynamoDbClient,
+    table_name: String,
+}
+
+impl DynamoDbIdempotencyStore {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyStore for DynamoDbIdempotencyStore {
+    async fn try_claim(&self, key: &str) -> Result<bool, String> {
+        let span = dynamodb_span("put_item", &self.table_name, &client_region(&self.client));
+        async {
+            let ttl = compute_expiry(Utc::now(), IDEMPOTENCY_TTL, MIN_IDEMPOTENCY_TTL, MAX_IDEMPOTENCY_TTL).timestamp();
+
+            let result = self
+                .client
+                .put_item()
+                .table_name(&self.table_name)
+                .item("idempotency_key", AttributeValue::S(key.to_string()))
+                .item("ttl", AttributeValue::N(ttl.to_string()))
+                .condition_expression("attribute_not_exists(idempotency_key)")
+                .send()
+                .await;
+
+            match result {
+                Ok(_) => Ok(true),
+                Err(err) if err.as_service_error().is_some_and(|e| e.is_conditional_check_failed_exception()) => Ok(false),
+                Err(err) => Err(err.to_string()),
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+pub(crate) struct InMemoryIdempotencyStore(pub(crate) Mutex<HashSet<String>>);
+
+#[cfg(test)]
+impl Default for InMemoryIdempotencyStore {
+    fn default() -> Self {
+        Self(Mutex::new(HashSet::new()))
+    }
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn try_claim(&self, key: &str) -> Result<bool, String> {
+        Ok(self.0.lock().unwrap().insert(key.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dummy_client() -> DynamoDbClient {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        DynamoDbClient::new(&sdk_config)
+    }
+
+    #[tokio::test]
+    async fn dynamodb_try_claim_surfaces_an_error_when_the_table_call_fails() {
+        let store = DynamoDbIdempotencyStore::new(dummy_client(), "test-idempotency-table".to_string());
+
+        let result = store.try_claim("food-1:FoodMissingImageViewed").await;
+
+        assert!(result.is_err(), "no live DynamoDB endpoint in tests, so the call should surface an error");
+    }
+
+    #[tokio::test]
+    async fn in_memory_try_claim_reports_the_first_claim_and_rejects_repeats() {
+        let store = InMemoryIdempotencyStore::default();
+
+        assert!(store.try_claim("food-1:FoodMissingImageViewed").await.unwrap());
+        assert!(!store.try_claim("food-1:FoodMissingImageViewed").await.unwrap());
+    }
+}
@@ -0,0 +1,6 @@
+mod emitter;
+mod food_event;
+mod idempotency;
+
+pub use emitter::{verify_event_bus_exists, EventEmitter};
+pub use food_event::{FoodEvent, ServiceStartedSummary};
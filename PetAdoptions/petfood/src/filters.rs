@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::{ApiError, ApiResult};
+
+const DEFAULT_MAX_PET_TYPE_FILTERS: usize = 10;
+const DEFAULT_MAX_EXCLUDE_INGREDIENTS_FILTERS: usize = 20;
+const DEFAULT_MAX_CATEGORY_FILTERS: usize = 10;
+const DEFAULT_MAX_INGREDIENTS_LIST_LIMIT: usize = 100;
+
+/// Toggled once at startup from `PETFOOD_MAX_PET_TYPE_FILTERS` /
+/// `PETFOOD_MAX_EXCLUDE_INGREDIENTS_FILTERS` / `PETFOOD_MAX_CATEGORY_FILTERS`;
+/// read by `list_foods` on every request.
+static MAX_PET_TYPE_FILTERS: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_PET_TYPE_FILTERS);
+static MAX_EXCLUDE_INGREDIENTS_FILTERS: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_EXCLUDE_INGREDIENTS_FILTERS);
+static MAX_CATEGORY_FILTERS: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_CATEGORY_FILTERS);
+/// Toggled once at startup from `PETFOOD_MAX_INGREDIENTS_LIST_LIMIT`; the
+/// hard cap `GET /api/foods/ingredients` enforces on `?limit=` regardless of
+/// what the caller asks for.
+static MAX_INGREDIENTS_LIST_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_INGREDIENTS_LIST_LIMIT);
+
+pub fn set_filter_limits(max_pet_type_filters: usize, max_exclude_ingredients_filters: usize, max_category_filters: usize) {
+    MAX_PET_TYPE_FILTERS.store(max_pet_type_filters, Ordering::Relaxed);
+    MAX_EXCLUDE_INGREDIENTS_FILTERS.store(max_exclude_ingredients_filters, Ordering::Relaxed);
+    MAX_CATEGORY_FILTERS.store(max_category_filters, Ordering::Relaxed);
+}
+
+pub fn max_pet_type_filters() -> usize {
+    MAX_PET_TYPE_FILTERS.load(Ordering::Relaxed)
+}
+
+pub fn max_exclude_ingredients_filters() -> usize {
+    MAX_EXCLUDE_INGREDIENTS_FILTERS.load(Ordering::Relaxed)
+}
+
+pub fn max_category_filters() -> usize {
+    MAX_CATEGORY_FILTERS.load(Ordering::Relaxed)
+}
+
+pub fn set_max_ingredients_list_limit(limit: usize) {
+    MAX_INGREDIENTS_LIST_LIMIT.store(limit, Ordering::Relaxed);
+}
+
+pub fn max_ingredients_list_limit() -> usize {
+    MAX_INGREDIENTS_LIST_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Resolves the caller's `?limit=` against the configured cap: absent means
+/// "use the cap", and anything the caller asks for is clamped down to it —
+/// never up.
+pub fn resolve_ingredients_limit(requested: Option<usize>) -> usize {
+    clamp_ingredients_limit(requested, max_ingredients_list_limit())
+}
+
+fn clamp_ingredients_limit(requested: Option<usize>, cap: usize) -> usize {
+    requested.map_or(cap, |requested| requested.min(cap))
+}
+
+/// Returns every value bound to `key` in a raw query string (e.g.
+/// `pet_type=dog&pet_type=cat` -> `["dog", "cat"]`). `axum::Query`'s
+/// `serde_urlencoded` deserializer can't collect repeated keys into a
+/// `Vec` field, so multi-valued filters are parsed from the raw query
+/// string instead.
+pub fn parse_multi_value_query(query: &str, key: &str) -> Vec<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .filter(|(k, _)| *k == key)
+        .map(|(_, v)| v.to_string())
+        .collect()
+}
+
+/// Caps how many values a multi-valued filter (`pet_type`,
+/// `exclude_ingredients`, ...) can carry on a single request, so a client
+/// can't force an unbounded fan-out of per-value queries downstream.
+pub fn enforce_filter_cap(field_name: &str, values: &[String], max: usize) -> ApiResult<()> {
+    if values.len() > max {
+        return Err(ApiError::Validation(format!(
+            "too many {field_name} values: {} (max {max})",
+            values.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_multi_value_query_collects_every_occurrence_of_the_key() {
+        assert_eq!(
+            parse_multi_value_query("pet_type=dog&pet_type=cat&sort=price_asc", "pet_type"),
+            vec!["dog".to_string(), "cat".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_multi_value_query_returns_empty_when_the_key_is_absent() {
+        assert!(parse_multi_value_query("sort=price_asc", "pet_type").is_empty());
+    }
+
+    #[test]
+    fn enforce_filter_cap_allows_exactly_the_maximum() {
+        let values = vec!["dog".to_string(); 10];
+        assert!(enforce_filter_cap("pet_type", &values, 10).is_ok());
+    }
+
+    #[test]
+    fn enforce_filter_cap_rejects_one_over_the_maximum() {
+        let values = vec!["dog".to_string(); 11];
+        let result = enforce_filter_cap("pet_type", &values, 10);
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+
+    #[test]
+    fn clamp_ingredients_limit_uses_the_cap_when_the_caller_asks_for_nothing() {
+        assert_eq!(clamp_ingredients_limit(None, 100), 100);
+    }
+
+    #[test]
+    fn clamp_ingredients_limit_honors_a_request_under_the_cap() {
+        assert_eq!(clamp_ingredients_limit(Some(5), 100), 5);
+    }
+
+    #[test]
+    fn clamp_ingredients_limit_clamps_a_request_over_the_cap_down_to_it() {
+        assert_eq!(clamp_ingredients_limit(Some(1_000), 100), 100);
+    }
+}
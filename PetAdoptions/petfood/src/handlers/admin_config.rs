@@ -0,0 +1,12 @@
+use axum::extract::State;
+use axum::Json;
+
+use crate::config::RedactedConfig;
+use crate::state::AppState;
+
+/// `GET /api/admin/config` — the effective config the service is actually
+/// running with, for debugging deployment issues, with SSM-resolved
+/// sensitive values masked.
+pub async fn effective_config(State(state): State<AppState>) -> Json<RedactedConfig> {
+    Json(state.config.redacted())
+}
@@ -0,0 +1,68 @@
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::tenant::tenant_id;
+use crate::error::ApiResult;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize)]
+pub struct SeedDatabaseResponse {
+    foods_created: usize,
+    rejected: Vec<crate::models::SeedRejection>,
+}
+
+pub async fn seed_database(State(state): State<AppState>, headers: HeaderMap) -> ApiResult<Json<SeedDatabaseResponse>> {
+    let result = state
+        .admin_state
+        .seed_database(
+            &state.food_service,
+            tenant_id(&headers),
+            state.config.seed_batch_concurrency,
+            state.config.seed_min_description_length,
+            &state.config.seed_banned_placeholder_substrings,
+            state.config.max_seed_items,
+        )
+        .await?;
+    state.recommendation_service.invalidate_cache();
+    Ok(Json(SeedDatabaseResponse { foods_created: result.created, rejected: result.rejected }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CleanupDatabaseQuery {
+    older_than_days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CleanupDatabaseResponse {
+    carts_removed: usize,
+}
+
+pub async fn cleanup_database(
+    State(state): State<AppState>,
+    Query(query): Query<CleanupDatabaseQuery>,
+) -> ApiResult<Json<CleanupDatabaseResponse>> {
+    let carts_removed = state
+        .admin_state
+        .cleanup_database(&state.cart_service, query.older_than_days, state.config.max_seed_items)
+        .await?;
+    Ok(Json(CleanupDatabaseResponse { carts_removed }))
+}
+
+pub async fn setup_tables(State(state): State<AppState>) -> ApiResult<Json<()>> {
+    state.admin_state.setup_tables().await?;
+    Ok(Json(()))
+}
+
+/// Zeroes the in-memory demo counters (not the Prometheus registry, which
+/// stays monotonic) so an instructor can start a fresh workshop exercise
+/// without restarting the process.
+pub async fn reset_metrics() -> ApiResult<Json<()>> {
+    crate::metrics::DEMO_METRICS.reset();
+    Ok(Json(()))
+}
+
+pub async fn metrics_snapshot() -> ApiResult<Json<crate::metrics::DemoMetricsSnapshot>> {
+    Ok(Json(crate::metrics::DEMO_METRICS.snapshot()))
+}
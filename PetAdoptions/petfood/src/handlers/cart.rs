@@ -0,0 +1,125 @@
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::request_context::request_id;
+use super::tenant::tenant_id;
+use crate::error::{ApiError, ApiResult};
+use crate::json_extractor::ApiJson;
+use crate::models::{BulkAddResult, CartResponse, CartValidationResponse, CheckoutRequest, Order, Quantity};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct AddItemRequest {
+    pub food_id: String,
+    pub quantity: u32,
+}
+
+pub async fn add_item(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<AddItemRequest>,
+) -> ApiResult<Json<CartResponse>> {
+    let quantity = Quantity::try_from(req.quantity).map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    Ok(Json(
+        state
+            .cart_service
+            .add_item(&user_id, &req.food_id, quantity, tenant_id(&headers), request_id(&headers))
+            .await?,
+    ))
+}
+
+pub async fn bulk_add_items(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<Vec<AddItemRequest>>,
+) -> ApiResult<Json<Vec<BulkAddResult>>> {
+    if req.is_empty() {
+        return Err(ApiError::Validation("bulk add request must not be empty".to_string()));
+    }
+
+    let items = req.into_iter().map(|item| (item.food_id, item.quantity)).collect();
+
+    Ok(Json(
+        state
+            .cart_service
+            .bulk_add_items(&user_id, items, tenant_id(&headers), request_id(&headers))
+            .await,
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplyCouponRequest {
+    pub code: String,
+}
+
+pub async fn apply_coupon(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<ApplyCouponRequest>,
+) -> ApiResult<Json<CartResponse>> {
+    Ok(Json(state.cart_service.apply_coupon(&user_id, &req.code, tenant_id(&headers)).await?))
+}
+
+pub async fn validate_cart(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<CartValidationResponse>> {
+    let issues = state.cart_service.validate_cart(&user_id, tenant_id(&headers)).await?;
+    Ok(Json(CartValidationResponse::new(issues)))
+}
+
+pub async fn checkout_cart(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<Order>> {
+    let request = if body.is_empty() {
+        CheckoutRequest::default()
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|e| ApiError::Validation(format!("invalid checkout request body: {e}")))?
+    };
+
+    Ok(Json(
+        state
+            .cart_service
+            .checkout_cart(&user_id, request, tenant_id(&headers), request_id(&headers))
+            .await?,
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CleanupCartsQuery {
+    older_than_days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CleanupCartsResponse {
+    removed: usize,
+}
+
+pub async fn cleanup_carts(
+    State(state): State<AppState>,
+    Query(query): Query<CleanupCartsQuery>,
+) -> ApiResult<Json<CleanupCartsResponse>> {
+    if query.older_than_days < 0 {
+        return Err(ApiError::Validation(
+            "older_than_days must not be negative".to_string(),
+        ));
+    }
+
+    let removed = state
+        .cart_service
+        .cleanup_stale_carts(query.older_than_days, state.config.max_seed_items)
+        .await?;
+    Ok(Json(CleanupCartsResponse { removed }))
+}
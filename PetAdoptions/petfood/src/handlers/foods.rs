@@ -0,0 +1,772 @@
+use axum::extract::{Path, Query, RawQuery, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::request_context::request_id;
+use super::tenant::tenant_id;
+use crate::api_version::ApiVersion;
+use crate::canonical_json::{canonical_json_enabled, CanonicalJson};
+use crate::capacity_budget::CapacityBudget;
+use crate::error::{ApiError, ApiResult};
+use crate::etag::{if_none_match_satisfied, weak_etag};
+use crate::filters::{
+    enforce_filter_cap, max_category_filters, max_exclude_ingredients_filters, max_pet_type_filters,
+    parse_multi_value_query,
+};
+use crate::json_extractor::ApiJson;
+use crate::models::{AuditEntry, AvailabilityStatus, CreateFoodRequest, CreationSource, Food, FoodResponse, FoodSort, PetType, SeedRejection};
+use crate::service::{BulkCreateResult, StockAdjustment};
+use crate::state::AppState;
+
+/// `Some(304)` if `if_none_match` already names `etag` (the client already
+/// has this exact version cached), `None` if `get_food` should render the
+/// full body.
+fn etag_response(if_none_match: Option<&str>, etag: &str) -> Option<Response> {
+    if_none_match_satisfied(if_none_match, etag).then(|| (StatusCode::NOT_MODIFIED, [(header::ETAG, etag.to_string())]).into_response())
+}
+
+fn parse_pet_type(pet_type: &str) -> ApiResult<PetType> {
+    match pet_type {
+        "dog" => Ok(PetType::Dog),
+        "cat" => Ok(PetType::Cat),
+        "bird" => Ok(PetType::Bird),
+        "fish" => Ok(PetType::Fish),
+        "other" => Ok(PetType::Other),
+        other => Err(ApiError::Validation(format!("unknown pet_type {other}"))),
+    }
+}
+
+/// `?search=` runs `FoodService::search_foods_ranked` instead of the plain
+/// catalog scan, so results come back ordered by relevance (name match >
+/// ingredient match > description match) rather than scan order. The other
+/// filters (`pet_type`, `exclude_ingredients`, `category`, `in_stock_only`)
+/// still apply on top, and an explicit `?sort=` still overrides the
+/// relevance order, same as it overrides scan order today.
+pub async fn list_foods(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    RawQuery(query): RawQuery,
+) -> ApiResult<Json<Vec<FoodResponse>>> {
+    let query = query.unwrap_or_default();
+    let pet_type_values = parse_multi_value_query(&query, "pet_type");
+    let exclude_ingredients = parse_multi_value_query(&query, "exclude_ingredients");
+    let categories = parse_multi_value_query(&query, "category");
+
+    let in_stock_only_values = parse_multi_value_query(&query, "in_stock_only");
+    let sort_values = parse_multi_value_query(&query, "sort");
+    let search_term = parse_multi_value_query(&query, "search").into_iter().next().filter(|term| !term.is_empty());
+
+    enforce_filter_cap("pet_type", &pet_type_values, max_pet_type_filters())?;
+    enforce_filter_cap("exclude_ingredients", &exclude_ingredients, max_exclude_ingredients_filters())?;
+    enforce_filter_cap("category", &categories, max_category_filters())?;
+
+    let pet_types = pet_type_values
+        .iter()
+        .map(|value| parse_pet_type(value))
+        .collect::<ApiResult<Vec<PetType>>>()?;
+    let in_stock_only = resolve_in_stock_only(&in_stock_only_values, state.config.hide_out_of_stock_by_default);
+    let sort = sort_values.first().map(|value| FoodSort::parse(value)).transpose()?;
+
+    let currency = crate::money::resolve_currency(&headers, &query, &state.config.default_currency);
+
+    let mut filtered = match &search_term {
+        Some(term) => {
+            let ranked = state.food_service.search_foods_ranked(tenant_id(&headers), term).await?;
+            apply_food_filters(
+                ranked.into_iter().map(|result| result.food).collect(),
+                &pet_types,
+                &exclude_ingredients,
+                &categories,
+                in_stock_only,
+            )
+        }
+        None => {
+            let budget = state.config.capacity_budget_rcu.map(CapacityBudget::new);
+            let foods = state.food_service.list_foods_within_budget(tenant_id(&headers), budget.as_ref()).await?;
+            apply_food_filters(foods, &pet_types, &exclude_ingredients, &categories, in_stock_only)
+        }
+    };
+    if let Some(sort) = sort {
+        sort_foods(&mut filtered, sort);
+    }
+    Ok(Json(
+        filtered
+            .iter()
+            .map(|food| {
+                food.to_response(state.config.stock_visibility, state.config.low_stock_threshold, &currency, &state.config.default_currency)
+            })
+            .collect(),
+    ))
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct FoodCountResponse {
+    count: usize,
+}
+
+/// Same filters as `GET /api/foods` (`pet_type`, `exclude_ingredients`,
+/// `category`, `in_stock_only`), but returns only the resulting count instead of the
+/// matching foods — for faceted-search UIs that want to show "N results"
+/// without paying to serialize and transfer items the caller won't render.
+pub async fn count_foods(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    RawQuery(query): RawQuery,
+) -> ApiResult<Json<FoodCountResponse>> {
+    let query = query.unwrap_or_default();
+    let pet_type_values = parse_multi_value_query(&query, "pet_type");
+    let exclude_ingredients = parse_multi_value_query(&query, "exclude_ingredients");
+    let categories = parse_multi_value_query(&query, "category");
+    let in_stock_only_values = parse_multi_value_query(&query, "in_stock_only");
+
+    enforce_filter_cap("pet_type", &pet_type_values, max_pet_type_filters())?;
+    enforce_filter_cap("exclude_ingredients", &exclude_ingredients, max_exclude_ingredients_filters())?;
+    enforce_filter_cap("category", &categories, max_category_filters())?;
+
+    let pet_types = pet_type_values
+        .iter()
+        .map(|value| parse_pet_type(value))
+        .collect::<ApiResult<Vec<PetType>>>()?;
+    let in_stock_only = resolve_in_stock_only(&in_stock_only_values, state.config.hide_out_of_stock_by_default);
+
+    let budget = state.config.capacity_budget_rcu.map(CapacityBudget::new);
+    let foods = state.food_service.list_foods_within_budget(tenant_id(&headers), budget.as_ref()).await?;
+    let count = apply_food_filters(foods, &pet_types, &exclude_ingredients, &categories, in_stock_only).len();
+    Ok(Json(FoodCountResponse { count }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetFoodsBatchRequest {
+    ids: Vec<String>,
+}
+
+/// Fetches multiple foods by id in one request, for callers (cart/order
+/// rendering, admin bulk views) that would otherwise issue one `GET
+/// /api/foods/:food_id` per item. Ids with no matching food are omitted
+/// from the response map rather than causing the whole request to fail.
+pub async fn get_foods_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<GetFoodsBatchRequest>,
+) -> ApiResult<Json<std::collections::HashMap<String, Food>>> {
+    Ok(Json(state.food_service.get_foods_batch(&req.ids, tenant_id(&headers)).await?))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListIngredientsQuery {
+    limit: Option<usize>,
+}
+
+/// Distinct ingredients across the catalog, most common first, for filter
+/// UIs that want to populate an `exclude_ingredients` picker without
+/// pulling down every food. `?limit=` is clamped to
+/// `PETFOOD_MAX_INGREDIENTS_LIST_LIMIT` regardless of what the caller asks
+/// for.
+pub async fn list_ingredients(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ListIngredientsQuery>,
+) -> ApiResult<Json<Vec<crate::models::IngredientCount>>> {
+    let limit = crate::filters::resolve_ingredients_limit(query.limit);
+    Ok(Json(state.food_service.list_ingredients(tenant_id(&headers), limit).await?))
+}
+
+/// Resolves `?in_stock_only=` against the configured default: an explicit
+/// `true`/`false` always wins, and an absent or unrecognized value falls
+/// back to `PETFOOD_HIDE_OUT_OF_STOCK_DEFAULT` so storefronts that want
+/// out-of-stock foods hidden don't have to pass the flag on every request.
+fn resolve_in_stock_only(values: &[String], hide_out_of_stock_by_default: bool) -> bool {
+    match values.first().map(String::as_str) {
+        Some("true") => true,
+        Some("false") => false,
+        _ => hide_out_of_stock_by_default,
+    }
+}
+
+/// Applied in-process after the repository read, same as the recommendation
+/// service's filtering — the catalog is small enough that a full scan plus
+/// client-side filtering doesn't need a dedicated query path.
+fn apply_food_filters(
+    foods: Vec<Food>,
+    pet_types: &[PetType],
+    exclude_ingredients: &[String],
+    categories: &[String],
+    in_stock_only: bool,
+) -> Vec<Food> {
+    foods
+        .into_iter()
+        .filter(|food| pet_types.is_empty() || pet_types.contains(&food.pet_type))
+        .filter(|food| {
+            !food
+                .ingredients
+                .iter()
+                .any(|ingredient| exclude_ingredients.iter().any(|excluded| excluded == ingredient))
+        })
+        .filter(|food| categories.is_empty() || categories.iter().any(|category| food.categories.contains(category)))
+        .filter(|food| !in_stock_only || food.availability_status == AvailabilityStatus::InStock)
+        .collect()
+}
+
+/// Orders `foods` per `?sort=` on `GET /api/foods`. Ties (e.g. equal
+/// prices) fall back to `food_id` so responses are deterministic across
+/// calls, rather than depending on whatever order the repository returned
+/// items in.
+fn sort_foods(foods: &mut [Food], sort: FoodSort) {
+    match sort {
+        FoodSort::PriceAsc => foods.sort_by(|a, b| a.price_cents.cmp(&b.price_cents).then_with(|| a.food_id.cmp(&b.food_id))),
+        FoodSort::PriceDesc => foods.sort_by(|a, b| b.price_cents.cmp(&a.price_cents).then_with(|| a.food_id.cmp(&b.food_id))),
+        FoodSort::NameAsc => foods.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.food_id.cmp(&b.food_id))),
+        FoodSort::NameDesc => foods.sort_by(|a, b| b.name.cmp(&a.name).then_with(|| a.food_id.cmp(&b.food_id))),
+        FoodSort::NewestFirst => foods.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| a.food_id.cmp(&b.food_id))),
+        FoodSort::StockDesc => foods.sort_by(|a, b| b.stock_quantity.cmp(&a.stock_quantity).then_with(|| a.food_id.cmp(&b.food_id))),
+    }
+}
+
+/// A single food is cacheable by callers keying off its `food_id`, so this
+/// is the one read endpoint that honors `PETFOOD_CANONICAL_JSON_ENABLED`,
+/// giving it a byte-stable body for ETag purposes. It's also the one
+/// endpoint wired up to [`ApiVersion`] negotiation so far, establishing the
+/// plumbing: `Accept: application/vnd.petfood.v2+json` or
+/// `?api_version=2` nests the food under `data`/`meta` instead of today's
+/// flat body.
+///
+/// Also the one endpoint that sets `ETag`: a weak tag derived from the
+/// food's `version` and `updated_at` (see [`crate::etag`]), so a poller
+/// that sends it back as `If-None-Match` gets `304 Not Modified` with no
+/// body instead of re-downloading a payload that hasn't changed.
+pub async fn get_food(
+    State(state): State<AppState>,
+    Path(food_id): Path<String>,
+    headers: HeaderMap,
+    RawQuery(query): RawQuery,
+) -> ApiResult<Response> {
+    let food = state
+        .food_service
+        .get_food(&food_id, tenant_id(&headers), request_id(&headers))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("food {food_id} not found")))?;
+
+    let etag = weak_etag(food.version, food.updated_at);
+    let if_none_match = headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok());
+    if let Some(not_modified) = etag_response(if_none_match, &etag) {
+        return Ok(not_modified);
+    }
+
+    let query = query.unwrap_or_default();
+    let currency = crate::money::resolve_currency(&headers, &query, &state.config.default_currency);
+    let response = food.to_response(state.config.stock_visibility, state.config.low_stock_threshold, &currency, &state.config.default_currency);
+    let version = ApiVersion::resolve(&headers, &query);
+    let body = crate::api_version::wrap(version, response);
+
+    let rendered = if canonical_json_enabled() {
+        CanonicalJson(body).into_response()
+    } else {
+        Json(body).into_response()
+    };
+
+    Ok(([(header::ETAG, etag)], rendered).into_response())
+}
+
+pub async fn create_food(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<CreateFoodRequest>,
+) -> ApiResult<Json<Food>> {
+    let food = state
+        .food_service
+        .create_food(
+            req,
+            tenant_id(&headers),
+            &state.config.allowed_image_domains,
+            CreationSource::Api,
+            state.config.trust_seed,
+        )
+        .await?;
+    state.recommendation_service.invalidate_cache();
+    Ok(Json(food))
+}
+
+/// `POST /api/admin/foods/bulk`'s response: every food that validated and
+/// was written, and every one that failed validation along with why.
+/// Responds `201` when every record was created, `207` (Multi-Status) when
+/// the batch was only partially successful.
+#[derive(Debug, serde::Serialize)]
+pub struct BulkCreateFoodsResponse {
+    created: Vec<Food>,
+    failed: Vec<SeedRejection>,
+}
+
+impl IntoResponse for BulkCreateFoodsResponse {
+    fn into_response(self) -> Response {
+        let status = if self.failed.is_empty() { StatusCode::CREATED } else { StatusCode::MULTI_STATUS };
+        (status, Json(self)).into_response()
+    }
+}
+
+impl From<BulkCreateResult> for BulkCreateFoodsResponse {
+    fn from(result: BulkCreateResult) -> Self {
+        Self { created: result.created, failed: result.failed }
+    }
+}
+
+/// Bulk-loads a catalog from an uploaded JSON array of `CreateFoodRequest`,
+/// for seeding or migrating a large number of foods in one call instead of
+/// one `POST /api/admin/foods` per record. Unlike `POST /api/admin/foods`,
+/// a record that fails validation doesn't fail the whole request — it's
+/// reported back in `failed` and the rest of the batch still gets written.
+pub async fn bulk_create_foods(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ApiJson(requests): ApiJson<Vec<CreateFoodRequest>>,
+) -> ApiResult<BulkCreateFoodsResponse> {
+    let result = state
+        .food_service
+        .bulk_create_foods(requests, tenant_id(&headers), &state.config.allowed_image_domains)
+        .await?;
+    if !result.created.is_empty() {
+        state.recommendation_service.invalidate_cache();
+    }
+    Ok(BulkCreateFoodsResponse::from(result))
+}
+
+pub async fn delete_food(
+    State(state): State<AppState>,
+    Path(food_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<()>> {
+    state
+        .food_service
+        .delete_food(
+            &food_id,
+            tenant_id(&headers),
+            state.config.prevent_empty_catalog,
+            state.config.catalog_size_alert_drop_threshold_percent,
+            request_id(&headers),
+        )
+        .await?;
+    state.recommendation_service.invalidate_cache();
+    Ok(Json(()))
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ValidateFoodResponse {
+    valid: bool,
+    errors: Vec<String>,
+}
+
+/// Runs `CreateFoodRequest` validation without touching the repository, so
+/// an admin UI can show inline feedback before the caller actually submits
+/// the food. Always responds 200 — a failing validation is a normal result,
+/// not an error.
+pub async fn validate_food(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<CreateFoodRequest>,
+) -> Json<ValidateFoodResponse> {
+    let errors = crate::service::FoodService::validate_create_food(&req, &state.config.allowed_image_domains);
+    Json(ValidateFoodResponse { valid: errors.is_empty(), errors })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePriceRequest {
+    price_cents: i64,
+}
+
+pub async fn update_price(
+    State(state): State<AppState>,
+    Path(food_id): Path<String>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<UpdatePriceRequest>,
+) -> ApiResult<Json<Food>> {
+    let food = state
+        .food_service
+        .update_price(&food_id, req.price_cents, tenant_id(&headers), request_id(&headers))
+        .await?;
+    state.recommendation_service.invalidate_cache();
+    Ok(Json(food))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdjustStockRequest {
+    delta: Option<i32>,
+    set: Option<u32>,
+}
+
+/// `PATCH /api/admin/foods/:food_id/stock`. Exactly one of `delta` (a
+/// relative change, which may be negative) or `set` (an absolute value)
+/// must be present; sending both or neither is a `400`, same as an
+/// ambiguous or empty request body elsewhere in this API.
+pub async fn adjust_stock(
+    State(state): State<AppState>,
+    Path(food_id): Path<String>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<AdjustStockRequest>,
+) -> ApiResult<Json<Food>> {
+    let adjustment = match (req.delta, req.set) {
+        (Some(delta), None) => StockAdjustment::Delta(delta),
+        (None, Some(set)) => StockAdjustment::Set(set),
+        (None, None) => return Err(ApiError::Validation("stock adjustment requires either delta or set".to_string())),
+        (Some(_), Some(_)) => return Err(ApiError::Validation("stock adjustment accepts either delta or set, not both".to_string())),
+    };
+
+    let food = state.food_service.adjust_stock(&food_id, adjustment, tenant_id(&headers)).await?;
+    state.recommendation_service.invalidate_cache();
+    Ok(Json(food))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkUpdatePricesRequest {
+    updates: Vec<FoodPriceUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FoodPriceUpdate {
+    food_id: String,
+    price_cents: i64,
+}
+
+pub async fn bulk_update_prices(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<BulkUpdatePricesRequest>,
+) -> ApiResult<Json<Vec<Food>>> {
+    let updates = req.updates.into_iter().map(|u| (u.food_id, u.price_cents)).collect();
+    let foods = state
+        .food_service
+        .bulk_update_prices(updates, tenant_id(&headers), request_id(&headers))
+        .await?;
+    state.recommendation_service.invalidate_cache();
+    Ok(Json(foods))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListFoodChangesQuery {
+    since: String,
+}
+
+pub async fn list_food_changes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ListFoodChangesQuery>,
+) -> ApiResult<Json<Vec<Food>>> {
+    let since = DateTime::parse_from_rfc3339(&query.since)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| ApiError::Validation(format!("invalid since timestamp: {}", query.since)))?;
+
+    Ok(Json(
+        state
+            .food_service
+            .list_changes_since(since, tenant_id(&headers))
+            .await?,
+    ))
+}
+
+/// Caps how many audit entries `GET /api/admin/foods/:food_id/history`
+/// returns when the caller doesn't pass `?limit=`.
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct FoodHistoryQuery {
+    limit: Option<usize>,
+}
+
+pub async fn get_food_history(
+    State(state): State<AppState>,
+    Path(food_id): Path<String>,
+    Query(query): Query<FoodHistoryQuery>,
+) -> ApiResult<Json<Vec<AuditEntry>>> {
+    let mut history = state.food_service.history_for(&food_id).await?;
+    history.truncate(query.limit.unwrap_or(DEFAULT_HISTORY_LIMIT));
+    Ok(Json(history))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FoodType;
+
+    fn food(food_id: &str, pet_type: PetType, ingredients: Vec<&str>) -> Food {
+        Food {
+            food_id: food_id.to_string(),
+            name: food_id.to_string(),
+            description: String::new(),
+            ingredients: ingredients.into_iter().map(str::to_string).collect(),
+            price_cents: 100,
+            stock_quantity: 5,
+            availability_status: AvailabilityStatus::InStock,
+            pet_type,
+            food_type: FoodType::Dry,
+            image_path: String::new(),
+            categories: Vec::new(),
+            prices: std::collections::HashMap::new(),
+            updated_at: Utc::now(),
+            version: 0,
+        }
+    }
+
+    fn food_with_categories(food_id: &str, categories: Vec<&str>) -> Food {
+        Food {
+            categories: categories.into_iter().map(str::to_string).collect(),
+            ..food(food_id, PetType::Dog, vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn a_get_followed_by_a_conditional_get_with_its_etag_yields_304() {
+        let food = food("food-1", PetType::Dog, vec!["chicken"]);
+        let etag = weak_etag(food.version, food.updated_at);
+
+        // First request: no If-None-Match yet, so get_food would render the full body.
+        assert!(etag_response(None, &etag).is_none());
+
+        // Second request: the client echoes back the ETag it captured from the first response.
+        let response = etag_response(Some(etag.as_str()), &etag).expect("a matching If-None-Match should short-circuit");
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG).unwrap(), etag.as_str());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty(), "304 Not Modified must not carry a body");
+    }
+
+    #[test]
+    fn etag_response_is_none_for_a_non_matching_if_none_match() {
+        let etag = weak_etag(3, Utc::now());
+
+        assert!(etag_response(Some(r#"W/"2-999""#), &etag).is_none());
+    }
+
+    #[test]
+    fn apply_food_filters_with_no_filters_keeps_everything() {
+        let foods = vec![food("dog-food", PetType::Dog, vec!["chicken"])];
+
+        let filtered = apply_food_filters(foods, &[], &[], &[], false);
+
+        assert_eq!(filtered.len(), 1);
+    }
+
+    #[test]
+    fn apply_food_filters_keeps_only_matching_pet_types() {
+        let foods = vec![
+            food("dog-food", PetType::Dog, vec![]),
+            food("cat-food", PetType::Cat, vec![]),
+        ];
+
+        let filtered = apply_food_filters(foods, &[PetType::Dog], &[], &[], false);
+
+        let ids: Vec<&str> = filtered.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["dog-food"]);
+    }
+
+    #[test]
+    fn apply_food_filters_drops_foods_containing_an_excluded_ingredient() {
+        let foods = vec![
+            food("with-corn", PetType::Dog, vec!["corn", "chicken"]),
+            food("without-corn", PetType::Dog, vec!["chicken"]),
+        ];
+
+        let filtered = apply_food_filters(foods, &[], &["corn".to_string()], &[], false);
+
+        let ids: Vec<&str> = filtered.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["without-corn"]);
+    }
+
+    #[test]
+    fn apply_food_filters_drops_out_of_stock_foods_when_in_stock_only_is_set() {
+        let mut out_of_stock = food("out-of-stock", PetType::Dog, vec![]);
+        out_of_stock.availability_status = AvailabilityStatus::OutOfStock;
+        let foods = vec![food("in-stock", PetType::Dog, vec![]), out_of_stock];
+
+        let filtered = apply_food_filters(foods, &[], &[], &[], true);
+
+        let ids: Vec<&str> = filtered.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["in-stock"]);
+    }
+
+    #[test]
+    fn apply_food_filters_keeps_only_foods_matching_any_requested_category() {
+        let foods = vec![
+            food_with_categories("grain-free", vec!["grain-free", "senior"]),
+            food_with_categories("senior", vec!["senior"]),
+            food_with_categories("uncategorized", vec![]),
+        ];
+
+        let filtered = apply_food_filters(foods, &[], &[], &["grain-free".to_string()], false);
+
+        let ids: Vec<&str> = filtered.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["grain-free"]);
+    }
+
+    #[test]
+    fn apply_food_filters_with_no_category_filter_keeps_uncategorized_foods() {
+        let foods = vec![food_with_categories("uncategorized", vec![])];
+
+        let filtered = apply_food_filters(foods, &[], &[], &[], false);
+
+        assert_eq!(filtered.len(), 1);
+    }
+
+    #[test]
+    fn enforce_filter_cap_rejects_too_many_category_values_for_list_foods() {
+        let values = vec!["grain-free".to_string(); max_category_filters() + 1];
+        let result = enforce_filter_cap("category", &values, max_category_filters());
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+
+    #[test]
+    fn resolve_in_stock_only_falls_back_to_the_configured_default_when_unspecified() {
+        assert!(resolve_in_stock_only(&[], true));
+        assert!(!resolve_in_stock_only(&[], false));
+    }
+
+    #[test]
+    fn resolve_in_stock_only_honors_an_explicit_value_over_the_default() {
+        assert!(!resolve_in_stock_only(&["false".to_string()], true));
+        assert!(resolve_in_stock_only(&["true".to_string()], false));
+    }
+
+    #[test]
+    fn enforce_filter_cap_rejects_too_many_pet_type_values_for_list_foods() {
+        let values = vec!["dog".to_string(); max_pet_type_filters() + 1];
+        let result = enforce_filter_cap("pet_type", &values, max_pet_type_filters());
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+
+    #[test]
+    fn enforce_filter_cap_accepts_exactly_the_max_exclude_ingredients_values_for_list_foods() {
+        let values = vec!["corn".to_string(); max_exclude_ingredients_filters()];
+        assert!(enforce_filter_cap("exclude_ingredients", &values, max_exclude_ingredients_filters()).is_ok());
+    }
+
+    // `count_foods` returns `apply_food_filters(..).len()`, so these exercise
+    // the same filtering logic the count endpoint is backed by, under the
+    // same filter combinations `list_foods` supports.
+
+    #[test]
+    fn count_with_no_filters_counts_everything() {
+        let foods = vec![
+            food("dog-food", PetType::Dog, vec!["chicken"]),
+            food("cat-food", PetType::Cat, vec!["salmon"]),
+        ];
+
+        let count = apply_food_filters(foods, &[], &[], &[], false).len();
+
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn count_with_a_pet_type_filter_only_counts_matching_foods() {
+        let foods = vec![
+            food("dog-food", PetType::Dog, vec![]),
+            food("cat-food", PetType::Cat, vec![]),
+            food("bird-food", PetType::Bird, vec![]),
+        ];
+
+        let count = apply_food_filters(foods, &[PetType::Dog, PetType::Bird], &[], &[], false).len();
+
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn count_with_an_exclude_ingredients_filter_drops_matching_foods() {
+        let foods = vec![
+            food("with-corn", PetType::Dog, vec!["corn", "chicken"]),
+            food("without-corn", PetType::Dog, vec!["chicken"]),
+        ];
+
+        let count = apply_food_filters(foods, &[], &["corn".to_string()], &[], false).len();
+
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn count_combining_pet_type_exclude_ingredients_and_in_stock_only_filters() {
+        let mut out_of_stock_dog = food("out-of-stock-dog", PetType::Dog, vec!["chicken"]);
+        out_of_stock_dog.availability_status = AvailabilityStatus::OutOfStock;
+        let foods = vec![
+            food("in-stock-dog", PetType::Dog, vec!["chicken"]),
+            food("corn-dog", PetType::Dog, vec!["corn"]),
+            food("in-stock-cat", PetType::Cat, vec!["chicken"]),
+            out_of_stock_dog,
+        ];
+
+        let count = apply_food_filters(foods, &[PetType::Dog], &["corn".to_string()], &[], true).len();
+
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn count_with_filters_matching_nothing_is_zero() {
+        let foods = vec![food("dog-food", PetType::Dog, vec![])];
+
+        let count = apply_food_filters(foods, &[PetType::Cat], &[], &[], false).len();
+
+        assert_eq!(count, 0);
+    }
+
+    fn priced_food(food_id: &str, price_cents: i64) -> Food {
+        Food { price_cents, ..food(food_id, PetType::Dog, vec![]) }
+    }
+
+    #[test]
+    fn sort_foods_price_asc_orders_cheapest_first() {
+        let mut foods = vec![priced_food("b", 300), priced_food("a", 100), priced_food("c", 200)];
+
+        sort_foods(&mut foods, FoodSort::PriceAsc);
+
+        let ids: Vec<&str> = foods.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c", "b"]);
+    }
+
+    #[test]
+    fn sort_foods_price_desc_orders_priciest_first() {
+        let mut foods = vec![priced_food("b", 300), priced_food("a", 100), priced_food("c", 200)];
+
+        sort_foods(&mut foods, FoodSort::PriceDesc);
+
+        let ids: Vec<&str> = foods.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn sort_foods_breaks_ties_on_equal_price_by_food_id_for_determinism() {
+        let mut foods = vec![priced_food("z", 100), priced_food("a", 100)];
+
+        sort_foods(&mut foods, FoodSort::PriceAsc);
+
+        let ids: Vec<&str> = foods.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "z"]);
+    }
+
+    #[test]
+    fn sort_foods_stock_desc_orders_most_stock_first() {
+        let mut low = priced_food("low", 100);
+        low.stock_quantity = 1;
+        let mut high = priced_food("high", 100);
+        high.stock_quantity = 10;
+        let mut foods = vec![low, high];
+
+        sort_foods(&mut foods, FoodSort::StockDesc);
+
+        let ids: Vec<&str> = foods.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["high", "low"]);
+    }
+
+    #[test]
+    fn bulk_create_foods_response_is_207_when_some_records_failed() {
+        let response = BulkCreateFoodsResponse {
+            created: vec![food("food-1", PetType::Dog, vec![])],
+            failed: vec![SeedRejection { name: "bad-record".to_string(), errors: vec!["name cannot be blank".to_string()] }],
+        }
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::MULTI_STATUS);
+    }
+
+    #[test]
+    fn bulk_create_foods_response_is_201_when_every_record_succeeded() {
+        let response = BulkCreateFoodsResponse { created: vec![food("food-1", PetType::Dog, vec![])], failed: vec![] }.into_response();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+}
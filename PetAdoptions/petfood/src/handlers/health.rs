@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::{json, Value};
+
+use crate::readiness::probe_otlp_reachable;
+use crate::state::AppState;
+
+/// Liveness: the process is up and handling requests. Always `ok` — this is
+/// what a load balancer or orchestrator should use to decide whether to
+/// restart the instance, as opposed to [`ready`], which decides whether to
+/// route traffic to it. Also mounted at `/health/live`.
+pub async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// Unlike `/health`, this can fail: when `PETFOOD_READY_REQUIRES_OTLP` is
+/// set, the instance isn't ready until it can TCP-connect to the OTLP
+/// collector, and when `PETFOOD_READY_REQUIRES_AWS` is set, it also isn't
+/// ready until DynamoDB and SSM are reachable — so a load balancer won't
+/// route traffic to it while those dependencies would just fail the
+/// request. Each check only runs when its flag is set, so a deployment that
+/// enables neither still gets the old always-`ok` behavior. Also mounted at
+/// `/health/status` for callers that predate the liveness/readiness split.
+/// The body always carries an `events` field (see
+/// [`EventEmitter::health`](crate::events::EventEmitter::health)) regardless
+/// of these flags — it's diagnostic context for an operator, not something
+/// that fails readiness on its own.
+pub async fn ready(State(state): State<AppState>) -> impl IntoResponse {
+    let mut body = json!({});
+    let mut ready = true;
+
+    if state.config.ready_requires_otlp {
+        let otlp_reachable = probe_otlp_reachable(
+            &state.config.otlp_endpoint,
+            Duration::from_millis(state.config.otlp_probe_timeout_ms),
+        )
+        .await;
+        body["otlp_reachable"] = json!(otlp_reachable);
+        ready &= otlp_reachable;
+    }
+
+    if state.config.ready_requires_aws {
+        let status = state.readiness_checker.check().await;
+        body["dynamodb_reachable"] = json!(status.dynamodb_reachable);
+        body["ssm_reachable"] = json!(status.ssm_reachable);
+        ready &= status.all_reachable();
+    }
+
+    // Diagnostic only — the events subsystem's health never itself fails
+    // readiness, unlike the checks above.
+    body["events"] = json!(state.event_emitter.health());
+
+    body["status"] = json!(if ready { "ok" } else { "not_ready" });
+
+    let status_code = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status_code, Json(body))
+}
@@ -0,0 +1,19 @@
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+pub async fn metrics() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, METRICS_CONTENT_TYPE)], crate::metrics::render())
+}
+
+/// Scrapers sometimes probe with `HEAD /metrics` before the real `GET` —
+/// respond with the same content-type header a `GET` would carry, minus
+/// the body.
+pub async fn metrics_head() -> impl IntoResponse {
+    [(header::CONTENT_TYPE, METRICS_CONTENT_TYPE)]
+}
+
+pub async fn metrics_options() -> impl IntoResponse {
+    (StatusCode::NO_CONTENT, [(header::ALLOW, "GET, HEAD, OPTIONS")])
+}
@@ -0,0 +1,22 @@
+mod admin_config;
+mod admin_ops;
+mod cart;
+mod foods;
+mod health;
+mod metrics;
+mod not_found;
+mod recommendations;
+mod request_context;
+mod tenant;
+
+pub use admin_config::effective_config;
+pub use admin_ops::{cleanup_database, metrics_snapshot, reset_metrics, seed_database, setup_tables};
+pub use cart::{add_item, apply_coupon, bulk_add_items, checkout_cart, cleanup_carts, validate_cart};
+pub use foods::{
+    adjust_stock, bulk_create_foods, bulk_update_prices, count_foods, create_food, delete_food, get_food, get_food_history,
+    get_foods_batch, list_food_changes, list_foods, list_ingredients, update_price, validate_food,
+};
+pub use health::{health, ready};
+pub use metrics::{metrics, metrics_head, metrics_options};
+pub use not_found::not_found;
+pub use recommendations::{recommend, recommendation_stats, recommendation_stats_for_all_pet_types};
@@ -0,0 +1,12 @@
+use axum::http::{StatusCode, Uri};
+
+use crate::error::ProblemDetails;
+
+/// Fallback for routes that don't match any registered handler, so callers
+/// always get the standard `application/problem+json` envelope instead of
+/// axum's bare 404.
+pub async fn not_found(uri: Uri) -> ProblemDetails {
+    let mut problem = ProblemDetails::new("Not Found", StatusCode::NOT_FOUND, format!("no route for {uri}"), "NOT_FOUND");
+    problem.instance = Some(uri.to_string());
+    problem
+}
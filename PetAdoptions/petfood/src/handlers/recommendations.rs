@@ -0,0 +1,74 @@
+use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
+use axum::Json;
+use serde::Deserialize;
+
+use super::tenant::tenant_id;
+use crate::error::{ApiError, ApiResult};
+use crate::models::{PetType, RecommendationStats, RecommendationStatsForAllPetTypes, RecommendationsResponse, SortOrder};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct RecommendQuery {
+    pub sort: Option<String>,
+    /// When given, seeds the tie-break order so a given user sees a stable
+    /// ordering across requests — see `RecommendationService::recommend`.
+    pub user_id: Option<String>,
+}
+
+fn parse_pet_type(pet_type: &str) -> ApiResult<PetType> {
+    match pet_type {
+        "dog" => Ok(PetType::Dog),
+        "cat" => Ok(PetType::Cat),
+        "bird" => Ok(PetType::Bird),
+        "fish" => Ok(PetType::Fish),
+        "other" => Ok(PetType::Other),
+        other => Err(ApiError::Validation(format!("unknown pet_type {other}"))),
+    }
+}
+
+pub async fn recommend(
+    State(state): State<AppState>,
+    Path(pet_type): Path<String>,
+    Query(query): Query<RecommendQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Json<RecommendationsResponse>> {
+    let pet_type = parse_pet_type(&pet_type)?;
+    let sort = query.sort.as_deref().map(SortOrder::parse).transpose()?;
+
+    Ok(Json(
+        state
+            .recommendation_service
+            .recommend(pet_type, sort, tenant_id(&headers), query.user_id.as_deref())
+            .await?,
+    ))
+}
+
+pub async fn recommendation_stats(
+    State(state): State<AppState>,
+    Path(pet_type): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<RecommendationStats>> {
+    let pet_type = parse_pet_type(&pet_type)?;
+
+    Ok(Json(
+        state
+            .recommendation_service
+            .get_recommendation_stats(pet_type, tenant_id(&headers))
+            .await?,
+    ))
+}
+
+/// `GET /api/recommendations/stats`: stats for every pet type in one call,
+/// for a dashboard that would otherwise issue one request per pet type.
+pub async fn recommendation_stats_for_all_pet_types(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<RecommendationStatsForAllPetTypes>> {
+    Ok(Json(
+        state
+            .recommendation_service
+            .get_recommendation_stats_for_all_pet_types(tenant_id(&headers))
+            .await?,
+    ))
+}
@@ -0,0 +1,12 @@
+use axum::http::HeaderMap;
+
+/// Header carrying the caller's correlation id for this request, propagated
+/// into emitted events so operators can link them back to the API call.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+pub(super) fn request_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
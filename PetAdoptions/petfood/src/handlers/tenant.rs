@@ -0,0 +1,9 @@
+use axum::http::HeaderMap;
+
+/// Header used to select a tenant's isolated table when multi-tenant table
+/// resolution is enabled; ignored otherwise.
+const TENANT_HEADER: &str = "x-tenant-id";
+
+pub(super) fn tenant_id(headers: &HeaderMap) -> Option<&str> {
+    headers.get(TENANT_HEADER).and_then(|v| v.to_str().ok())
+}
@@ -0,0 +1,188 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use axum::extract::Request;
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::error::ProblemDetails;
+
+/// Path prefix exempted from HTTPS enforcement so load balancer health
+/// checks never fail because the probe doesn't set `X-Forwarded-Proto`.
+/// Matched as a prefix (`/health`, `/health/live`, `/health/ready`, ...)
+/// since health-check subpaths all share this exemption.
+const HEALTH_CHECK_PATH: &str = "/health";
+
+/// Toggled once at startup from `PETFOOD_REQUIRE_HTTPS`; read by
+/// [`enforce_https_middleware`] on every request.
+static REQUIRE_HTTPS: AtomicBool = AtomicBool::new(false);
+
+/// Encodes [`HttpsEnforcementMode`] for atomic storage: 0 = `Reject`, 1 =
+/// `Redirect`.
+static ENFORCEMENT_MODE: AtomicU8 = AtomicU8::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpsEnforcementMode {
+    /// Returns `426 Upgrade Required` with the standard error envelope.
+    Reject,
+    /// Returns a `301` redirect to the `https://` equivalent of the request.
+    Redirect,
+}
+
+impl HttpsEnforcementMode {
+    /// Unrecognized values fall back to `Reject`, the conservative choice:
+    /// refusing plaintext traffic outright rather than guessing at a
+    /// redirect target.
+    pub fn parse(value: &str) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "redirect" => Self::Redirect,
+            _ => Self::Reject,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Reject => 0,
+            Self::Redirect => 1,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Redirect,
+            _ => Self::Reject,
+        }
+    }
+}
+
+pub fn set_https_enforcement(enabled: bool, mode: HttpsEnforcementMode) {
+    REQUIRE_HTTPS.store(enabled, Ordering::Relaxed);
+    ENFORCEMENT_MODE.store(mode.to_u8(), Ordering::Relaxed);
+}
+
+/// Rejects (or redirects) requests that arrive over plain HTTP behind a
+/// TLS-terminating load balancer, detected via `X-Forwarded-Proto`. A
+/// missing header is let through rather than treated as plaintext, since an
+/// ALB that isn't configured to set it gives us no signal either way.
+pub async fn enforce_https_middleware(request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    if !REQUIRE_HTTPS.load(Ordering::Relaxed) || path == HEALTH_CHECK_PATH || path.starts_with("/health/") {
+        return next.run(request).await;
+    }
+
+    let forwarded_proto = request
+        .headers()
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok());
+
+    if !is_plaintext(forwarded_proto) {
+        return next.run(request).await;
+    }
+
+    match HttpsEnforcementMode::from_u8(ENFORCEMENT_MODE.load(Ordering::Relaxed)) {
+        HttpsEnforcementMode::Reject => reject_response(),
+        HttpsEnforcementMode::Redirect => redirect_response(&request),
+    }
+}
+
+fn is_plaintext(forwarded_proto: Option<&str>) -> bool {
+    forwarded_proto.is_some_and(|proto| proto.eq_ignore_ascii_case("http"))
+}
+
+fn reject_response() -> Response {
+    ProblemDetails::new(
+        "HTTPS Required",
+        StatusCode::UPGRADE_REQUIRED,
+        "this endpoint requires HTTPS; the request arrived over plain HTTP".to_string(),
+        "HTTPS_REQUIRED",
+    )
+    .into_response()
+}
+
+fn redirect_response(request: &Request) -> Response {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let path_and_query = request
+        .uri()
+        .path_and_query()
+        .map(|v| v.as_str())
+        .unwrap_or("/");
+    let location = format!("https://{host}{path_and_query}");
+
+    (StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, location)]).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Request as HttpRequest;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn ok_handler() -> &'static str {
+        "ok"
+    }
+
+    fn test_router() -> Router {
+        Router::new()
+            .route(HEALTH_CHECK_PATH, get(ok_handler))
+            .route("/health/live", get(ok_handler))
+            .route("/api/foods", get(ok_handler))
+            .layer(axum::middleware::from_fn(enforce_https_middleware))
+    }
+
+    fn request(path: &str, forwarded_proto: Option<&str>) -> HttpRequest<Body> {
+        let mut builder = HttpRequest::builder().uri(path).header(header::HOST, "example.com");
+        if let Some(proto) = forwarded_proto {
+            builder = builder.header("x-forwarded-proto", proto);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    /// Exercises every case in one test, rather than several tests that
+    /// independently flip the shared enforcement flags, since tests run
+    /// concurrently and could otherwise race on them.
+    #[tokio::test]
+    async fn enforcement_behaves_per_forwarded_proto_header_and_mode() {
+        set_https_enforcement(true, HttpsEnforcementMode::Reject);
+
+        let https_present = test_router().oneshot(request("/api/foods", Some("https"))).await.unwrap();
+        assert_eq!(https_present.status(), StatusCode::OK);
+
+        let header_absent = test_router().oneshot(request("/api/foods", None)).await.unwrap();
+        assert_eq!(header_absent.status(), StatusCode::OK);
+
+        let http_present = test_router().oneshot(request("/api/foods", Some("http"))).await.unwrap();
+        assert_eq!(http_present.status(), StatusCode::UPGRADE_REQUIRED);
+        assert_eq!(
+            http_present.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+        let body = axum::body::to_bytes(http_present.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "HTTPS_REQUIRED");
+
+        let health_check = test_router().oneshot(request(HEALTH_CHECK_PATH, Some("http"))).await.unwrap();
+        assert_eq!(health_check.status(), StatusCode::OK);
+
+        let health_subpath = test_router().oneshot(request("/health/live", Some("http"))).await.unwrap();
+        assert_eq!(health_subpath.status(), StatusCode::OK);
+
+        set_https_enforcement(true, HttpsEnforcementMode::Redirect);
+
+        let redirected = test_router().oneshot(request("/api/foods", Some("http"))).await.unwrap();
+        assert_eq!(redirected.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            redirected.headers().get(header::LOCATION).unwrap(),
+            "https://example.com/api/foods"
+        );
+
+        set_https_enforcement(false, HttpsEnforcementMode::Reject);
+    }
+}
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::de::DeserializeOwned;
+
+use crate::error::ApiError;
+
+/// Drop-in replacement for `axum::Json` on write endpoints: a malformed or
+/// type-mismatched body still returns the standard error envelope (via
+/// `ApiError::Validation`) instead of axum's bare-text rejection, carrying
+/// serde's own message and, where it has one, the line/column it points at.
+pub struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(req, state).await {
+            Ok(Json(value)) => Ok(ApiJson(value)),
+            // The body exceeded `DefaultBodyLimit` before we ever got to
+            // parsing it as JSON — a distinct `413`, not a `400` validation
+            // error.
+            Err(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                Err(ApiError::PayloadTooLarge(rejection.body_text()))
+            }
+            Err(rejection) => Err(ApiError::Validation(describe_rejection(rejection))),
+        }
+    }
+}
+
+/// `JsonRejection::body_text` already carries serde's own message plus, for
+/// a syntax error or a type mismatch, the line/column it points at — no
+/// need to re-derive that ourselves.
+fn describe_rejection(rejection: JsonRejection) -> String {
+    format!("invalid JSON body: {}", rejection.body_text())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{header, Request as HttpRequest};
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Payload {
+        #[allow(dead_code)]
+        name: String,
+        #[allow(dead_code)]
+        age: u32,
+    }
+
+    async fn extract(body: &'static str) -> Result<ApiJson<Payload>, ApiError> {
+        let request = HttpRequest::builder()
+            .method("POST")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        ApiJson::<Payload>::from_request(request, &()).await
+    }
+
+    #[tokio::test]
+    async fn truncated_json_body_is_reported_as_a_validation_error() {
+        let result = extract(r#"{"name": "kibble""#).await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+        let Err(ApiError::Validation(message)) = result else {
+            unreachable!();
+        };
+        assert!(message.starts_with("invalid JSON body: "));
+    }
+
+    #[tokio::test]
+    async fn a_type_mismatch_is_reported_as_a_validation_error_naming_the_field() {
+        let result = extract(r#"{"name": "kibble", "age": "not a number"}"#).await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+        let Err(ApiError::Validation(message)) = result else {
+            unreachable!();
+        };
+        assert!(message.contains("age"));
+    }
+
+    #[tokio::test]
+    async fn a_well_formed_body_still_extracts_successfully() {
+        let result = extract(r#"{"name": "kibble", "age": 3}"#).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn a_body_over_the_default_body_limit_is_reported_as_payload_too_large() {
+        use axum::extract::DefaultBodyLimit;
+        use axum::routing::post;
+        use axum::Router;
+        use tower::ServiceExt;
+
+        async fn echo(ApiJson(payload): ApiJson<Payload>) -> &'static str {
+            let _ = payload;
+            "ok"
+        }
+
+        let app = Router::new().route("/", post(echo)).layer(DefaultBodyLimit::max(16));
+        let oversized = format!(r#"{{"name": "{}", "age": 3}}"#, "x".repeat(32));
+
+        let response = app
+            .oneshot(
+                HttpRequest::builder()
+                    .method("POST")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(oversized))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
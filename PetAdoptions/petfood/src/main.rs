@@ -0,0 +1,262 @@
+mod admin;
+mod api_version;
+mod app;
+mod canonical_json;
+mod capacity_budget;
+mod config;
+mod cors;
+mod error;
+mod etag;
+mod events;
+mod filters;
+mod handlers;
+mod https_enforcement;
+mod json_extractor;
+mod metrics;
+mod middleware;
+mod models;
+mod money;
+mod readiness;
+mod repository;
+mod service;
+mod shutdown;
+mod ssm_cache;
+mod startup_probes;
+mod startup_timing;
+mod state;
+mod table_metrics;
+mod trust;
+mod ttl;
+mod warmup;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use admin::AdminState;
+use app::create_app;
+use config::ServerConfig;
+use events::{verify_event_bus_exists, EventEmitter, FoodEvent};
+use readiness::ReadinessChecker;
+use repository::{
+    DynamoDbAuditRepository, DynamoDbCartRepository, DynamoDbDiscountRepository, DynamoDbFoodRepository,
+    DynamoDbOrderRepository,
+};
+use service::{AuditLogger, CartService, FoodService, RecommendationService};
+use startup_timing::StartupTimings;
+use state::AppState;
+
+#[tokio::main]
+async fn main() {
+    let mut startup_timings = StartupTimings::new();
+    tracing_subscriber::fmt::init();
+
+    let phase_start = std::time::Instant::now();
+    let config = ServerConfig::from_env();
+    startup_timings.record("config_load", phase_start.elapsed());
+    config::log_ssm_parameter_resolutions(&config);
+    metrics::init_otel_metrics(config.otel_metrics_enabled);
+    metrics::set_metrics_max_label_values(config.metrics_max_label_values);
+    money::set_price_as_string(config.price_as_string);
+    middleware::set_slow_request_threshold_ms(config.slow_request_threshold_ms);
+    middleware::set_rate_limit(config.rate_limit_rps, config.rate_limit_burst);
+    middleware::set_trusted_proxy_allow_list(config.trusted_proxy_allow_list.clone());
+    cors::set_cors_allowed_origin(config.cors_allowed_origin.clone());
+    https_enforcement::set_https_enforcement(config.require_https, config.https_enforcement_mode);
+    canonical_json::set_canonical_json_enabled(config.canonical_json_enabled);
+    filters::set_filter_limits(
+        config.max_pet_type_filters,
+        config.max_exclude_ingredients_filters,
+        config.max_category_filters,
+    );
+    filters::set_max_ingredients_list_limit(config.max_ingredients_list_limit);
+    let phase_start = std::time::Instant::now();
+    let mut aws_config_loader =
+        aws_config::defaults(aws_config::BehaviorVersion::latest());
+    if let Some(region) = config.aws_region.clone() {
+        aws_config_loader = aws_config_loader.region(aws_config::Region::new(region));
+    }
+    let aws_config = aws_config_loader.load().await;
+
+    let dynamodb_client = aws_sdk_dynamodb::Client::new(&aws_config);
+    let eventbridge_client = aws_sdk_eventbridge::Client::new(&aws_config);
+    let ssm_client = aws_sdk_ssm::Client::new(&aws_config);
+    startup_timings.record("aws_client_init", phase_start.elapsed());
+
+    startup_probes::run_startup_probes(
+        &ssm_client,
+        &dynamodb_client,
+        &config.foods_table_name,
+        Duration::from_millis(config.startup_probe_timeout_ms),
+    )
+    .await;
+
+    if config.warm_connections_enabled {
+        let phase_start = std::time::Instant::now();
+        warmup::warm_connections(&dynamodb_client, &eventbridge_client, &config.foods_table_name).await;
+        startup_timings.record("table_warmup", phase_start.elapsed());
+    }
+
+    let food_repository = Arc::new(DynamoDbFoodRepository::new(
+        dynamodb_client.clone(),
+        config.foods_table_name.clone(),
+        config.multi_tenant_tables_enabled,
+    ));
+    let cart_repository = Arc::new(DynamoDbCartRepository::new(
+        dynamodb_client.clone(),
+        config.carts_table_name.clone(),
+        config.cart_ttl_days,
+        config.multi_tenant_tables_enabled,
+    ));
+    let order_repository = Arc::new(DynamoDbOrderRepository::new(
+        dynamodb_client.clone(),
+        config.orders_table_name.clone(),
+    ));
+    let discount_repository = Arc::new(DynamoDbDiscountRepository::new(
+        dynamodb_client.clone(),
+        config.discounts_table_name.clone(),
+    ));
+    let event_bus_enabled = match verify_event_bus_exists(&eventbridge_client, &config.event_bus_name).await {
+        Ok(exists) => exists,
+        Err(err) => {
+            tracing::warn!(error = %err, "could not verify EventBridge bus existence, assuming it exists");
+            true
+        }
+    };
+    if !event_bus_enabled {
+        assert!(
+            !config.event_bus_strict,
+            "configured EventBridge bus {:?} does not exist",
+            config.event_bus_name
+        );
+        tracing::warn!(
+            event_bus_name = %config.event_bus_name,
+            "configured EventBridge bus does not exist; disabling event emission"
+        );
+    }
+    let event_retry_timeout = std::time::Duration::from_secs(config.event_retry_timeout_seconds);
+    let analytics_emitter = config.analytics_events_enabled.then(|| {
+        Arc::new(
+            EventEmitter::with_concurrency_limit(
+                eventbridge_client.clone(),
+                config.event_bus_name.clone(),
+                config.event_max_concurrency,
+                config.event_shed_when_saturated,
+            )
+            .with_enabled(event_bus_enabled)
+            .with_source("petfood.analytics".to_string())
+            .with_retry(config.event_retry_attempts, event_retry_timeout),
+        )
+    });
+    let mut event_emitter = EventEmitter::with_concurrency_limit(
+        eventbridge_client,
+        config.event_bus_name.clone(),
+        config.event_max_concurrency,
+        config.event_shed_when_saturated,
+    )
+    .with_enabled(event_bus_enabled)
+    .with_retry(config.event_retry_attempts, event_retry_timeout);
+    if let Some(table_name) = config.event_idempotency_table_name.clone() {
+        event_emitter = event_emitter.with_idempotency_table(dynamodb_client.clone(), table_name);
+    }
+    let event_emitter = Arc::new(event_emitter);
+    emit_service_started_event(&event_emitter, &config).await;
+    let audit_repository = Arc::new(DynamoDbAuditRepository::new(
+        dynamodb_client.clone(),
+        config.audit_table_name.clone(),
+    ));
+    let audit_logger = Arc::new(AuditLogger::new(audit_repository));
+
+    let readiness_checker = Arc::new(ReadinessChecker::new(
+        dynamodb_client.clone(),
+        ssm_client,
+        config.foods_table_name.clone(),
+        config.carts_table_name.clone(),
+        Duration::from_millis(config.readiness_probe_timeout_ms),
+        config.readiness_cache_ttl_ms.map(Duration::from_millis),
+    ));
+
+    let state = AppState {
+        food_service: Arc::new(
+            FoodService::new(food_repository.clone(), event_emitter.clone(), audit_logger)
+                .with_missing_image_emit_window(config.missing_image_emit_window_ms.map(Duration::from_millis))
+                .with_analytics_emitter(analytics_emitter.clone()),
+        ),
+        cart_service: Arc::new(
+            CartService::new(
+                cart_repository,
+                food_repository.clone(),
+                order_repository,
+                discount_repository,
+                event_emitter.clone(),
+                config.add_dedupe_window_ms.map(Duration::from_millis),
+            )
+            .with_high_value_cart_threshold_cents(config.high_value_cart_threshold_cents)
+            .with_food_lookup_cache_ttl(config.cart_food_lookup_cache_ttl_ms.map(Duration::from_millis))
+            .with_analytics_emitter(analytics_emitter),
+        ),
+        recommendation_service: Arc::new(
+            RecommendationService::new(
+                food_repository,
+                config.recommendation_default_sort,
+                config.recommendation_empty_reason_enabled,
+                config.recommendation_cache_ttl_ms.map(Duration::from_millis),
+            )
+            .with_stats_fanout_concurrency(config.recommendation_stats_fanout_concurrency)
+            .with_stats_time_budget(config.recommendation_stats_time_budget_ms.map(Duration::from_millis)),
+        ),
+        config: Arc::new(config.clone()),
+        admin_state: Arc::new(AdminState::new()),
+        readiness_checker,
+        event_emitter,
+    };
+
+    let (table_metrics_shutdown_tx, table_metrics_shutdown_rx) = tokio::sync::oneshot::channel();
+    let table_metrics_handle = tokio::spawn(table_metrics::run_table_item_count_reporter(
+        dynamodb_client,
+        config.foods_table_name.clone(),
+        config.carts_table_name.clone(),
+        Duration::from_secs(config.table_item_count_interval_secs),
+        table_metrics_shutdown_rx,
+    ));
+
+    let app = create_app(state);
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", config.port))
+        .await
+        .expect("failed to bind listener");
+
+    let total_startup_seconds = startup_timings.total().as_secs_f64();
+    metrics::observe_startup_duration(total_startup_seconds);
+    tracing::info!(
+        cold_start = true,
+        total_seconds = total_startup_seconds,
+        config_load_seconds = startup_timings.phase_seconds("config_load").unwrap_or(0.0),
+        aws_client_init_seconds = startup_timings.phase_seconds("aws_client_init").unwrap_or(0.0),
+        table_warmup_seconds = startup_timings.phase_seconds("table_warmup").unwrap_or(0.0),
+        "cold_start"
+    );
+
+    tracing::info!(port = config.port, "petfood service listening");
+    shutdown::serve_with_graceful_shutdown(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        Duration::from_secs(config.shutdown_drain_seconds),
+        shutdown::wait_for_shutdown_signal(),
+    )
+    .await;
+
+    let _ = table_metrics_shutdown_tx.send(());
+    let _ = table_metrics_handle.await;
+}
+
+/// Fires `FoodEvent::service_started` once at startup, for a fleet-wide
+/// config-drift collector to inventory what's deployed where. Best-effort —
+/// `EventEmitter::emit_event` already no-ops when the event bus is disabled,
+/// and a failure here is logged, not propagated, since losing this one
+/// inventory event should never block the service from starting.
+async fn emit_service_started_event(event_emitter: &EventEmitter, config: &ServerConfig) {
+    let event = FoodEvent::service_started(config.service_started_summary());
+    if let Err(err) = event_emitter.emit_event(&event).await {
+        tracing::error!(error = %err, "failed to emit service started event");
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use once_cell::sync::{Lazy, OnceCell};
+use opentelemetry::metrics::{Histogram as OtelHistogram, MeterProvider};
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use prometheus::{Encoder, Gauge, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder};
+use serde::Serialize;
+
+pub static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// `PETFOOD_METRICS_MAX_LABEL_VALUES`: how many distinct values a
+/// [`CardinalityGuard`]-protected label is allowed to accumulate before
+/// further unseen values collapse into `other`. Read by every guard on each
+/// call, so changing it takes effect without restarting — a guard never
+/// caches the limit it was constructed with. Defaults to 200, matching
+/// `ServerConfig::metrics_max_label_values`'s default.
+static METRICS_MAX_LABEL_VALUES: AtomicUsize = AtomicUsize::new(200);
+
+pub fn set_metrics_max_label_values(max: usize) {
+    METRICS_MAX_LABEL_VALUES.store(max, Ordering::Relaxed);
+}
+
+/// Caps a single label's distinct values across a metric's lifetime: the
+/// first `PETFOOD_METRICS_MAX_LABEL_VALUES` values seen pass through
+/// unchanged, and anything after that collapses into `"other"` instead of
+/// opening a new series — a raw, per-entity value (a user ID, a literal
+/// request path) would otherwise grow the metric's cardinality without
+/// bound. Logs a warning the first time it starts collapsing, not on every
+/// subsequent overflow, so a sustained flood of new values doesn't flood
+/// the logs too.
+struct CardinalityGuard {
+    metric_name: &'static str,
+    seen: Mutex<HashSet<String>>,
+    overflowed: std::sync::atomic::AtomicBool,
+}
+
+impl CardinalityGuard {
+    fn new(metric_name: &'static str) -> Self {
+        Self {
+            metric_name,
+            seen: Mutex::new(HashSet::new()),
+            overflowed: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    /// Checks `label` against `max_distinct`, the caller's current read of
+    /// [`METRICS_MAX_LABEL_VALUES`] — taken as a parameter rather than read
+    /// internally so tests can exercise the collapsing logic at a small
+    /// limit without mutating the process-wide setting other tests rely on.
+    fn guard(&self, label: &str, max_distinct: usize) -> String {
+        let mut seen = self.seen.lock().unwrap();
+        if seen.contains(label) {
+            return label.to_string();
+        }
+
+        if seen.len() >= max_distinct {
+            if !self.overflowed.swap(true, Ordering::Relaxed) {
+                tracing::warn!(
+                    metric = self.metric_name,
+                    max_distinct_label_values = max_distinct,
+                    "metric label cardinality limit reached; collapsing further values into \"other\""
+                );
+            }
+            return "other".to_string();
+        }
+
+        seen.insert(label.to_string());
+        label.to_string()
+    }
+}
+
+/// Counts reads that succeeded on a secondary region replica after the
+/// primary region's client errored out.
+pub static DYNAMODB_READ_FAILOVER_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    let counter = IntCounter::with_opts(Opts::new(
+        "petfood_dynamodb_read_failover_total",
+        "Reads that fell through to a secondary region replica after the primary region failed",
+    ))
+    .unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+pub fn observe_dynamodb_read_failover() {
+    DYNAMODB_READ_FAILOVER_TOTAL.inc();
+    DEMO_METRICS.dynamodb_read_failovers.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts `FoodEvent`s dropped because `EventEmitter`'s concurrency limit was
+/// saturated and shedding (rather than queueing) is configured.
+pub static EVENTS_SHED_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    let counter = IntCounter::with_opts(Opts::new(
+        "petfood_events_shed_total",
+        "FoodEvents dropped because the emitter's concurrency limit was saturated",
+    ))
+    .unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+pub fn observe_event_shed() {
+    EVENTS_SHED_TOTAL.inc();
+    DEMO_METRICS.events_shed.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts `EventEmitter` retries of a failed `put_events` call, one
+/// increment per retry attempt (not per event).
+pub static EVENTBRIDGE_RETRIES_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    let counter = IntCounter::with_opts(Opts::new(
+        "eventbridge_retries_total",
+        "put_events calls retried after a retryable EventBridge failure",
+    ))
+    .unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+pub fn observe_eventbridge_retry() {
+    EVENTBRIDGE_RETRIES_TOTAL.inc();
+}
+
+/// Time between a `FoodEvent` being created and the emitter actually handing
+/// it to EventBridge, i.e. how long it sat queued before emission.
+pub static EVENT_EMIT_AGE_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    let histogram = Histogram::with_opts(HistogramOpts::new(
+        "petfood_event_emit_age_seconds",
+        "Seconds between FoodEvent creation and emission to EventBridge",
+    ))
+    .unwrap();
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("metric already registered");
+    histogram
+});
+
+/// Present only when `PETFOOD_OTEL_METRICS_ENABLED` is set, so the default
+/// deployment keeps emitting Prometheus only.
+static OTEL_EVENT_EMIT_AGE_SECONDS: OnceCell<OtelHistogram<f64>> = OnceCell::new();
+
+/// Sets up an OpenTelemetry meter provider alongside the existing Prometheus
+/// registry. Call once at startup; a no-op when `enabled` is false.
+pub fn init_otel_metrics(enabled: bool) {
+    if !enabled {
+        return;
+    }
+
+    let exporter = opentelemetry_stdout::MetricExporter::default();
+    let provider = SdkMeterProvider::builder().with_periodic_exporter(exporter).build();
+    let meter = provider.meter("petfood");
+
+    let histogram = meter
+        .f64_histogram("petfood_event_emit_age_seconds")
+        .with_description("Seconds between FoodEvent creation and emission to EventBridge")
+        .build();
+
+    let _ = OTEL_EVENT_EMIT_AGE_SECONDS.set(histogram);
+}
+
+pub fn observe_event_emit_age_seconds(seconds: f64) {
+    EVENT_EMIT_AGE_SECONDS.observe(seconds);
+    if let Some(histogram) = OTEL_EVENT_EMIT_AGE_SECONDS.get() {
+        histogram.record(seconds, &[]);
+    }
+}
+
+/// Distribution of incoming request body sizes, labeled by route path, so
+/// `PETFOOD_MAX_REQUEST_SIZE`-style body limits can be right-sized from
+/// observed traffic instead of guessed.
+pub static REQUEST_BODY_BYTES: Lazy<HistogramVec> = Lazy::new(|| {
+    let histogram = HistogramVec::new(
+        HistogramOpts::new(
+            "petfood_request_body_bytes",
+            "Size in bytes of incoming request bodies, labeled by route",
+        )
+        .buckets(vec![0.0, 100.0, 500.0, 1_000.0, 5_000.0, 10_000.0, 50_000.0, 100_000.0]),
+        &["route"],
+    )
+    .unwrap();
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("metric already registered");
+    histogram
+});
+
+static REQUEST_BODY_BYTES_CARDINALITY_GUARD: Lazy<CardinalityGuard> =
+    Lazy::new(|| CardinalityGuard::new("petfood_request_body_bytes"));
+
+pub fn observe_request_body_bytes(route: &str, bytes: u64) {
+    let route = REQUEST_BODY_BYTES_CARDINALITY_GUARD.guard(route, METRICS_MAX_LABEL_VALUES.load(Ordering::Relaxed));
+    REQUEST_BODY_BYTES.with_label_values(&[&route]).observe(bytes as f64);
+}
+
+/// Wall-clock duration of every request, labeled by method and route
+/// template (e.g. `/api/foods/:food_id`, not the raw path) so distinct
+/// foods don't each get their own label series.
+pub static HTTP_REQUEST_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    let histogram = HistogramVec::new(
+        HistogramOpts::new("http_request_duration_seconds", "Request duration in seconds, labeled by method and route")
+            .buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]),
+        &["method", "route"],
+    )
+    .unwrap();
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("metric already registered");
+    histogram
+});
+
+static HTTP_REQUEST_DURATION_SECONDS_CARDINALITY_GUARD: Lazy<CardinalityGuard> =
+    Lazy::new(|| CardinalityGuard::new("http_request_duration_seconds"));
+
+pub fn observe_http_request_duration(method: &str, route: &str, seconds: f64) {
+    let route =
+        HTTP_REQUEST_DURATION_SECONDS_CARDINALITY_GUARD.guard(route, METRICS_MAX_LABEL_VALUES.load(Ordering::Relaxed));
+    HTTP_REQUEST_DURATION_SECONDS.with_label_values(&[method, &route]).observe(seconds);
+}
+
+/// Counts every successful checkout, for a basic throughput panel alongside
+/// the value and item-count histograms below.
+pub static CHECKOUTS_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    let counter = IntCounter::with_opts(Opts::new("petfood_checkouts_total", "Successful checkouts")).unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+/// Dollar value of each completed order, for revenue dashboards that want
+/// more than a raw count of checkouts.
+pub static ORDER_VALUE_DOLLARS: Lazy<Histogram> = Lazy::new(|| {
+    let histogram = Histogram::with_opts(
+        HistogramOpts::new("petfood_order_value_dollars", "Dollar value of each completed order")
+            .buckets(vec![5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1_000.0]),
+    )
+    .unwrap();
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("metric already registered");
+    histogram
+});
+
+/// Total item quantity (summed across lines) in each completed order.
+pub static ORDER_ITEMS: Lazy<Histogram> = Lazy::new(|| {
+    let histogram = Histogram::with_opts(
+        HistogramOpts::new("petfood_order_items", "Total item quantity in each completed order")
+            .buckets(vec![1.0, 2.0, 3.0, 5.0, 10.0, 20.0, 50.0]),
+    )
+    .unwrap();
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("metric already registered");
+    histogram
+});
+
+pub fn observe_order_placed(total_cents: i64, item_count: u32) {
+    CHECKOUTS_TOTAL.inc();
+    ORDER_VALUE_DOLLARS.observe(total_cents as f64 / 100.0);
+    ORDER_ITEMS.observe(item_count as f64);
+    DEMO_METRICS.checkouts.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts deletions that dropped the active food count by more than
+/// `PETFOOD_CATALOG_SIZE_ALERT_DROP_PERCENT`, alongside the
+/// `CatalogSizeAlert` event fired for the same condition.
+pub static CATALOG_SIZE_ALERTS_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    let counter = IntCounter::with_opts(Opts::new(
+        "petfood_catalog_size_alerts_total",
+        "Deletions that dropped the active food count by more than the configured alert threshold",
+    ))
+    .unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+pub fn observe_catalog_size_alert() {
+    CATALOG_SIZE_ALERTS_TOTAL.inc();
+    DEMO_METRICS.catalog_size_alerts.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts carts whose total crossed `PETFOOD_HIGH_VALUE_CART_THRESHOLD`,
+/// alongside the `HighValueCart` event fired for the same crossing.
+pub static HIGH_VALUE_CARTS_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    let counter = IntCounter::with_opts(Opts::new(
+        "petfood_high_value_carts_total",
+        "Carts whose total crossed the configured high-value threshold",
+    ))
+    .unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+pub fn observe_high_value_cart() {
+    HIGH_VALUE_CARTS_TOTAL.inc();
+    DEMO_METRICS.high_value_carts.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts `FoodMissingImageViewed` events fired for an image-less food,
+/// throttled to at most one per food per
+/// `PETFOOD_MISSING_IMAGE_EMIT_WINDOW_MS`.
+pub static MISSING_IMAGE_VIEWS_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    let counter = IntCounter::with_opts(Opts::new(
+        "petfood_missing_image_views_total",
+        "Image-less foods read through an endpoint with the missing-image throttle enabled",
+    ))
+    .unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+pub fn observe_missing_image_viewed() {
+    MISSING_IMAGE_VIEWS_TOTAL.inc();
+    DEMO_METRICS.missing_image_views.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Foods returned by a catalog listing, broken down by `pet_type` and
+/// `food_type` — a per-food-type view on top of `petfood_checkouts_total`'s
+/// per-order one, for "what's actually being browsed" dashboards.
+pub static FOODS_LISTED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = IntCounterVec::new(
+        Opts::new("petfood_foods_listed_total", "Foods returned from a catalog listing, labeled by pet_type and food_type"),
+        &["pet_type", "food_type"],
+    )
+    .unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+pub fn observe_food_listed(pet_type: &str, food_type: &str) {
+    FOODS_LISTED_TOTAL.with_label_values(&[pet_type, food_type]).inc();
+}
+
+/// Items added to a cart, broken down by `food_type` — lets a dashboard
+/// compare what's browsed (`petfood_foods_listed_total`) against what's
+/// actually added to a cart.
+pub static CART_ITEMS_ADDED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = IntCounterVec::new(
+        Opts::new("petfood_cart_items_added_total", "Items added to a cart, labeled by food_type"),
+        &["food_type"],
+    )
+    .unwrap();
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric already registered");
+    counter
+});
+
+pub fn observe_cart_item_added(food_type: &str) {
+    CART_ITEMS_ADDED_TOTAL.with_label_values(&[food_type]).inc();
+}
+
+/// Mutable copies of the six business counters above, kept outside the
+/// Prometheus registry so a workshop instructor can reset them between
+/// exercises (`POST /api/admin/metrics/reset`) without resetting the
+/// Prometheus counters themselves, which are meant to stay monotonic for
+/// the lifetime of the process.
+#[derive(Default)]
+pub struct DemoMetrics {
+    checkouts: AtomicU64,
+    catalog_size_alerts: AtomicU64,
+    events_shed: AtomicU64,
+    dynamodb_read_failovers: AtomicU64,
+    high_value_carts: AtomicU64,
+    missing_image_views: AtomicU64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DemoMetricsSnapshot {
+    pub checkouts: u64,
+    pub catalog_size_alerts: u64,
+    pub events_shed: u64,
+    pub dynamodb_read_failovers: u64,
+    pub high_value_carts: u64,
+    pub missing_image_views: u64,
+}
+
+impl DemoMetrics {
+    pub fn snapshot(&self) -> DemoMetricsSnapshot {
+        DemoMetricsSnapshot {
+            checkouts: self.checkouts.load(Ordering::Relaxed),
+            catalog_size_alerts: self.catalog_size_alerts.load(Ordering::Relaxed),
+            events_shed: self.events_shed.load(Ordering::Relaxed),
+            dynamodb_read_failovers: self.dynamodb_read_failovers.load(Ordering::Relaxed),
+            high_value_carts: self.high_value_carts.load(Ordering::Relaxed),
+            missing_image_views: self.missing_image_views.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset(&self) {
+        self.checkouts.store(0, Ordering::Relaxed);
+        self.catalog_size_alerts.store(0, Ordering::Relaxed);
+        self.events_shed.store(0, Ordering::Relaxed);
+        self.dynamodb_read_failovers.store(0, Ordering::Relaxed);
+        self.high_value_carts.store(0, Ordering::Relaxed);
+        self.missing_image_views.store(0, Ordering::Relaxed);
+    }
+}
+
+pub static DEMO_METRICS: Lazy<DemoMetrics> = Lazy::new(DemoMetrics::default);
+
+/// Time from process start to "server listening" (config load, AWS client
+/// init, table warm-up), set once at startup for the cold-start dashboard.
+pub static STARTUP_DURATION_SECONDS: Lazy<Gauge> = Lazy::new(|| {
+    let gauge = Gauge::with_opts(Opts::new(
+        "petfood_startup_duration_seconds",
+        "Time from process start to the server accepting connections",
+    ))
+    .unwrap();
+    REGISTRY.register(Box::new(gauge.clone())).expect("metric already registered");
+    gauge
+});
+
+pub fn observe_startup_duration(seconds: f64) {
+    STARTUP_DURATION_SECONDS.set(seconds);
+}
+
+pub fn render() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = REGISTRY.gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_otel_metrics_registers_the_histogram_when_enabled() {
+        init_otel_metrics(true);
+        assert!(OTEL_EVENT_EMIT_AGE_SECONDS.get().is_some());
+
+        // Should not panic when the otel backend is already initialized.
+        observe_event_emit_age_seconds(1.5);
+    }
+
+    #[test]
+    fn demo_metrics_snapshot_reflects_recorded_counts() {
+        let metrics = DemoMetrics::default();
+        metrics.checkouts.fetch_add(3, Ordering::Relaxed);
+        metrics.catalog_size_alerts.fetch_add(1, Ordering::Relaxed);
+
+        let snapshot = metrics.snapshot();
+
+        assert_eq!(snapshot.checkouts, 3);
+        assert_eq!(snapshot.catalog_size_alerts, 1);
+        assert_eq!(snapshot.events_shed, 0);
+        assert_eq!(snapshot.dynamodb_read_failovers, 0);
+    }
+
+    #[test]
+    fn demo_metrics_reset_zeroes_every_counter() {
+        let metrics = DemoMetrics::default();
+        metrics.checkouts.fetch_add(5, Ordering::Relaxed);
+        metrics.events_shed.fetch_add(2, Ordering::Relaxed);
+        metrics.dynamodb_read_failovers.fetch_add(7, Ordering::Relaxed);
+
+        metrics.reset();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.checkouts, 0);
+        assert_eq!(snapshot.catalog_size_alerts, 0);
+        assert_eq!(snapshot.events_shed, 0);
+        assert_eq!(snapshot.dynamodb_read_failovers, 0);
+    }
+
+    #[test]
+    fn cardinality_guard_passes_through_distinct_values_up_to_the_limit() {
+        let guard = CardinalityGuard::new("test_metric");
+
+        assert_eq!(guard.guard("a", 3), "a");
+        assert_eq!(guard.guard("b", 3), "b");
+        assert_eq!(guard.guard("c", 3), "c");
+        assert_eq!(
+            guard.guard("a", 3),
+            "a",
+            "a value already seen should keep its own label even once the limit is hit"
+        );
+    }
+
+    #[test]
+    fn cardinality_guard_collapses_unseen_values_once_the_limit_is_reached() {
+        let guard = CardinalityGuard::new("test_metric");
+
+        guard.guard("a", 2);
+        guard.guard("b", 2);
+
+        assert_eq!(guard.guard("c", 2), "other");
+        assert_eq!(guard.guard("d", 2), "other", "every subsequent unseen value should also collapse");
+    }
+}
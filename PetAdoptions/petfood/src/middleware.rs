@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use axum::extract::{ConnectInfo, MatchedPath, Request};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use once_cell::sync::Lazy;
+use tracing::Instrument;
+
+use crate::error::ApiError;
+use crate::metrics;
+use crate::trust::TrustedProxyAllowList;
+
+const DEFAULT_SLOW_REQUEST_THRESHOLD_MS: u64 = 1000;
+
+/// Query parameter values are redacted rather than dropped for these keys
+/// (case-insensitive), so the key is still visible on the span without the
+/// credential it carries.
+const SENSITIVE_QUERY_KEYS: &[&str] = &["token", "password", "secret", "api_key", "access_token", "authorization"];
+
+/// Caps the sanitized query string's length on the request span, so a
+/// pathologically long query can't bloat span/log storage.
+const MAX_QUERY_STRING_LEN: usize = 512;
+
+/// Toggled once at startup from `PETFOOD_SLOW_REQUEST_MS`; read by
+/// [`observability_middleware`] on every request.
+static SLOW_REQUEST_THRESHOLD_MS: AtomicU64 = AtomicU64::new(DEFAULT_SLOW_REQUEST_THRESHOLD_MS);
+
+pub fn set_slow_request_threshold_ms(threshold_ms: u64) {
+    SLOW_REQUEST_THRESHOLD_MS.store(threshold_ms, Ordering::Relaxed);
+}
+
+/// Wraps every request in a `http_request` span carrying the method, path,
+/// and a sanitized query string, so a slow `/api/foods` trace shows which
+/// filters were in play. Also logs a per-request summary line, but only for
+/// requests that are slow or return an error status — fast successful
+/// requests still count toward metrics, just without an individual log
+/// line, to keep log volume down on high-traffic routes.
+pub async fn observability_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let route = route_template(&request).unwrap_or_else(|| path.clone());
+    let query = sanitize_query_string(request.uri().query().unwrap_or(""));
+    let span = tracing::info_span!("http_request", method = %method, path = %path, query = %query);
+
+    metrics::observe_request_body_bytes(&path, content_length(&request));
+
+    async move {
+        let started_at = Instant::now();
+
+        let response = next.run(request).await;
+
+        let elapsed = started_at.elapsed();
+        let elapsed_ms = elapsed.as_millis() as u64;
+        let status = response.status();
+
+        metrics::observe_http_request_duration(method.as_str(), &route, elapsed.as_secs_f64());
+
+        if should_log(elapsed_ms, status, SLOW_REQUEST_THRESHOLD_MS.load(Ordering::Relaxed)) {
+            tracing::info!(
+                method = %method,
+                path,
+                status = status.as_u16(),
+                elapsed_ms,
+                "request completed"
+            );
+        }
+
+        response
+    }
+    .instrument(span)
+    .await
+}
+
+/// The route pattern the router matched (e.g. `/api/foods/:food_id`), not
+/// the raw path, so per-entity paths don't each fragment the
+/// `http_request_duration_seconds` label space into their own series.
+/// `None` for requests that didn't match any route (e.g. a 404).
+fn route_template(request: &Request) -> Option<String> {
+    request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched_path| matched_path.as_str().to_string())
+}
+
+/// Reads `Content-Length` rather than buffering the body, so recording this
+/// metric never forces a streaming request into memory. Requests without a
+/// `Content-Length` (e.g. chunked transfer encoding) are recorded as 0.
+fn content_length(request: &Request) -> u64 {
+    request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0)
+}
+
+fn should_log(elapsed_ms: u64, status: StatusCode, threshold_ms: u64) -> bool {
+    elapsed_ms >= threshold_ms || status.is_client_error() || status.is_server_error()
+}
+
+/// Redacts the value of any sensitive key (`token`, `password`, etc.) and
+/// caps the overall length, so the query string can be attached to the
+/// request span without leaking credentials or letting one request blow up
+/// span storage.
+fn sanitize_query_string(query: &str) -> String {
+    let sanitized = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _value)) if is_sensitive_key(key) => format!("{key}=REDACTED"),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    if sanitized.chars().count() > MAX_QUERY_STRING_LEN {
+        let truncated: String = sanitized.chars().take(MAX_QUERY_STRING_LEN).collect();
+        format!("{truncated}...")
+    } else {
+        sanitized
+    }
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    SENSITIVE_QUERY_KEYS
+        .iter()
+        .any(|sensitive| key.eq_ignore_ascii_case(sensitive))
+}
+
+/// Toggled once at startup from `PETFOOD_RATE_LIMIT_RPS`; read by
+/// [`rate_limit_middleware`] on every request. `false` (the default) means
+/// rate limiting is off entirely, regardless of `RATE_LIMIT_RPS_MILLIS`.
+static RATE_LIMIT_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// `rate_limit_rps * 1000`, stored as an integer so it fits an atomic.
+static RATE_LIMIT_RPS_MILLIS: AtomicU64 = AtomicU64::new(0);
+
+/// Token bucket capacity; see `ServerConfig::rate_limit_burst`.
+static RATE_LIMIT_BURST: AtomicU32 = AtomicU32::new(10);
+
+pub fn set_rate_limit(rps: Option<f64>, burst: u32) {
+    RATE_LIMIT_ENABLED.store(rps.is_some(), Ordering::Relaxed);
+    RATE_LIMIT_RPS_MILLIS.store((rps.unwrap_or(0.0) * 1000.0).round() as u64, Ordering::Relaxed);
+    RATE_LIMIT_BURST.store(burst, Ordering::Relaxed);
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// One bucket per client IP, so a single noisy caller can't exhaust the
+/// budget everyone else shares. Never pruned — see the doc comment on
+/// `rate_limit_middleware` for why that's an accepted tradeoff for now.
+static BUCKETS: Lazy<Mutex<HashMap<IpAddr, TokenBucket>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// `PETFOOD_TRUSTED_PROXY_CIDRS` — see `ServerConfig::trusted_proxy_allow_list`.
+/// Consulted by [`client_ip`] before honoring an inbound `X-Forwarded-For`.
+/// Defaults to an empty allow-list (trusts nothing) until `main` calls
+/// [`set_trusted_proxy_allow_list`] with the configured one.
+static TRUSTED_PROXY_ALLOW_LIST: Lazy<Mutex<TrustedProxyAllowList>> =
+    Lazy::new(|| Mutex::new(TrustedProxyAllowList::default()));
+
+pub fn set_trusted_proxy_allow_list(allow_list: TrustedProxyAllowList) {
+    *TRUSTED_PROXY_ALLOW_LIST.lock().unwrap() = allow_list;
+}
+
+/// The caller's IP for rate-limiting purposes: the first hop in
+/// `X-Forwarded-For` (the original client, assuming a single trusted
+/// reverse proxy prepends it), but only when the immediate TCP peer (from
+/// [`ConnectInfo`]) is inside `TRUSTED_PROXY_ALLOW_LIST` — otherwise any
+/// caller could spoof the header to dodge its own rate limit entirely, so
+/// the peer's own address is used instead. Neither is available, an
+/// unspecified address is used, which simply means every such request
+/// shares one bucket.
+fn client_ip(request: &Request) -> IpAddr {
+    let peer_ip = request.extensions().get::<ConnectInfo<SocketAddr>>().map(|info| info.0.ip());
+
+    let forwarded_for_is_trusted = match peer_ip {
+        Some(IpAddr::V4(peer)) => TRUSTED_PROXY_ALLOW_LIST.lock().unwrap().is_trusted(peer),
+        _ => false,
+    };
+
+    if forwarded_for_is_trusted {
+        if let Some(forwarded) = request
+            .headers()
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .and_then(|value| value.trim().parse().ok())
+        {
+            return forwarded;
+        }
+    }
+
+    peer_ip.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+}
+
+/// Refills `ip`'s bucket for the elapsed time since it was last touched (capped
+/// at `burst`), then takes one token if available. Returns whether the
+/// request may proceed.
+fn take_token(ip: IpAddr, rps: f64, burst: f64) -> bool {
+    let mut buckets = BUCKETS.lock().unwrap();
+    let now = Instant::now();
+    let bucket = buckets.entry(ip).or_insert_with(|| TokenBucket { tokens: burst, last_refill: now });
+
+    let elapsed_secs = now.duration_since(bucket.last_refill).as_secs_f64();
+    bucket.tokens = (bucket.tokens + elapsed_secs * rps).min(burst);
+    bucket.last_refill = now;
+
+    if bucket.tokens >= 1.0 {
+        bucket.tokens -= 1.0;
+        true
+    } else {
+        false
+    }
+}
+
+fn too_many_requests_response(rps: f64) -> Response {
+    let mut response = ApiError::RateLimited("rate limit exceeded; slow down".to_string()).into_response();
+    let retry_after_secs = (1.0 / rps).ceil().max(1.0) as u64;
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from_str(&retry_after_secs.to_string()).unwrap());
+    response
+}
+
+/// Token-bucket rate limiting keyed by client IP (see [`client_ip`]), guarding
+/// every route (most importantly the unauthenticated admin seed/cleanup
+/// endpoints) against a single caller hammering the service. Disabled by
+/// default; set `PETFOOD_RATE_LIMIT_RPS` to enable it, with
+/// `PETFOOD_RATE_LIMIT_BURST` controlling how large a burst above the
+/// steady rate is tolerated before throttling kicks in. Buckets accumulate
+/// in memory for the life of the process rather than being pruned for
+/// inactivity — acceptable for a demo-scale service, but a real deployment
+/// fronted by many distinct client IPs would want an eviction policy.
+pub async fn rate_limit_middleware(request: Request, next: Next) -> Response {
+    if !RATE_LIMIT_ENABLED.load(Ordering::Relaxed) {
+        return next.run(request).await;
+    }
+
+    let rps = RATE_LIMIT_RPS_MILLIS.load(Ordering::Relaxed) as f64 / 1000.0;
+    let burst = RATE_LIMIT_BURST.load(Ordering::Relaxed) as f64;
+    let ip = client_ip(&request);
+
+    if !take_token(ip, rps, burst) {
+        return too_many_requests_response(rps);
+    }
+
+    next.run(request).await
+}
+
+/// How many requests are currently being handled, incremented in
+/// [`track_in_flight_requests`] before the rest of the stack runs and
+/// decremented once it returns. Read by `shutdown::serve_with_graceful_shutdown`
+/// to report how many requests were still active when its drain timeout
+/// elapsed.
+static IN_FLIGHT_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn in_flight_request_count() -> usize {
+    IN_FLIGHT_REQUESTS.load(Ordering::Relaxed)
+}
+
+/// Counts requests currently in flight so a graceful shutdown can tell
+/// whether it drained cleanly or gave up with work still running.
+pub async fn track_in_flight_requests(request: Request, next: Next) -> Response {
+    IN_FLIGHT_REQUESTS.fetch_add(1, Ordering::Relaxed);
+    let response = next.run(request).await;
+    IN_FLIGHT_REQUESTS.fetch_sub(1, Ordering::Relaxed);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_successful_requests_are_not_logged() {
+        assert!(!should_log(5, StatusCode::OK, 1000));
+    }
+
+    #[test]
+    fn requests_at_or_over_the_threshold_are_logged() {
+        assert!(should_log(1000, StatusCode::OK, 1000));
+        assert!(should_log(5000, StatusCode::OK, 1000));
+    }
+
+    #[test]
+    fn error_responses_are_logged_even_when_fast() {
+        assert!(should_log(5, StatusCode::NOT_FOUND, 1000));
+        assert!(should_log(5, StatusCode::INTERNAL_SERVER_ERROR, 1000));
+    }
+
+    #[test]
+    fn sanitize_query_string_leaves_ordinary_filters_visible() {
+        assert_eq!(sanitize_query_string("pet_type=dog&sort=price_asc"), "pet_type=dog&sort=price_asc");
+    }
+
+    #[test]
+    fn sanitize_query_string_redacts_sensitive_keys_case_insensitively() {
+        assert_eq!(
+            sanitize_query_string("user_id=user-1&API_KEY=super-secret"),
+            "user_id=user-1&API_KEY=REDACTED"
+        );
+    }
+
+    #[test]
+    fn sanitize_query_string_caps_overly_long_queries() {
+        let long_value = "a".repeat(1000);
+        let query = format!("filter={long_value}");
+
+        let sanitized = sanitize_query_string(&query);
+
+        assert!(sanitized.ends_with("..."));
+        assert_eq!(sanitized.chars().count(), MAX_QUERY_STRING_LEN + "...".chars().count());
+    }
+
+    #[test]
+    fn sanitize_query_string_handles_an_empty_query() {
+        assert_eq!(sanitize_query_string(""), "");
+    }
+
+    #[test]
+    fn content_length_reads_the_header_when_present() {
+        let request = Request::builder()
+            .header(header::CONTENT_LENGTH, "42")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        assert_eq!(content_length(&request), 42);
+    }
+
+    #[test]
+    fn content_length_defaults_to_zero_when_the_header_is_missing() {
+        let request = Request::builder().body(axum::body::Body::empty()).unwrap();
+
+        assert_eq!(content_length(&request), 0);
+    }
+
+    #[test]
+    fn client_ip_ignores_x_forwarded_for_from_an_untrusted_peer() {
+        set_trusted_proxy_allow_list(TrustedProxyAllowList::parse(&["10.0.0.0/8".to_string()]).unwrap());
+
+        let mut request = Request::builder()
+            .header("x-forwarded-for", "203.0.113.9")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let untrusted_peer = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1));
+        request.extensions_mut().insert(ConnectInfo(SocketAddr::new(untrusted_peer, 0)));
+
+        assert_eq!(client_ip(&request), untrusted_peer, "an untrusted peer's forwarded header must be ignored");
+    }
+
+    #[test]
+    fn client_ip_honors_x_forwarded_for_from_a_trusted_peer() {
+        set_trusted_proxy_allow_list(TrustedProxyAllowList::parse(&["10.0.0.0/8".to_string()]).unwrap());
+
+        let mut request = Request::builder()
+            .header("x-forwarded-for", "203.0.113.9")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let trusted_peer = IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3));
+        request.extensions_mut().insert(ConnectInfo(SocketAddr::new(trusted_peer, 0)));
+
+        assert_eq!(
+            client_ip(&request),
+            IpAddr::V4(Ipv4Addr::new(203, 0, 113, 9)),
+            "a trusted proxy's forwarded header should be honored"
+        );
+    }
+
+    async fn rate_limited_handler() -> &'static str {
+        "ok"
+    }
+
+    fn rate_limit_request() -> Request {
+        Request::builder()
+            .method("POST")
+            .uri("/api/admin/seed")
+            .header("x-forwarded-for", "203.0.113.9")
+            .body(axum::body::Body::empty())
+            .unwrap()
+    }
+
+    fn rate_limited_router() -> axum::Router {
+        axum::Router::new()
+            .route("/api/admin/seed", axum::routing::post(rate_limited_handler))
+            .layer(axum::middleware::from_fn(rate_limit_middleware))
+    }
+
+    /// Fires burst + 1 requests from the same client IP in one test, rather
+    /// than several tests that independently flip the shared rate-limit
+    /// statics, since tests run concurrently and could otherwise race on
+    /// them.
+    #[tokio::test]
+    async fn the_burst_plus_first_request_is_rejected_with_retry_after() {
+        use tower::ServiceExt;
+
+        set_rate_limit(Some(5.0), 3);
+
+        for _ in 0..3 {
+            let response = rate_limited_router().oneshot(rate_limit_request()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let rejected = rate_limited_router().oneshot(rate_limit_request()).await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(rejected.headers().get(header::RETRY_AFTER).is_some());
+        let body = axum::body::to_bytes(rejected.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "RATE_LIMITED", "must be distinguishable from the DynamoDB capacity budget error");
+
+        set_rate_limit(None, 10);
+
+        let disabled = rate_limited_router().oneshot(rate_limit_request()).await.unwrap();
+        assert_eq!(disabled.status(), StatusCode::OK);
+    }
+}
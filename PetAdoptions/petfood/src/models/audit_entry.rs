@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// A single recorded change to a food, e.g. a price update. Stored in the
+/// audit table (queryable by `food_id` via a GSI) so `GET
+/// /api/admin/foods/:food_id/history` can answer "what changed and when"
+/// without replaying `FoodEvent`s from EventBridge.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditEntry {
+    pub food_id: String,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub changed_at: DateTime<Utc>,
+    pub correlation_id: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn price_change(
+        food_id: String,
+        old_price_cents: i64,
+        new_price_cents: i64,
+        correlation_id: Option<String>,
+    ) -> Self {
+        Self {
+            food_id,
+            field: "price_cents".to_string(),
+            old_value: old_price_cents.to_string(),
+            new_value: new_price_cents.to_string(),
+            changed_at: Utc::now(),
+            correlation_id,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::Quantity;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CartItem {
+    pub food_id: String,
+    pub quantity: Quantity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cart {
+    pub user_id: String,
+    pub items: Vec<CartItem>,
+    /// Bumped on every `put_cart`, so `POST /api/admin/carts/cleanup` can
+    /// find carts that have sat untouched for longer than its threshold.
+    pub updated_at: DateTime<Utc>,
+    /// The version this in-memory copy was read at. `put_cart` implementations
+    /// condition the write on the stored version still matching this value
+    /// (bumping it by one on success) and fail with `ApiError::Conflict`
+    /// otherwise, so a caller that read a stale cart can't silently clobber
+    /// a write that happened in between. Defaults to 0 for a brand-new cart.
+    #[serde(default)]
+    pub version: u64,
+    /// The coupon code `CartService::apply_coupon` most recently validated
+    /// and stored on this cart, if any. Re-validated (expiry, minimum cart
+    /// total) at checkout rather than trusted as still usable.
+    #[serde(default)]
+    pub applied_coupon: Option<String>,
+}
+
+/// Cart shape returned to callers, with `total_price` rounded to 2 decimal
+/// places so the API never surfaces cents-division decimal noise.
+#[derive(Debug, Clone, Serialize)]
+pub struct CartResponse {
+    pub user_id: String,
+    pub items: Vec<CartItem>,
+    #[serde(serialize_with = "crate::money::serialize_price")]
+    pub total_price: f64,
+}
+
+/// One item's outcome from `CartService::bulk_add_items` — `cart` is the
+/// resulting cart snapshot on success, `error` is the failure's message on
+/// failure, and exactly one of the two is present.
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkAddResult {
+    pub food_id: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cart: Option<CartResponse>,
+}
+
+impl CartResponse {
+    pub fn new(cart: Cart, total_price_cents: i64) -> Self {
+        Self {
+            user_id: cart.user_id,
+            items: cart.items,
+            total_price: crate::money::round2(total_price_cents as f64 / 100.0),
+        }
+    }
+}
+
+/// Why a cart item failed `CartService::validate_cart`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CartIssueKind {
+    /// The food no longer exists in the catalog.
+    NotFound,
+    /// The food exists but isn't currently available at all.
+    OutOfStock,
+    /// The food is available, but not in the quantity the cart wants.
+    InsufficientStock,
+}
+
+/// One problem found with a cart item by `CartService::validate_cart`, so
+/// the frontend can warn about it per-item before the user reaches checkout.
+#[derive(Debug, Clone, Serialize)]
+pub struct CartValidationIssue {
+    pub food_id: String,
+    pub kind: CartIssueKind,
+    pub message: String,
+}
+
+/// Response for `GET /api/cart/:user_id/validate`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CartValidationResponse {
+    pub valid: bool,
+    pub issues: Vec<CartValidationIssue>,
+}
+
+impl CartValidationResponse {
+    pub fn new(issues: Vec<CartValidationIssue>) -> Self {
+        Self { valid: issues.is_empty(), issues }
+    }
+}
+
+impl Cart {
+    pub fn new(user_id: String) -> Self {
+        Self {
+            user_id,
+            items: Vec::new(),
+            updated_at: Utc::now(),
+            version: 0,
+            applied_coupon: None,
+        }
+    }
+
+    /// Merges into an existing line for `food_id` rather than pushing a
+    /// second entry, so adding the same food twice accumulates quantity.
+    /// The merged total is clamped to `Quantity`'s upper bound rather than
+    /// rejected, since a line that's already full just can't hold more.
+    pub fn add_item(&mut self, food_id: String, quantity: Quantity) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.food_id == food_id) {
+            let merged = item.quantity.get().saturating_add(quantity.get()).min(Quantity::MAX);
+            item.quantity = Quantity::try_from(merged).expect("clamped within Quantity's valid range");
+        } else {
+            self.items.push(CartItem { food_id, quantity });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_item_merges_duplicate_food_ids() {
+        let mut cart = Cart::new("user-1".to_string());
+        cart.add_item("food-1".to_string(), Quantity::try_from(2).unwrap());
+        cart.add_item("food-1".to_string(), Quantity::try_from(3).unwrap());
+
+        assert_eq!(cart.items.len(), 1);
+        assert_eq!(cart.items[0].quantity.get(), 5);
+    }
+
+    #[test]
+    fn add_item_clamps_a_merge_that_would_exceed_the_maximum() {
+        let mut cart = Cart::new("user-1".to_string());
+        cart.add_item("food-1".to_string(), Quantity::try_from(Quantity::MAX).unwrap());
+        cart.add_item("food-1".to_string(), Quantity::try_from(1).unwrap());
+
+        assert_eq!(cart.items[0].quantity.get(), Quantity::MAX);
+    }
+
+    #[test]
+    fn add_item_keeps_distinct_foods_separate() {
+        let mut cart = Cart::new("user-1".to_string());
+        cart.add_item("food-1".to_string(), Quantity::try_from(2).unwrap());
+        cart.add_item("food-2".to_string(), Quantity::try_from(1).unwrap());
+
+        assert_eq!(cart.items.len(), 2);
+    }
+}
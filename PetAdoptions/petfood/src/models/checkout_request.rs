@@ -0,0 +1,33 @@
+use serde::Deserialize;
+
+use crate::error::{ApiError, ApiResult};
+
+/// Optional shipping/payment metadata attached to a checkout. All fields are
+/// optional so an empty request body preserves the pre-existing
+/// no-metadata checkout behavior.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CheckoutRequest {
+    pub shipping_address: Option<String>,
+    pub payment_method_token: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl CheckoutRequest {
+    pub fn validate(&self) -> ApiResult<()> {
+        if let Some(address) = &self.shipping_address {
+            if address.trim().is_empty() {
+                return Err(ApiError::Validation(
+                    "shipping_address cannot be blank".to_string(),
+                ));
+            }
+        }
+        if let Some(token) = &self.payment_method_token {
+            if token.trim().is_empty() {
+                return Err(ApiError::Validation(
+                    "payment_method_token cannot be blank".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ApiError, ApiResult};
+
+/// How a [`Discount`] reduces a cart's subtotal.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscountKind {
+    /// Whole percentage points off the subtotal, e.g. `10` for 10% off.
+    Percentage(u32),
+    /// A flat amount off the subtotal, in cents.
+    FixedCents(i64),
+}
+
+/// A coupon code's rules, as loaded from the `discounts` table by
+/// `DiscountRepository::get_discount`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Discount {
+    pub code: String,
+    pub kind: DiscountKind,
+    /// The cart's subtotal must be at least this many cents for the
+    /// discount to apply. `None` means no minimum.
+    pub min_cart_total_cents: Option<i64>,
+    /// Once past this time, the code is treated the same as one that was
+    /// never created. `None` means the code never expires.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Discount {
+    /// Checks `subtotal_cents` against `expires_at`/`min_cart_total_cents`,
+    /// returning `ApiError::InvalidCoupon` with a reason a caller can show
+    /// the user rather than silently applying nothing.
+    pub fn validate_usable(&self, subtotal_cents: i64, now: DateTime<Utc>) -> ApiResult<()> {
+        if self.expires_at.is_some_and(|expires_at| now >= expires_at) {
+            return Err(ApiError::InvalidCoupon(format!("coupon {} has expired", self.code)));
+        }
+        if let Some(min) = self.min_cart_total_cents {
+            if subtotal_cents < min {
+                return Err(ApiError::InvalidCoupon(format!(
+                    "coupon {} requires a cart total of at least {min} cents",
+                    self.code
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The amount `subtotal_cents` is reduced by, clamped so a discount can
+    /// never take a total below zero.
+    pub fn discount_amount_cents(&self, subtotal_cents: i64) -> i64 {
+        let raw = match self.kind {
+            DiscountKind::Percentage(percent) => subtotal_cents * percent as i64 / 100,
+            DiscountKind::FixedCents(cents) => cents,
+        };
+        raw.clamp(0, subtotal_cents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discount(kind: DiscountKind) -> Discount {
+        Discount {
+            code: "SAVE".to_string(),
+            kind,
+            min_cart_total_cents: None,
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn percentage_discount_rounds_down_to_the_nearest_cent() {
+        let discount = discount(DiscountKind::Percentage(10));
+
+        assert_eq!(discount.discount_amount_cents(999), 99);
+    }
+
+    #[test]
+    fn fixed_discount_is_clamped_so_the_total_never_goes_negative() {
+        let discount = discount(DiscountKind::FixedCents(5000));
+
+        assert_eq!(discount.discount_amount_cents(1000), 1000);
+    }
+
+    #[test]
+    fn validate_usable_rejects_an_expired_code() {
+        let mut discount = discount(DiscountKind::FixedCents(100));
+        discount.expires_at = Some(Utc::now() - chrono::Duration::days(1));
+
+        let result = discount.validate_usable(1000, Utc::now());
+
+        assert!(matches!(result, Err(ApiError::InvalidCoupon(_))));
+    }
+
+    #[test]
+    fn validate_usable_rejects_a_cart_below_the_minimum() {
+        let mut discount = discount(DiscountKind::FixedCents(100));
+        discount.min_cart_total_cents = Some(2000);
+
+        let result = discount.validate_usable(1000, Utc::now());
+
+        assert!(matches!(result, Err(ApiError::InvalidCoupon(_))));
+    }
+
+    #[test]
+    fn validate_usable_accepts_a_cart_meeting_the_minimum_before_expiry() {
+        let mut discount = discount(DiscountKind::Percentage(10));
+        discount.min_cart_total_cents = Some(500);
+        discount.expires_at = Some(Utc::now() + chrono::Duration::days(1));
+
+        assert!(discount.validate_usable(500, Utc::now()).is_ok());
+    }
+}
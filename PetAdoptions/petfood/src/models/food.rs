@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PetType {
+    Dog,
+    Cat,
+    Bird,
+    Fish,
+    Other,
+}
+
+impl PetType {
+    /// Every variant, for call sites that need to fan out over the full
+    /// set (e.g. the aggregate `GET /api/recommendations/stats` endpoint).
+    pub const ALL: [PetType; 5] = [PetType::Dog, PetType::Cat, PetType::Bird, PetType::Fish, PetType::Other];
+}
+
+/// Matches the `#[serde(rename_all = "snake_case")]` spelling, so this is
+/// safe to use directly as a metric label value.
+impl std::fmt::Display for PetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            PetType::Dog => "dog",
+            PetType::Cat => "cat",
+            PetType::Bird => "bird",
+            PetType::Fish => "fish",
+            PetType::Other => "other",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodType {
+    Dry,
+    Wet,
+    Treat,
+    Supplement,
+}
+
+/// Matches the `#[serde(rename_all = "snake_case")]` spelling, so this is
+/// safe to use directly as a metric label value.
+impl std::fmt::Display for FoodType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            FoodType::Dry => "dry",
+            FoodType::Wet => "wet",
+            FoodType::Treat => "treat",
+            FoodType::Supplement => "supplement",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AvailabilityStatus {
+    InStock,
+    OutOfStock,
+}
+
+/// Where a `CreateFoodRequest` originated, so `FoodService::create_food` can
+/// tell a caller-supplied record (which always gets full field validation)
+/// apart from a built-in seed record (which may skip it under
+/// `PETFOOD_TRUST_SEED` — see `FoodService::create_food`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreationSource {
+    Api,
+    Seeding,
+}
+
+/// A catalog item. `price_cents` avoids floating point error in totals;
+/// API responses carry it as integer cents as well.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Food {
+    pub food_id: String,
+    pub name: String,
+    pub description: String,
+    pub ingredients: Vec<String>,
+    pub price_cents: i64,
+    pub stock_quantity: u32,
+    pub availability_status: AvailabilityStatus,
+    pub pet_type: PetType,
+    pub food_type: FoodType,
+    pub image_path: String,
+    /// Merchandising tags orthogonal to `pet_type`/`food_type` (e.g.
+    /// "grain-free", "senior"), filterable via `?category=` on `GET
+    /// /api/foods`. `#[serde(default)]` so a food persisted before this
+    /// field existed still deserializes.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Prices in currencies other than `ServerConfig::default_currency`
+    /// (`price_cents` is always denominated in the default currency), keyed
+    /// by uppercase ISO 4217 code (e.g. "EUR"). `Food::price_for_currency`
+    /// reads this when a caller requests a currency other than the default;
+    /// a currency with no entry here falls back to `price_cents`.
+    /// `#[serde(default)]` so a food persisted before this field existed
+    /// still deserializes.
+    #[serde(default)]
+    pub prices: HashMap<String, i64>,
+    /// When this catalog entry was last created or modified, so callers can
+    /// page through `GET /api/admin/foods/changes?since=...` incrementally.
+    pub updated_at: DateTime<Utc>,
+    /// The version this in-memory copy was read at. `FoodRepository::put_food`
+    /// implementations condition the write on the stored version still
+    /// matching this value (bumping it by one on success) and fail with
+    /// `ApiError::Conflict` otherwise, so a caller that read a stale food
+    /// (e.g. `FoodService::update_price`) can't silently clobber a write
+    /// that happened in between. Defaults to 0 for a brand-new food.
+    /// `#[serde(default)]` so a food persisted before this field existed
+    /// still deserializes.
+    #[serde(default)]
+    pub version: u64,
+}
+
+/// Caps imposed on `CreateFoodRequest::categories` by `validate`, so a
+/// merchandiser fat-fingering a category list can't blow up storage or the
+/// `?category=` filter UI with an unbounded or enormous tag.
+const MAX_CATEGORY_TAGS: usize = 10;
+const MAX_CATEGORY_TAG_LENGTH: usize = 40;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateFoodRequest {
+    pub name: String,
+    pub description: String,
+    pub ingredients: Vec<String>,
+    pub price_cents: i64,
+    pub stock_quantity: u32,
+    pub pet_type: PetType,
+    pub food_type: FoodType,
+    pub image_path: String,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub prices: HashMap<String, i64>,
+}
+
+impl CreateFoodRequest {
+    /// Collects every field-level problem instead of stopping at the first,
+    /// so a caller (or `POST /api/admin/foods/validate`) can show all of
+    /// them at once instead of a fix-one-resubmit-see-the-next loop.
+    /// `allowed_image_domains` backs `PETFOOD_ALLOWED_IMAGE_DOMAINS`: an
+    /// absolute `image_path` URL must point at one of these hosts (a mild
+    /// SSRF/brand-safety guard against admins pointing images at arbitrary
+    /// external hosts); a relative path is always allowed.
+    pub fn validate(&self, allowed_image_domains: &[String]) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if self.name.trim().is_empty() {
+            errors.push("name cannot be blank".to_string());
+        }
+        if self.description.trim().is_empty() {
+            errors.push("description cannot be blank".to_string());
+        }
+        if self.price_cents < 0 {
+            errors.push("price_cents cannot be negative".to_string());
+        }
+        if let Some(currency) = self.prices.iter().find(|(_, &cents)| cents < 0).map(|(currency, _)| currency) {
+            errors.push(format!("prices.{currency} cannot be negative"));
+        }
+        if self.ingredients.iter().any(|ingredient| ingredient.trim().is_empty()) {
+            errors.push("ingredients cannot contain a blank entry".to_string());
+        }
+        if let Some(host) = image_url_host(&self.image_path) {
+            if !allowed_image_domains.iter().any(|domain| domain.eq_ignore_ascii_case(host)) {
+                errors.push(format!("image_path domain '{host}' is not in the allowed list"));
+            }
+        }
+        if self.categories.len() > MAX_CATEGORY_TAGS {
+            errors.push(format!(
+                "too many categories: {} (max {MAX_CATEGORY_TAGS})",
+                self.categories.len()
+            ));
+        }
+        if self.categories.iter().any(|category| category.trim().is_empty()) {
+            errors.push("categories cannot contain a blank entry".to_string());
+        }
+        if let Some(category) = self.categories.iter().find(|category| category.len() > MAX_CATEGORY_TAG_LENGTH) {
+            errors.push(format!(
+                "category '{category}' exceeds the maximum length of {MAX_CATEGORY_TAG_LENGTH}"
+            ));
+        }
+
+        errors
+    }
+}
+
+/// Returns the host of `raw` if it's an absolute `http(s)://` URL, or
+/// `None` if it's a relative path (which `validate` always allows).
+fn image_url_host(raw: &str) -> Option<&str> {
+    let rest = raw.strip_prefix("http://").or_else(|| raw.strip_prefix("https://"))?;
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    Some(host.split(':').next().unwrap_or(host))
+}
+
+/// The stored form every `image_path` is normalized to: a root-relative
+/// path under `/images/`.
+const CANONICAL_IMAGE_PREFIX: &str = "/images/";
+
+/// CDN hosts admins sometimes paste full URLs from, instead of the bare
+/// filename or repo-relative path the catalog actually stores.
+const KNOWN_CDN_PREFIXES: &[&str] = &[
+    "https://cdn.petfood.example.com/",
+    "https://petfood-assets.s3.amazonaws.com/",
+];
+
+/// Normalizes an admin-supplied image reference to the canonical stored
+/// form (`/images/<name>`) regardless of which of the three shapes it
+/// arrives in: a full CDN URL (the known host prefix is stripped), a bare
+/// filename (the canonical prefix is added), or a `petfood/...`-relative
+/// path (the `petfood/` segment is stripped before the canonical prefix is
+/// added). An already-canonical path round-trips unchanged.
+pub fn normalize_image_path(raw: &str) -> String {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return String::new();
+    }
+
+    let mut path = trimmed;
+    for prefix in KNOWN_CDN_PREFIXES {
+        if let Some(stripped) = path.strip_prefix(prefix) {
+            path = stripped;
+            break;
+        }
+    }
+    let path = path
+        .strip_prefix(CANONICAL_IMAGE_PREFIX)
+        .or_else(|| path.strip_prefix("images/"))
+        .or_else(|| path.strip_prefix("petfood/"))
+        .unwrap_or(path);
+
+    format!("{CANONICAL_IMAGE_PREFIX}{path}")
+}
+
+impl Food {
+    pub fn from_create_request(food_id: String, updated_at: DateTime<Utc>, req: CreateFoodRequest) -> Self {
+        let availability_status = if req.stock_quantity > 0 {
+            AvailabilityStatus::InStock
+        } else {
+            AvailabilityStatus::OutOfStock
+        };
+        Self {
+            food_id,
+            name: req.name,
+            description: req.description,
+            ingredients: req.ingredients,
+            price_cents: req.price_cents,
+            stock_quantity: req.stock_quantity,
+            availability_status,
+            pet_type: req.pet_type,
+            food_type: req.food_type,
+            image_path: normalize_image_path(&req.image_path),
+            categories: req.categories,
+            prices: req.prices,
+            updated_at,
+            version: 0,
+        }
+    }
+
+    /// The price and currency code to render for a caller who requested
+    /// `currency`: an exact (case-insensitive) match against `default_currency`
+    /// always returns `price_cents`; otherwise `prices` is checked for an
+    /// entry, falling back to `price_cents`/`default_currency` when absent.
+    pub fn price_for_currency(&self, currency: &str, default_currency: &str) -> (i64, String) {
+        if currency.eq_ignore_ascii_case(default_currency) {
+            return (self.price_cents, default_currency.to_ascii_uppercase());
+        }
+
+        let currency = currency.to_ascii_uppercase();
+        match self.prices.get(&currency) {
+            Some(&cents) => (cents, currency),
+            None => (self.price_cents, default_currency.to_ascii_uppercase()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_request() -> CreateFoodRequest {
+        CreateFoodRequest {
+            name: "Chicken Kibble".to_string(),
+            description: "Crunchy chicken-based kibble".to_string(),
+            ingredients: vec!["chicken".to_string(), "rice".to_string()],
+            price_cents: 1299,
+            stock_quantity: 10,
+            pet_type: PetType::Dog,
+            food_type: FoodType::Dry,
+            image_path: String::new(),
+            categories: Vec::new(),
+            prices: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_a_well_formed_request() {
+        assert!(valid_request().validate(&[]).is_empty());
+    }
+
+    #[test]
+    fn validate_accepts_an_absolute_image_url_on_an_allow_listed_domain() {
+        let req = CreateFoodRequest {
+            image_path: "https://cdn.petfood.example.com/kibble.png".to_string(),
+            ..valid_request()
+        };
+
+        assert!(req.validate(&["cdn.petfood.example.com".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_an_absolute_image_url_on_a_disallowed_domain() {
+        let req = CreateFoodRequest {
+            image_path: "https://evil.example.com/kibble.png".to_string(),
+            ..valid_request()
+        };
+
+        let errors = req.validate(&["cdn.petfood.example.com".to_string()]);
+
+        assert_eq!(errors, vec!["image_path domain 'evil.example.com' is not in the allowed list"]);
+    }
+
+    #[test]
+    fn validate_always_allows_a_relative_image_path() {
+        let req = CreateFoodRequest {
+            image_path: "/images/kibble.png".to_string(),
+            ..valid_request()
+        };
+
+        assert!(req.validate(&[]).is_empty());
+    }
+
+    #[test]
+    fn normalize_image_path_strips_a_known_cdn_prefix() {
+        assert_eq!(
+            normalize_image_path("https://cdn.petfood.example.com/kibble.png"),
+            "/images/kibble.png"
+        );
+    }
+
+    #[test]
+    fn normalize_image_path_prefixes_a_bare_filename() {
+        assert_eq!(normalize_image_path("kibble.png"), "/images/kibble.png");
+    }
+
+    #[test]
+    fn normalize_image_path_strips_a_petfood_relative_path() {
+        assert_eq!(normalize_image_path("petfood/kibble.png"), "/images/kibble.png");
+    }
+
+    #[test]
+    fn normalize_image_path_is_idempotent_on_an_already_canonical_path() {
+        assert_eq!(normalize_image_path("/images/kibble.png"), "/images/kibble.png");
+    }
+
+    #[test]
+    fn normalize_image_path_leaves_an_empty_value_empty() {
+        assert_eq!(normalize_image_path(""), "");
+    }
+
+    #[test]
+    fn validate_aggregates_every_field_level_error() {
+        let req = CreateFoodRequest {
+            name: "  ".to_string(),
+            description: String::new(),
+            ingredients: vec!["chicken".to_string(), " ".to_string()],
+            price_cents: -1,
+            ..valid_request()
+        };
+
+        let errors = req.validate(&[]);
+
+        assert_eq!(errors.len(), 4);
+    }
+
+    #[test]
+    fn validate_rejects_a_negative_price_in_the_prices_map() {
+        let req = CreateFoodRequest { prices: HashMap::from([("EUR".to_string(), -1)]), ..valid_request() };
+
+        let errors = req.validate(&[]);
+
+        assert_eq!(errors, vec!["prices.EUR cannot be negative"]);
+    }
+
+    fn food_with_prices(prices: HashMap<String, i64>) -> Food {
+        Food::from_create_request(
+            "food-1".to_string(),
+            Utc::now(),
+            CreateFoodRequest { price_cents: 1299, prices, ..valid_request() },
+        )
+    }
+
+    #[test]
+    fn price_for_currency_returns_the_base_price_for_the_default_currency() {
+        let food = food_with_prices(HashMap::new());
+
+        assert_eq!(food.price_for_currency("USD", "USD"), (1299, "USD".to_string()));
+    }
+
+    #[test]
+    fn price_for_currency_returns_the_matching_entry_when_present() {
+        let food = food_with_prices(HashMap::from([("EUR".to_string(), 1199)]));
+
+        assert_eq!(food.price_for_currency("eur", "USD"), (1199, "EUR".to_string()));
+    }
+
+    #[test]
+    fn price_for_currency_falls_back_to_the_default_currency_when_absent() {
+        let food = food_with_prices(HashMap::from([("EUR".to_string(), 1199)]));
+
+        assert_eq!(food.price_for_currency("GBP", "USD"), (1299, "USD".to_string()));
+    }
+}
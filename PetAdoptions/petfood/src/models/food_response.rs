@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{AvailabilityStatus, Food, FoodType, PetType};
+
+/// Controls how `Food::to_response` renders `stock_quantity` for public
+/// (non-admin) reads. Backed by `PETFOOD_STOCK_VISIBILITY`; admin endpoints
+/// keep returning `Food` directly, so admins always see the exact count
+/// regardless of this setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StockVisibility {
+    /// Passes `stock_quantity` through unchanged — today's behavior.
+    Exact,
+    /// Replaces `stock_quantity` with a coarse `stock_level` bucket.
+    Coarse,
+    /// Omits stock information entirely.
+    Hidden,
+}
+
+impl StockVisibility {
+    /// Unrecognized values fall back to `Exact`, preserving today's
+    /// behavior for anyone who hasn't set `PETFOOD_STOCK_VISIBILITY`.
+    pub fn parse(value: &str) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "coarse" => Self::Coarse,
+            "hidden" => Self::Hidden,
+            _ => Self::Exact,
+        }
+    }
+}
+
+/// The coarse bucket `StockVisibility::Coarse` reports instead of an exact
+/// count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StockLevel {
+    InStock,
+    LowStock,
+    Out,
+}
+
+impl StockLevel {
+    fn bucket(stock_quantity: u32, availability_status: AvailabilityStatus, low_stock_threshold: u32) -> Self {
+        if availability_status == AvailabilityStatus::OutOfStock || stock_quantity == 0 {
+            Self::Out
+        } else if stock_quantity <= low_stock_threshold {
+            Self::LowStock
+        } else {
+            Self::InStock
+        }
+    }
+}
+
+/// The public shape of a catalog item, produced by `Food::to_response`.
+/// Identical to `Food` except for how `stock_quantity` is (or isn't)
+/// exposed, per `StockVisibility`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FoodResponse {
+    pub food_id: String,
+    pub name: String,
+    pub description: String,
+    pub ingredients: Vec<String>,
+    pub price_cents: i64,
+    /// The ISO 4217 code `price_cents` above is denominated in — the
+    /// caller's requested currency if `Food::prices` had an entry for it,
+    /// otherwise `ServerConfig::default_currency`. See
+    /// `Food::price_for_currency`.
+    pub currency: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_quantity: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_level: Option<StockLevel>,
+    pub availability_status: AvailabilityStatus,
+    pub pet_type: PetType,
+    pub food_type: FoodType,
+    pub image_path: String,
+    pub categories: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Food {
+    /// Renders this food for a public (non-admin) response. `Exact` passes
+    /// `stock_quantity` through unchanged; `Coarse` replaces it with a
+    /// `stock_level` bucket relative to `low_stock_threshold`; `Hidden`
+    /// omits stock information entirely. `requested_currency` selects which
+    /// of `prices` (falling back to `price_cents`/`default_currency`) is
+    /// rendered — see `Food::price_for_currency`.
+    pub fn to_response(
+        &self,
+        visibility: StockVisibility,
+        low_stock_threshold: u32,
+        requested_currency: &str,
+        default_currency: &str,
+    ) -> FoodResponse {
+        let (stock_quantity, stock_level) = match visibility {
+            StockVisibility::Exact => (Some(self.stock_quantity), None),
+            StockVisibility::Coarse => (
+                None,
+                Some(StockLevel::bucket(self.stock_quantity, self.availability_status, low_stock_threshold)),
+            ),
+            StockVisibility::Hidden => (None, None),
+        };
+        let (price_cents, currency) = self.price_for_currency(requested_currency, default_currency);
+
+        FoodResponse {
+            food_id: self.food_id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            ingredients: self.ingredients.clone(),
+            price_cents,
+            currency,
+            stock_quantity,
+            stock_level,
+            availability_status: self.availability_status,
+            pet_type: self.pet_type,
+            food_type: self.food_type,
+            image_path: self.image_path.clone(),
+            categories: self.categories.clone(),
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FoodType;
+
+    fn food(stock_quantity: u32, availability_status: AvailabilityStatus) -> Food {
+        Food {
+            food_id: "dog-food".to_string(),
+            name: "Dog Food".to_string(),
+            description: "Crunchy kibble".to_string(),
+            ingredients: vec!["chicken".to_string()],
+            price_cents: 1299,
+            stock_quantity,
+            availability_status,
+            pet_type: PetType::Dog,
+            food_type: FoodType::Dry,
+            image_path: "/images/dog-food.png".to_string(),
+            categories: Vec::new(),
+            prices: std::collections::HashMap::new(),
+            updated_at: chrono::Utc::now(),
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn exact_visibility_passes_the_stock_quantity_through_unchanged() {
+        let response = food(7, AvailabilityStatus::InStock).to_response(StockVisibility::Exact, 5, "USD", "USD");
+
+        assert_eq!(response.stock_quantity, Some(7));
+        assert_eq!(response.stock_level, None);
+    }
+
+    #[test]
+    fn coarse_visibility_buckets_a_healthy_count_as_in_stock() {
+        let response = food(20, AvailabilityStatus::InStock).to_response(StockVisibility::Coarse, 5, "USD", "USD");
+
+        assert_eq!(response.stock_quantity, None);
+        assert_eq!(response.stock_level, Some(StockLevel::InStock));
+    }
+
+    #[test]
+    fn coarse_visibility_buckets_a_count_at_or_below_the_threshold_as_low_stock() {
+        let response = food(5, AvailabilityStatus::InStock).to_response(StockVisibility::Coarse, 5, "USD", "USD");
+
+        assert_eq!(response.stock_level, Some(StockLevel::LowStock));
+    }
+
+    #[test]
+    fn coarse_visibility_buckets_an_out_of_stock_food_as_out_regardless_of_the_threshold() {
+        let response = food(0, AvailabilityStatus::OutOfStock).to_response(StockVisibility::Coarse, 5, "USD", "USD");
+
+        assert_eq!(response.stock_level, Some(StockLevel::Out));
+    }
+
+    #[test]
+    fn hidden_visibility_omits_stock_information_entirely() {
+        let response = food(7, AvailabilityStatus::InStock).to_response(StockVisibility::Hidden, 5, "USD", "USD");
+
+        assert_eq!(response.stock_quantity, None);
+        assert_eq!(response.stock_level, None);
+    }
+
+    #[test]
+    fn parse_falls_back_to_exact_for_an_unrecognized_value() {
+        assert_eq!(StockVisibility::parse("bogus"), StockVisibility::Exact);
+        assert_eq!(StockVisibility::parse("COARSE"), StockVisibility::Coarse);
+        assert_eq!(StockVisibility::parse("hidden"), StockVisibility::Hidden);
+    }
+
+    #[test]
+    fn to_response_renders_the_matching_currency_price_when_present() {
+        let mut dog_food = food(7, AvailabilityStatus::InStock);
+        dog_food.prices = std::collections::HashMap::from([("EUR".to_string(), 1199)]);
+
+        let response = dog_food.to_response(StockVisibility::Exact, 5, "eur", "USD");
+
+        assert_eq!(response.price_cents, 1199);
+        assert_eq!(response.currency, "EUR");
+    }
+
+    #[test]
+    fn to_response_falls_back_to_the_default_currency_when_the_requested_one_is_absent() {
+        let response = food(7, AvailabilityStatus::InStock).to_response(StockVisibility::Exact, 5, "GBP", "USD");
+
+        assert_eq!(response.price_cents, 1299);
+        assert_eq!(response.currency, "USD");
+    }
+}
@@ -0,0 +1,135 @@
+use super::Food;
+
+/// One `search_foods_ranked` result: the matching food and the score it
+/// earned. Serialization isn't needed yet — nothing renders this over HTTP
+/// — so this stays a plain struct rather than picking up `Serialize`.
+#[derive(Debug, Clone)]
+pub struct FoodSearchResult {
+    pub food: Food,
+    pub score: u32,
+}
+
+/// A name match outranks an ingredient match, which outranks a
+/// description-only match — someone searching "chicken" almost always means
+/// "a chicken food", not "a food that happens to mention chicken in its
+/// blurb".
+const NAME_MATCH_SCORE: u32 = 100;
+const INGREDIENT_MATCH_SCORE: u32 = 10;
+const DESCRIPTION_MATCH_SCORE: u32 = 1;
+
+/// Case-insensitive search across `name`, `ingredients`, and `description`,
+/// scoring and ordering matches by relevance instead of returning them in
+/// scan order. A food matching none of the three fields is excluded
+/// entirely; ties are broken by `food_id` for a stable order. An empty
+/// `term` matches nothing, same as no term at all.
+pub fn search_foods_ranked(foods: &[Food], term: &str) -> Vec<FoodSearchResult> {
+    let term = term.to_lowercase();
+    if term.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results: Vec<FoodSearchResult> = foods
+        .iter()
+        .filter_map(|food| score_food(food, &term).map(|score| FoodSearchResult { food: food.clone(), score }))
+        .collect();
+
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.food.food_id.cmp(&b.food.food_id)));
+    results
+}
+
+/// `None` when `food` matches none of the three fields, so the caller can
+/// filter it out entirely rather than including a zero-score result.
+fn score_food(food: &Food, lowercase_term: &str) -> Option<u32> {
+    let mut score = 0;
+
+    if food.name.to_lowercase().contains(lowercase_term) {
+        score += NAME_MATCH_SCORE;
+    }
+    if food.ingredients.iter().any(|ingredient| ingredient.to_lowercase().contains(lowercase_term)) {
+        score += INGREDIENT_MATCH_SCORE;
+    }
+    if food.description.to_lowercase().contains(lowercase_term) {
+        score += DESCRIPTION_MATCH_SCORE;
+    }
+
+    (score > 0).then_some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::models::{AvailabilityStatus, FoodType, PetType};
+
+    fn food(food_id: &str, name: &str, description: &str, ingredients: Vec<&str>) -> Food {
+        Food {
+            food_id: food_id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            ingredients: ingredients.into_iter().map(str::to_string).collect(),
+            price_cents: 100,
+            stock_quantity: 5,
+            availability_status: AvailabilityStatus::InStock,
+            pet_type: PetType::Dog,
+            food_type: FoodType::Dry,
+            image_path: String::new(),
+            categories: Vec::new(),
+            prices: std::collections::HashMap::new(),
+            updated_at: Utc::now(),
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn matching_is_case_insensitive() {
+        let foods = vec![food("food-1", "Chicken Delight", "", vec![])];
+
+        let results = search_foods_ranked(&foods, "CHICKEN");
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].food.food_id, "food-1");
+    }
+
+    #[test]
+    fn a_name_match_ranks_above_a_description_only_match() {
+        let foods = vec![
+            food("food-description-only", "Salmon Bites", "great for chicken-loving dogs too", vec![]),
+            food("food-name-match", "Chicken Bites", "a tasty meal", vec![]),
+        ];
+
+        let results = search_foods_ranked(&foods, "chicken");
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].food.food_id, "food-name-match");
+        assert_eq!(results[1].food.food_id, "food-description-only");
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[test]
+    fn an_ingredient_match_ranks_above_a_description_only_match() {
+        let foods = vec![
+            food("food-description-only", "Salmon Bites", "chicken-approved by picky eaters", vec![]),
+            food("food-ingredient-match", "Salmon Bites", "a tasty meal", vec!["chicken", "rice"]),
+        ];
+
+        let results = search_foods_ranked(&foods, "chicken");
+
+        assert_eq!(results[0].food.food_id, "food-ingredient-match");
+        assert_eq!(results[1].food.food_id, "food-description-only");
+    }
+
+    #[test]
+    fn a_food_matching_nothing_is_excluded() {
+        let foods = vec![food("food-1", "Salmon Bites", "a tasty meal", vec!["salmon"])];
+
+        assert!(search_foods_ranked(&foods, "chicken").is_empty());
+    }
+
+    #[test]
+    fn an_empty_term_matches_nothing() {
+        let foods = vec![food("food-1", "Chicken Delight", "", vec![])];
+
+        assert!(search_foods_ranked(&foods, "").is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::error::{ApiError, ApiResult};
+
+/// Ordering applied to `GET /api/foods` results after filtering, via
+/// `?sort=`. Unlike `SortOrder` (which only orders recommendation results
+/// after pet-type prioritization), this sorts the catalog listing directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoodSort {
+    PriceAsc,
+    PriceDesc,
+    NameAsc,
+    NameDesc,
+    NewestFirst,
+    StockDesc,
+}
+
+impl FoodSort {
+    pub fn parse(value: &str) -> ApiResult<Self> {
+        match value {
+            "price_asc" => Ok(FoodSort::PriceAsc),
+            "price_desc" => Ok(FoodSort::PriceDesc),
+            "name_asc" => Ok(FoodSort::NameAsc),
+            "name_desc" => Ok(FoodSort::NameDesc),
+            "newest_first" => Ok(FoodSort::NewestFirst),
+            "stock_desc" => Ok(FoodSort::StockDesc),
+            other => Err(ApiError::Validation(format!("unknown sort {other}"))),
+        }
+    }
+}
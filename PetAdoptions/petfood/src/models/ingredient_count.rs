@@ -0,0 +1,97 @@
+use serde::Serialize;
+
+use super::Food;
+
+/// One entry in `GET /api/foods/ingredients`: an ingredient name and how
+/// many foods in the catalog list it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IngredientCount {
+    pub ingredient: String,
+    pub count: usize,
+}
+
+/// Tallies how many times each ingredient appears across `foods`, sorted by
+/// occurrence count descending (ties broken alphabetically for a stable
+/// order), truncated to `limit` entries when given.
+pub fn top_ingredients(foods: &[Food], limit: Option<usize>) -> Vec<IngredientCount> {
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for food in foods {
+        for ingredient in &food.ingredients {
+            *counts.entry(ingredient.as_str()).or_insert(0) += 1;
+        }
+    }
+
+    let mut ranked: Vec<IngredientCount> = counts
+        .into_iter()
+        .map(|(ingredient, count)| IngredientCount { ingredient: ingredient.to_string(), count })
+        .collect();
+    ranked.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.ingredient.cmp(&b.ingredient)));
+
+    if let Some(limit) = limit {
+        ranked.truncate(limit);
+    }
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::models::{AvailabilityStatus, FoodType, PetType};
+
+    fn food(ingredients: Vec<&str>) -> Food {
+        Food {
+            food_id: "food".to_string(),
+            name: "food".to_string(),
+            description: String::new(),
+            ingredients: ingredients.into_iter().map(str::to_string).collect(),
+            price_cents: 100,
+            stock_quantity: 5,
+            availability_status: AvailabilityStatus::InStock,
+            pet_type: PetType::Dog,
+            food_type: FoodType::Dry,
+            image_path: String::new(),
+            categories: Vec::new(),
+            prices: std::collections::HashMap::new(),
+            updated_at: Utc::now(),
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn top_ingredients_orders_by_occurrence_count_descending() {
+        let foods = vec![
+            food(vec!["chicken", "rice"]),
+            food(vec!["chicken", "corn"]),
+            food(vec!["chicken"]),
+        ];
+
+        let ranked = top_ingredients(&foods, None);
+
+        assert_eq!(
+            ranked,
+            vec![
+                IngredientCount { ingredient: "chicken".to_string(), count: 3 },
+                IngredientCount { ingredient: "corn".to_string(), count: 1 },
+                IngredientCount { ingredient: "rice".to_string(), count: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn top_ingredients_respects_the_limit() {
+        let foods = vec![food(vec!["chicken", "rice", "corn"])];
+
+        let ranked = top_ingredients(&foods, Some(2));
+
+        assert_eq!(ranked.len(), 2);
+    }
+
+    #[test]
+    fn top_ingredients_with_no_limit_returns_everything() {
+        let foods = vec![food(vec!["chicken", "rice", "corn"])];
+
+        assert_eq!(top_ingredients(&foods, None).len(), 3);
+    }
+}
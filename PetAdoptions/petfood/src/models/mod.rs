@@ -0,0 +1,31 @@
+pub mod audit_entry;
+pub mod cart;
+pub mod checkout_request;
+pub mod discount;
+pub mod food;
+pub mod food_response;
+pub mod food_search;
+pub mod food_sort;
+pub mod ingredient_count;
+pub mod order;
+pub mod quantity;
+pub mod recommendation_stats;
+pub mod recommendations_response;
+pub mod seed_rejection;
+pub mod sort_order;
+
+pub use audit_entry::AuditEntry;
+pub use cart::{BulkAddResult, Cart, CartIssueKind, CartItem, CartResponse, CartValidationIssue, CartValidationResponse};
+pub use checkout_request::CheckoutRequest;
+pub use discount::{Discount, DiscountKind};
+pub use food::{AvailabilityStatus, CreateFoodRequest, CreationSource, Food, FoodType, PetType};
+pub use food_response::{FoodResponse, StockVisibility};
+pub use food_search::{search_foods_ranked, FoodSearchResult};
+pub use food_sort::FoodSort;
+pub use ingredient_count::{top_ingredients, IngredientCount};
+pub use order::Order;
+pub use quantity::Quantity;
+pub use recommendation_stats::{RecommendationStats, RecommendationStatsForAllPetTypes};
+pub use recommendations_response::{EmptyRecommendationReason, RecommendationsResponse};
+pub use seed_rejection::{seed_quality_errors, SeedRejection};
+pub use sort_order::SortOrder;
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use super::cart::CartItem;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Order {
+    pub order_id: String,
+    pub user_id: String,
+    pub items: Vec<CartItem>,
+    /// Sum of each line's `price_cents * quantity`, before `discount_cents`
+    /// is subtracted.
+    pub subtotal_cents: i64,
+    /// How much `applied_coupon` knocked off `subtotal_cents`. `0` when no
+    /// coupon was applied.
+    pub discount_cents: i64,
+    /// `subtotal_cents - discount_cents` — the amount actually charged.
+    pub total_cents: i64,
+    pub applied_coupon: Option<String>,
+    pub shipping_address: Option<String>,
+    pub payment_method_token: Option<String>,
+    pub notes: Option<String>,
+}
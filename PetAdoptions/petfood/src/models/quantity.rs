@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+const MIN_QUANTITY: u32 = 1;
+const MAX_QUANTITY: u32 = 100;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum QuantityError {
+    #[error("quantity must be at least {MIN_QUANTITY}, got {actual}")]
+    TooLow { actual: u32 },
+    #[error("quantity must be at most {MAX_QUANTITY}, got {actual}")]
+    TooHigh { actual: u32 },
+}
+
+/// A cart line quantity, bounds-checked once on construction so an invalid
+/// value (zero, or more than a single line can reasonably hold) can't flow
+/// into cart state — callers no longer need to re-check it at every site
+/// that touches a quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct Quantity(u32);
+
+impl TryFrom<u32> for Quantity {
+    type Error = QuantityError;
+
+    fn try_from(actual: u32) -> Result<Self, Self::Error> {
+        if actual < MIN_QUANTITY {
+            Err(QuantityError::TooLow { actual })
+        } else if actual > MAX_QUANTITY {
+            Err(QuantityError::TooHigh { actual })
+        } else {
+            Ok(Self(actual))
+        }
+    }
+}
+
+impl From<Quantity> for u32 {
+    fn from(quantity: Quantity) -> Self {
+        quantity.0
+    }
+}
+
+impl Quantity {
+    pub const MAX: u32 = MAX_QUANTITY;
+
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_zero() {
+        assert_eq!(Quantity::try_from(0), Err(QuantityError::TooLow { actual: 0 }));
+    }
+
+    #[test]
+    fn accepts_the_maximum() {
+        assert_eq!(Quantity::try_from(MAX_QUANTITY).unwrap().get(), MAX_QUANTITY);
+    }
+
+    #[test]
+    fn rejects_over_the_maximum() {
+        assert_eq!(
+            Quantity::try_from(MAX_QUANTITY + 1),
+            Err(QuantityError::TooHigh { actual: MAX_QUANTITY + 1 })
+        );
+    }
+}
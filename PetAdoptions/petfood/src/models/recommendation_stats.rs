@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::PetType;
+
+/// Summary returned alongside a recommendation list; `average_price` is
+/// rounded to 2 decimal places at the response boundary.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecommendationStats {
+    pub count: usize,
+    #[serde(serialize_with = "crate::money::serialize_price")]
+    pub average_price: f64,
+}
+
+impl RecommendationStats {
+    pub fn from_price_cents(price_cents: &[i64]) -> Self {
+        let count = price_cents.len();
+        let average_price = if count == 0 {
+            0.0
+        } else {
+            let total: i64 = price_cents.iter().sum();
+            crate::money::round2(total as f64 / count as f64 / 100.0)
+        };
+
+        Self { count, average_price }
+    }
+}
+
+/// Returned by `RecommendationService::get_recommendation_stats_for_all_pet_types`.
+/// `partial` is `true` when the configured time budget ran out before every
+/// pet type's query finished — `stats` then holds only whichever queries
+/// landed in time, rather than the full `PetType::ALL` set.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecommendationStatsForAllPetTypes {
+    pub stats: HashMap<PetType, RecommendationStats>,
+    pub partial: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn average_price_rounds_long_decimal_tails() {
+        let stats = RecommendationStats::from_price_cents(&[1000, 1000, 1001]);
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.average_price, 10.0);
+    }
+
+    #[test]
+    fn average_price_is_zero_for_an_empty_list() {
+        let stats = RecommendationStats::from_price_cents(&[]);
+        assert_eq!(stats.count, 0);
+        assert_eq!(stats.average_price, 0.0);
+    }
+}
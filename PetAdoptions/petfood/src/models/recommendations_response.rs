@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+use super::Food;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyRecommendationReason {
+    /// The catalog has no foods at all, for any pet type.
+    NoCatalog,
+    /// The catalog has foods, but none for the requested pet type.
+    NoActiveFoods,
+    /// The catalog has foods for the requested pet type, but all are out of
+    /// stock.
+    OutOfStock,
+}
+
+/// Shape returned by `GET /api/recommendations/:pet_type`. Serializes as a
+/// bare array in the common case, preserving the pre-existing wire format;
+/// only becomes an object carrying `reason` when the result is empty and
+/// `PETFOOD_RECOMMENDATION_EMPTY_REASON_ENABLED` opts into explaining why.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum RecommendationsResponse {
+    Foods(Vec<Food>),
+    EmptyWithReason {
+        foods: Vec<Food>,
+        reason: EmptyRecommendationReason,
+    },
+}
@@ -0,0 +1,86 @@
+use serde::Serialize;
+
+use super::CreateFoodRequest;
+
+/// One seed record `FoodService::create_foods_batch` refused to write, with
+/// every content-quality rule it tripped.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeedRejection {
+    pub name: String,
+    pub errors: Vec<String>,
+}
+
+/// Content-quality checks applied only to batch-seeded records, not the
+/// synchronous `POST /api/admin/foods` path: external seed files sometimes carry
+/// placeholder junk ("TODO", "lorem ipsum") that a non-blank description
+/// already satisfies `CreateFoodRequest::validate`, so that check wouldn't
+/// catch it.
+pub fn seed_quality_errors(
+    req: &CreateFoodRequest,
+    min_description_length: usize,
+    banned_placeholder_substrings: &[String],
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    let description = req.description.trim();
+    if description.len() < min_description_length {
+        errors.push(format!(
+            "description is shorter than the minimum of {min_description_length} characters"
+        ));
+    }
+
+    let lower = description.to_lowercase();
+    for substring in banned_placeholder_substrings {
+        if !substring.is_empty() && lower.contains(&substring.to_lowercase()) {
+            errors.push(format!("description contains banned placeholder text '{substring}'"));
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{FoodType, PetType};
+
+    fn request(description: &str) -> CreateFoodRequest {
+        CreateFoodRequest {
+            name: "Test Food".to_string(),
+            description: description.to_string(),
+            ingredients: Vec::new(),
+            price_cents: 100,
+            stock_quantity: 1,
+            pet_type: PetType::Dog,
+            food_type: FoodType::Dry,
+            image_path: String::new(),
+            categories: Vec::new(),
+            prices: std::collections::HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn seed_quality_errors_accepts_a_clean_record() {
+        let req = request("Crunchy chicken-based kibble with real meat");
+
+        assert!(seed_quality_errors(&req, 10, &["TODO".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn seed_quality_errors_trips_the_minimum_length_rule() {
+        let req = request("Tasty");
+
+        let errors = seed_quality_errors(&req, 10, &[]);
+
+        assert_eq!(errors, vec!["description is shorter than the minimum of 10 characters".to_string()]);
+    }
+
+    #[test]
+    fn seed_quality_errors_trips_the_banned_substring_rule_case_insensitively() {
+        let req = request("TODO: write a real description for this food");
+
+        let errors = seed_quality_errors(&req, 10, &["todo".to_string()]);
+
+        assert_eq!(errors, vec!["description contains banned placeholder text 'todo'".to_string()]);
+    }
+}
@@ -0,0 +1,19 @@
+use crate::error::{ApiError, ApiResult};
+
+/// Ordering applied to recommendation results after pet-type prioritization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    /// Keep the pet-type-prioritized order produced by the recommendation logic.
+    None,
+    PriceAsc,
+}
+
+impl SortOrder {
+    pub fn parse(value: &str) -> ApiResult<Self> {
+        match value {
+            "none" => Ok(SortOrder::None),
+            "price_asc" => Ok(SortOrder::PriceAsc),
+            other => Err(ApiError::Validation(format!("unknown sort order {other}"))),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::http::{header, HeaderMap};
+use serde::Serializer;
+
+use crate::filters::parse_multi_value_query;
+
+/// Locale regions mapped to the currency a storefront serving that region
+/// would price in, for an `Accept-Language`-only caller who never sends an
+/// explicit `?currency=`. Deliberately small — just the regions this
+/// catalog currently fronts; an unmapped region falls back to
+/// `default_currency`.
+const REGION_CURRENCIES: &[(&str, &str)] = &[
+    ("US", "USD"),
+    ("GB", "GBP"),
+    ("DE", "EUR"),
+    ("FR", "EUR"),
+    ("JP", "JPY"),
+    ("CA", "CAD"),
+];
+
+/// Resolves the currency `Food::to_response` should price in: an explicit
+/// `?currency=` wins, then the region implied by the first `Accept-Language`
+/// tag, then `default_currency`. Whether the resolved currency actually has
+/// a price on a given food is a separate fallback, handled by
+/// `Food::price_for_currency`.
+pub fn resolve_currency(headers: &HeaderMap, query: &str, default_currency: &str) -> String {
+    if let Some(currency) = parse_multi_value_query(query, "currency").first() {
+        return currency.to_ascii_uppercase();
+    }
+    if let Some(currency) = currency_from_accept_language(headers) {
+        return currency;
+    }
+    default_currency.to_ascii_uppercase()
+}
+
+fn currency_from_accept_language(headers: &HeaderMap) -> Option<String> {
+    let raw = headers.get(header::ACCEPT_LANGUAGE)?.to_str().ok()?;
+    let first_tag = raw.split(',').next()?.split(';').next()?.trim();
+    let region = first_tag.split(['-', '_']).nth(1)?.to_ascii_uppercase();
+    REGION_CURRENCIES
+        .iter()
+        .find(|(candidate, _)| *candidate == region)
+        .map(|(_, currency)| currency.to_string())
+}
+
+/// Toggled once at startup from `PETFOOD_PRICE_AS_STRING`; read by
+/// [`serialize_price`] on every response. A global flag rather than a
+/// per-call argument because `serde`'s `serialize_with` hook has no way to
+/// thread request-scoped or config state through to the `Serializer`.
+static PRICE_AS_STRING: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether [`serialize_price`] renders prices as JSON strings instead
+/// of numbers. Called once from `main` with the resolved `ServerConfig`.
+pub fn set_price_as_string(enabled: bool) {
+    PRICE_AS_STRING.store(enabled, Ordering::Relaxed);
+}
+
+/// Serializes a rounded dollar amount as a JSON number by default, or as a
+/// `"12.99"`-style decimal string when `PETFOOD_PRICE_AS_STRING=true`, for
+/// clients that parse JSON numbers as floats and would otherwise lose
+/// precision. Intended for use via `#[serde(serialize_with = "...")]` on
+/// response price fields.
+pub fn serialize_price<S>(value: &f64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if PRICE_AS_STRING.load(Ordering::Relaxed) {
+        serializer.serialize_str(&format!("{value:.2}"))
+    } else {
+        serializer.serialize_f64(*value)
+    }
+}
+
+/// Rounds a dollar amount to 2 decimal places using banker's rounding
+/// (round-half-to-even), so money values never surface long decimal tails at
+/// the response boundary and repeated rounding doesn't introduce bias.
+pub fn round2(value: f64) -> f64 {
+    let scaled = value * 100.0;
+    let floor = scaled.floor();
+    let diff = scaled - floor;
+
+    let rounded = if (diff - 0.5).abs() < f64::EPSILON {
+        if (floor as i64) % 2 == 0 {
+            floor
+        } else {
+            floor + 1.0
+        }
+    } else {
+        scaled.round()
+    };
+
+    rounded / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serialize(value: f64) -> String {
+        serde_json::to_string(&SerializeWith { value }).unwrap()
+    }
+
+    struct SerializeWith {
+        value: f64,
+    }
+
+    impl serde::Serialize for SerializeWith {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serialize_price(&self.value, serializer)
+        }
+    }
+
+    /// Exercises both representations in one test, rather than two tests
+    /// that independently flip the shared global flag, since tests run
+    /// concurrently and could otherwise race on it.
+    #[test]
+    fn serialize_price_round_trips_both_representations() {
+        set_price_as_string(false);
+        assert_eq!(serialize(12.99), "12.99");
+
+        set_price_as_string(true);
+        assert_eq!(serialize(12.99), "\"12.99\"");
+
+        set_price_as_string(false);
+    }
+
+    #[test]
+    fn rounds_down_on_the_half_when_the_lower_cent_is_even() {
+        assert_eq!(round2(0.125), 0.12);
+    }
+
+    #[test]
+    fn rounds_up_on_the_half_when_the_lower_cent_is_odd() {
+        assert_eq!(round2(0.375), 0.38);
+    }
+
+    #[test]
+    fn rounds_normally_away_from_the_half() {
+        assert_eq!(round2(1.2345), 1.23);
+        assert_eq!(round2(1.236), 1.24);
+    }
+
+    #[test]
+    fn leaves_already_rounded_values_unchanged() {
+        assert_eq!(round2(9.99), 9.99);
+        assert_eq!(round2(0.0), 0.0);
+    }
+
+    #[test]
+    fn resolve_currency_prefers_the_explicit_query_parameter() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_LANGUAGE, "en-GB".parse().unwrap());
+
+        assert_eq!(resolve_currency(&headers, "currency=eur", "USD"), "EUR");
+    }
+
+    #[test]
+    fn resolve_currency_falls_back_to_the_accept_language_region_when_no_query_param_is_present() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_LANGUAGE, "en-GB,en;q=0.9".parse().unwrap());
+
+        assert_eq!(resolve_currency(&headers, "", "USD"), "GBP");
+    }
+
+    #[test]
+    fn resolve_currency_falls_back_to_the_default_when_nothing_resolves() {
+        let headers = HeaderMap::new();
+
+        assert_eq!(resolve_currency(&headers, "", "usd"), "USD");
+    }
+
+    #[test]
+    fn resolve_currency_falls_back_to_the_default_for_an_unmapped_region() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_LANGUAGE, "zh-CN".parse().unwrap());
+
+        assert_eq!(resolve_currency(&headers, "", "USD"), "USD");
+    }
+}
@@ -0,0 +1,183 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use aws_sdk_ssm::Client as SsmClient;
+use tokio::net::TcpStream;
+
+/// TCP-connects to the OTLP collector's `host:port` within `timeout`, never
+/// erroring — a connect failure just means "not reachable", which is the
+/// caller's signal to fail readiness, not a condition worth its own error
+/// type.
+pub async fn probe_otlp_reachable(endpoint: &str, timeout: Duration) -> bool {
+    matches!(tokio::time::timeout(timeout, TcpStream::connect(endpoint)).await, Ok(Ok(_)))
+}
+
+/// Per-dependency reachability from [`ReadinessChecker::check`], rendered
+/// into `GET /health/ready` / `GET /health/status`'s response body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DependencyStatus {
+    pub dynamodb_reachable: bool,
+    pub ssm_reachable: bool,
+}
+
+impl DependencyStatus {
+    pub fn all_reachable(&self) -> bool {
+        self.dynamodb_reachable && self.ssm_reachable
+    }
+}
+
+/// Backs the `ready_requires_aws` check: probes DynamoDB (`describe_table`
+/// against both the foods and carts tables) and SSM (`describe_parameters`),
+/// each bounded by `timeout` for the same reason as
+/// [`crate::startup_probes::run_startup_probes`]. Unlike the startup probes,
+/// a failure here is meant to be surfaced, not just logged.
+///
+/// Results are cached for `cache_ttl` so a load balancer polling
+/// `/health/ready` every few seconds doesn't repeat the same AWS calls on
+/// every poll. `None` disables caching and probes on every call.
+pub struct ReadinessChecker {
+    dynamodb_client: DynamoDbClient,
+    ssm_client: SsmClient,
+    foods_table_name: String,
+    carts_table_name: String,
+    timeout: Duration,
+    cache_ttl: Option<Duration>,
+    cached: Mutex<Option<(Instant, DependencyStatus)>>,
+}
+
+impl ReadinessChecker {
+    pub fn new(
+        dynamodb_client: DynamoDbClient,
+        ssm_client: SsmClient,
+        foods_table_name: String,
+        carts_table_name: String,
+        timeout: Duration,
+        cache_ttl: Option<Duration>,
+    ) -> Self {
+        Self {
+            dynamodb_client,
+            ssm_client,
+            foods_table_name,
+            carts_table_name,
+            timeout,
+            cache_ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub async fn check(&self) -> DependencyStatus {
+        if let Some(cache_ttl) = self.cache_ttl {
+            if let Some((checked_at, status)) = *self.cached.lock().unwrap() {
+                if checked_at.elapsed() < cache_ttl {
+                    return status;
+                }
+            }
+        }
+
+        let status = DependencyStatus {
+            dynamodb_reachable: self.probe_dynamodb(&self.foods_table_name).await
+                && self.probe_dynamodb(&self.carts_table_name).await,
+            ssm_reachable: self.probe_ssm().await,
+        };
+
+        if self.cache_ttl.is_some() {
+            *self.cached.lock().unwrap() = Some((Instant::now(), status));
+        }
+
+        status
+    }
+
+    async fn probe_dynamodb(&self, table_name: &str) -> bool {
+        bounded(self.timeout, self.dynamodb_client.describe_table().table_name(table_name).send()).await
+    }
+
+    async fn probe_ssm(&self) -> bool {
+        bounded(self.timeout, self.ssm_client.describe_parameters().send()).await
+    }
+}
+
+async fn bounded<F, T, E>(timeout: Duration, call: F) -> bool
+where
+    F: Future<Output = Result<T, E>>,
+{
+    matches!(tokio::time::timeout(timeout, call).await, Ok(Ok(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_reachable_when_something_is_listening() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = listener.accept().await;
+        });
+
+        assert!(probe_otlp_reachable(&addr.to_string(), Duration::from_millis(500)).await);
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_when_nothing_is_listening() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        assert!(!probe_otlp_reachable(&addr.to_string(), Duration::from_millis(500)).await);
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_on_a_malformed_endpoint() {
+        assert!(!probe_otlp_reachable("not a valid endpoint", Duration::from_millis(500)).await);
+    }
+
+    #[test]
+    fn all_reachable_requires_both_dependencies() {
+        assert!(DependencyStatus { dynamodb_reachable: true, ssm_reachable: true }.all_reachable());
+        assert!(!DependencyStatus { dynamodb_reachable: true, ssm_reachable: false }.all_reachable());
+        assert!(!DependencyStatus { dynamodb_reachable: false, ssm_reachable: true }.all_reachable());
+    }
+
+    fn unreachable_checker(cache_ttl: Option<Duration>) -> ReadinessChecker {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .endpoint_url("http://127.0.0.1:1")
+            .build();
+        ReadinessChecker::new(
+            aws_sdk_dynamodb::Client::new(&sdk_config),
+            aws_sdk_ssm::Client::new(&sdk_config),
+            "test-foods".to_string(),
+            "test-carts".to_string(),
+            Duration::from_millis(200),
+            cache_ttl,
+        )
+    }
+
+    #[tokio::test]
+    async fn check_reports_both_dependencies_unreachable_when_nothing_is_listening() {
+        let checker = unreachable_checker(None);
+
+        let status = checker.check().await;
+
+        assert!(!status.dynamodb_reachable);
+        assert!(!status.ssm_reachable);
+    }
+
+    #[tokio::test]
+    async fn check_reuses_the_cached_result_within_the_ttl() {
+        let checker = unreachable_checker(Some(Duration::from_secs(60)));
+
+        let first = checker.check().await;
+        *checker.cached.lock().unwrap() = Some((Instant::now(), DependencyStatus { dynamodb_reachable: true, ssm_reachable: true }));
+        let second = checker.check().await;
+
+        assert_eq!(first, DependencyStatus { dynamodb_reachable: false, ssm_reachable: false });
+        assert_eq!(second, DependencyStatus { dynamodb_reachable: true, ssm_reachable: true });
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::AuditEntry;
+
+/// Caps how many entries a single `history_for` call returns, so a food
+/// with a long edit history can't trigger an unbounded response.
+const HISTORY_LIMIT: usize = 200;
+
+#[async_trait::async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn put_entry(&self, entry: &AuditEntry) -> ApiResult<()>;
+
+    /// Entries for `food_id`, newest first, capped at `HISTORY_LIMIT`.
+    async fn history_for(&self, food_id: &str) -> ApiResult<Vec<AuditEntry>>;
+}
+
+pub struct DynamoDbAuditRepository {
+    client: DynamoDbClient,
+    table_name: String,
+}
+
+impl DynamoDbAuditRepository {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+}
+
+fn entry_to_item(entry: &AuditEntry) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert("entry_id".to_string(), AttributeValue::S(Uuid::new_v4().to_string()));
+    item.insert("food_id".to_string(), AttributeValue::S(entry.food_id.clone()));
+    item.insert("field".to_string(), AttributeValue::S(entry.field.clone()));
+    item.insert("old_value".to_string(), AttributeValue::S(entry.old_value.clone()));
+    item.insert("new_value".to_string(), AttributeValue::S(entry.new_value.clone()));
+    item.insert("changed_at".to_string(), AttributeValue::S(entry.changed_at.to_rfc3339()));
+    if let Some(correlation_id) = &entry.correlation_id {
+        item.insert("correlation_id".to_string(), AttributeValue::S(correlation_id.clone()));
+    }
+    item
+}
+
+fn item_to_entry(item: &HashMap<String, AttributeValue>) -> ApiResult<AuditEntry> {
+    let get_s = |key: &str| -> ApiResult<String> {
+        item.get(key)
+            .and_then(|v| v.as_s().ok())
+            .map(|s| s.to_string())
+            .ok_or_else(|| ApiError::Internal(format!("missing attribute {key}")))
+    };
+
+    Ok(AuditEntry {
+        food_id: get_s("food_id")?,
+        field: get_s("field")?,
+        old_value: get_s("old_value")?,
+        new_value: get_s("new_value")?,
+        changed_at: item
+            .get("changed_at")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now),
+        correlation_id: item
+            .get("correlation_id")
+            .and_then(|v| v.as_s().ok())
+            .map(|s| s.to_string()),
+    })
+}
+
+#[async_trait::async_trait]
+impl AuditRepository for DynamoDbAuditRepository {
+    async fn put_entry(&self, entry: &AuditEntry) -> ApiResult<()> {
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(entry_to_item(entry)))
+            .send()
+            .await
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Queries the `food_id` GSI and sorts newest-first client-side — the
+    /// GSI only guarantees partition-key locality until it's also given
+    /// `changed_at` as a range key.
+    async fn history_for(&self, food_id: &str) -> ApiResult<Vec<AuditEntry>> {
+        let output = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .index_name("food_id-index")
+            .key_condition_expression("food_id = :food_id")
+            .expression_attribute_values(":food_id", AttributeValue::S(food_id.to_string()))
+            .send()
+            .await
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+        let mut entries = output
+            .items
+            .unwrap_or_default()
+            .iter()
+            .map(item_to_entry)
+            .collect::<ApiResult<Vec<AuditEntry>>>()?;
+
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.changed_at));
+        entries.truncate(HISTORY_LIMIT);
+        Ok(entries)
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use chrono::{DateTime, Utc};
+use tracing::Instrument;
+
+use crate::config::resolve_table_name;
+use crate::error::{ApiError, ApiResult};
+use crate::models::{Cart, CartItem};
+use crate::repository::tracing::{client_region, dynamodb_span};
+use crate::ttl::compute_expiry;
+
+/// Sane bounds for the configured cart TTL, applied by [`compute_expiry`] —
+/// guards against a clock-skewed instance or a badly misconfigured
+/// `PETFOOD_CART_TTL_DAYS` producing an `expires_at` in the past or one so
+/// far out it never meaningfully expires.
+const MIN_CART_TTL: chrono::Duration = chrono::Duration::hours(1);
+const MAX_CART_TTL: chrono::Duration = chrono::Duration::days(365);
+
+#[async_trait::async_trait]
+pub trait CartRepository: Send + Sync {
+    async fn get_cart(&self, user_id: &str, tenant_id: Option<&str>) -> ApiResult<Option<Cart>>;
+
+    /// Writes `cart`, conditioned on the stored version still matching
+    /// `cart.version` (or the item not existing yet, for a brand-new cart).
+    /// The stored version is bumped by one on a successful write. Returns
+    /// `ApiError::Conflict` if another writer updated the cart first —
+    /// callers should re-read and re-apply their mutation rather than treat
+    /// this like any other failure.
+    async fn put_cart(&self, cart: &Cart, tenant_id: Option<&str>) -> ApiResult<()>;
+
+    /// Backs `POST /api/admin/carts/cleanup`'s stale-cart sweep. Pages
+    /// through the full table rather than a single `scan`, since an
+    /// operator-triggered cleanup should see every cart, not just the first
+    /// page DynamoDB happens to return.
+    async fn list_carts(&self, tenant_id: Option<&str>) -> ApiResult<Vec<Cart>>;
+    async fn delete_cart(&self, user_id: &str, tenant_id: Option<&str>) -> ApiResult<()>;
+}
+
+pub struct DynamoDbCartRepository {
+    client: DynamoDbClient,
+    base_table_name: String,
+    multi_tenant_tables_enabled: bool,
+    /// `PETFOOD_CART_TTL_DAYS` — see `ServerConfig::cart_ttl_days`. Written
+    /// as `expires_at` on every `put_cart` and consulted by `get_cart` to
+    /// treat an expired-but-not-yet-physically-deleted cart as not found.
+    ttl_days: i64,
+}
+
+impl DynamoDbCartRepository {
+    pub fn new(client: DynamoDbClient, base_table_name: String, ttl_days: i64, multi_tenant_tables_enabled: bool) -> Self {
+        Self { client, base_table_name, multi_tenant_tables_enabled, ttl_days }
+    }
+
+    /// `{base_table_name}-{tenant_id}` when `PETFOOD_MULTI_TENANT_TABLES_ENABLED`
+    /// is set and a tenant is given, same as `DynamoDbFoodRepository::table_name` —
+    /// keeps a tenant's carts isolated from every other tenant's, including
+    /// one sharing the same `user_id`.
+    fn table_name(&self, tenant_id: Option<&str>) -> String {
+        resolve_table_name(&self.base_table_name, tenant_id, self.multi_tenant_tables_enabled)
+    }
+}
+
+#[async_trait::async_trait]
+impl CartRepository for DynamoDbCartRepository {
+    async fn get_cart(&self, user_id: &str, tenant_id: Option<&str>) -> ApiResult<Option<Cart>> {
+        let table_name = self.table_name(tenant_id);
+        let span = dynamodb_span("get_item", &table_name, &client_region(&self.client));
+        async {
+            let output = self
+                .client
+                .get_item()
+                .table_name(&table_name)
+                .key("user_id", AttributeValue::S(user_id.to_string()))
+                .send()
+                .await
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+            let Some(item) = output.item else {
+                return Ok(None);
+            };
+
+            if is_expired(&item) {
+                return Ok(None);
+            }
+
+            Ok(Some(item_to_cart(user_id, &item)?))
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn put_cart(&self, cart: &Cart, tenant_id: Option<&str>) -> ApiResult<()> {
+        let table_name = self.table_name(tenant_id);
+        let span = dynamodb_span("put_item", &table_name, &client_region(&self.client));
+        async {
+            let mut item = cart_to_item(cart)?;
+            item.insert(
+                "version".to_string(),
+                AttributeValue::N((cart.version + 1).to_string()),
+            );
+            item.insert(
+                "expires_at".to_string(),
+                AttributeValue::N(
+                    compute_expiry(Utc::now(), chrono::Duration::days(self.ttl_days), MIN_CART_TTL, MAX_CART_TTL)
+                        .timestamp()
+                        .to_string(),
+                ),
+            );
+
+            self.client
+                .put_item()
+                .table_name(&table_name)
+                .set_item(Some(item))
+                .condition_expression("attribute_not_exists(user_id) OR version = :expected_version")
+                .expression_attribute_values(":expected_version", AttributeValue::N(cart.version.to_string()))
+                .send()
+                .await
+                .map_err(|e| {
+                    if e.as_service_error().is_some_and(|e| e.is_conditional_check_failed_exception()) {
+                        ApiError::Conflict(format!("cart for user {} was modified concurrently", cart.user_id))
+                    } else {
+                        ApiError::Internal(e.to_string())
+                    }
+                })?;
+            Ok(())
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn list_carts(&self, tenant_id: Option<&str>) -> ApiResult<Vec<Cart>> {
+        let table_name = self.table_name(tenant_id);
+        let span = dynamodb_span("scan", &table_name, &client_region(&self.client));
+        async {
+            let mut carts = Vec::new();
+            let mut exclusive_start_key = None;
+
+            loop {
+                let output = self
+                    .client
+                    .scan()
+                    .table_name(&table_name)
+                    .set_exclusive_start_key(exclusive_start_key)
+                    .send()
+                    .await
+                    .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+                for item in output.items.unwrap_or_default() {
+                    let user_id = item
+                        .get("user_id")
+                        .and_then(|v| v.as_s().ok())
+                        .cloned()
+                        .ok_or_else(|| ApiError::Internal("missing attribute user_id".to_string()))?;
+                    carts.push(item_to_cart(&user_id, &item)?);
+                }
+
+                exclusive_start_key = output.last_evaluated_key;
+                if exclusive_start_key.is_none() {
+                    break;
+                }
+            }
+
+            Ok(carts)
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn delete_cart(&self, user_id: &str, tenant_id: Option<&str>) -> ApiResult<()> {
+        let table_name = self.table_name(tenant_id);
+        let span = dynamodb_span("delete_item", &table_name, &client_region(&self.client));
+        async {
+            self.client
+                .delete_item()
+                .table_name(&table_name)
+                .key("user_id", AttributeValue::S(user_id.to_string()))
+                .send()
+                .await
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+            Ok(())
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+/// Items with no `expires_at` attribute (written before TTL existed) are
+/// never treated as expired.
+fn is_expired(item: &HashMap<String, AttributeValue>) -> bool {
+    item.get("expires_at")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+        .is_some_and(|expires_at| expires_at < Utc::now().timestamp())
+}
+
+fn cart_to_item(cart: &Cart) -> ApiResult<HashMap<String, AttributeValue>> {
+    let items_json = serde_json::to_string(&cart.items)
+        .map_err(|e| ApiError::Internal(format!("failed to serialize cart items: {e}")))?;
+
+    let mut item: HashMap<String, AttributeValue> = HashMap::new();
+    item.insert("user_id".to_string(), AttributeValue::S(cart.user_id.clone()));
+    item.insert("items".to_string(), AttributeValue::S(items_json));
+    item.insert(
+        "updated_at".to_string(),
+        AttributeValue::S(cart.updated_at.to_rfc3339()),
+    );
+    if let Some(code) = &cart.applied_coupon {
+        item.insert("applied_coupon".to_string(), AttributeValue::S(code.clone()));
+    }
+    Ok(item)
+}
+
+/// Items written before optimistic locking existed won't carry a `version`
+/// attribute — treat those as version 0 so the first write after upgrading
+/// still succeeds its condition check.
+fn item_to_cart(user_id: &str, item: &HashMap<String, AttributeValue>) -> ApiResult<Cart> {
+    let items_json = item
+        .get("items")
+        .and_then(|v| v.as_s().ok())
+        .cloned()
+        .unwrap_or_else(|| "[]".to_string());
+    let items: Vec<CartItem> = serde_json::from_str(&items_json)
+        .map_err(|e| ApiError::Internal(format!("corrupt cart item list: {e}")))?;
+
+    let updated_at = item
+        .get("updated_at")
+        .and_then(|v| v.as_s().ok())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+
+    let version = item
+        .get("version")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0);
+
+    let applied_coupon = item.get("applied_coupon").and_then(|v| v.as_s().ok()).cloned();
+
+    Ok(Cart {
+        user_id: user_id.to_string(),
+        items,
+        updated_at,
+        version,
+        applied_coupon,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_expired_is_false_when_expires_at_is_absent() {
+        assert!(!is_expired(&HashMap::new()));
+    }
+
+    #[test]
+    fn is_expired_is_false_for_a_future_expires_at() {
+        let mut item = HashMap::new();
+        item.insert(
+            "expires_at".to_string(),
+            AttributeValue::N((Utc::now() + chrono::Duration::days(1)).timestamp().to_string()),
+        );
+        assert!(!is_expired(&item));
+    }
+
+    #[test]
+    fn is_expired_is_true_for_a_past_expires_at_even_though_the_item_still_exists() {
+        let mut item = HashMap::new();
+        item.insert(
+            "expires_at".to_string(),
+            AttributeValue::N((Utc::now() - chrono::Duration::days(1)).timestamp().to_string()),
+        );
+        assert!(is_expired(&item), "a cart past its TTL should be treated as expired even if DynamoDB hasn't deleted it yet");
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use chrono::{DateTime, Utc};
+use tracing::Instrument;
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{Discount, DiscountKind};
+use crate::repository::tracing::{client_region, dynamodb_span};
+
+#[async_trait::async_trait]
+pub trait DiscountRepository: Send + Sync {
+    async fn get_discount(&self, code: &str) -> ApiResult<Option<Discount>>;
+}
+
+pub struct DynamoDbDiscountRepository {
+    client: DynamoDbClient,
+    table_name: String,
+}
+
+impl DynamoDbDiscountRepository {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+}
+
+#[async_trait::async_trait]
+impl DiscountRepository for DynamoDbDiscountRepository {
+    async fn get_discount(&self, code: &str) -> ApiResult<Option<Discount>> {
+        let span = dynamodb_span("get_item", &self.table_name, &client_region(&self.client));
+        async {
+            let output = self
+                .client
+                .get_item()
+                .table_name(&self.table_name)
+                .key("code", AttributeValue::S(code.to_string()))
+                .send()
+                .await
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+            let Some(item) = output.item else {
+                return Ok(None);
+            };
+
+            Ok(Some(item_to_discount(code, &item)?))
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+fn item_to_discount(code: &str, item: &HashMap<String, AttributeValue>) -> ApiResult<Discount> {
+    let kind = match item.get("kind").and_then(|v| v.as_s().ok()).map(String::as_str) {
+        Some("percentage") => {
+            let percent = item
+                .get("percent")
+                .and_then(|v| v.as_n().ok())
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| ApiError::Internal(format!("discount {code} is missing attribute percent")))?;
+            DiscountKind::Percentage(percent)
+        }
+        Some("fixed_cents") => {
+            let cents = item
+                .get("amount_cents")
+                .and_then(|v| v.as_n().ok())
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| ApiError::Internal(format!("discount {code} is missing attribute amount_cents")))?;
+            DiscountKind::FixedCents(cents)
+        }
+        other => {
+            return Err(ApiError::Internal(format!(
+                "discount {code} has unrecognized kind {other:?}"
+            )))
+        }
+    };
+
+    let min_cart_total_cents = item
+        .get("min_cart_total_cents")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse().ok());
+
+    let expires_at = item
+        .get("expires_at")
+        .and_then(|v| v.as_s().ok())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    Ok(Discount {
+        code: code.to_string(),
+        kind,
+        min_cart_total_cents,
+        expires_at,
+    })
+}
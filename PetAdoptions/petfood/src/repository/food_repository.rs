@@ -0,0 +1,802 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use aws_sdk_dynamodb::types::{AttributeValue, KeysAndAttributes, PutRequest, ReturnConsumedCapacity, WriteRequest};
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use chrono::{DateTime, Utc};
+use tracing::Instrument;
+
+use crate::capacity_budget::CapacityBudget;
+use crate::config::resolve_table_name;
+use crate::error::{ApiError, ApiResult};
+use crate::metrics;
+use crate::models::{AvailabilityStatus, Food, FoodType, PetType};
+use crate::repository::tracing::{client_region, dynamodb_span};
+
+/// Caps how many changed rows a single `list_foods_updated_since` call
+/// returns, so an operator polling a wide time range can't trigger an
+/// unbounded scan response.
+const CHANGES_SINCE_LIMIT: usize = 500;
+
+/// DynamoDB's hard cap on items per `BatchWriteItem` call.
+pub const BATCH_WRITE_LIMIT: usize = 25;
+
+/// Bounds how many times `put_foods_batch` resubmits a batch's
+/// `UnprocessedItems` before giving up, so a persistently throttled table
+/// fails the request instead of retrying forever.
+const BATCH_WRITE_MAX_ATTEMPTS: usize = 5;
+
+/// DynamoDB's hard cap on items per `BatchGetItem` call.
+pub const BATCH_GET_LIMIT: usize = 100;
+
+/// Bounds how many times `find_by_ids` resubmits a batch's
+/// `UnprocessedKeys` before giving up.
+const BATCH_GET_MAX_ATTEMPTS: usize = 5;
+
+#[async_trait::async_trait]
+pub trait FoodRepository: Send + Sync {
+    async fn get_food(&self, food_id: &str, tenant_id: Option<&str>) -> ApiResult<Option<Food>>;
+    async fn list_foods(&self, tenant_id: Option<&str>) -> ApiResult<Vec<Food>>;
+
+    /// Writes `food`, conditioned on the stored version still matching
+    /// `food.version` (or the item not existing yet, for a brand-new
+    /// food). The stored version is bumped by one on a successful write.
+    /// Returns `ApiError::Conflict` if another writer updated the food
+    /// first — callers like `FoodService::update_price` that read-then-write
+    /// should re-read and re-apply their mutation rather than treat this
+    /// like any other failure.
+    async fn put_food(&self, food: &Food, tenant_id: Option<&str>) -> ApiResult<()>;
+    async fn delete_food(&self, food_id: &str, tenant_id: Option<&str>) -> ApiResult<()>;
+
+    /// How many foods currently exist, for the `PETFOOD_PREVENT_EMPTY_CATALOG`
+    /// backstop. Defaults to a full `list_foods` count for repositories
+    /// (e.g. the in-memory test double) with no cheaper way to get it;
+    /// nothing currently overrides this with a DynamoDB item-count estimate,
+    /// since that count is only refreshed every few hours and a deletion
+    /// guard needs an exact, current number.
+    async fn count_foods(&self, tenant_id: Option<&str>) -> ApiResult<usize> {
+        Ok(self.list_foods(tenant_id).await?.len())
+    }
+
+    /// Same as `get_food`, but forces a strongly consistent read against the
+    /// primary table instead of `get_food`'s eventually consistent,
+    /// failover-eligible read. For callers like `CartService::checkout_cart`
+    /// that re-validate stock immediately before committing an order, a
+    /// stale read could let an item that was just depleted slip through.
+    /// Defaults to `get_food` for repositories (e.g. the in-memory test
+    /// double) with no read-consistency distinction to make.
+    async fn get_food_consistent(&self, food_id: &str, tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+        self.get_food(food_id, tenant_id).await
+    }
+
+    /// Writes a single chunk of foods (at most [`BATCH_WRITE_LIMIT`]).
+    /// Defaults to one `put_food` per item for repositories (e.g. the
+    /// in-memory test double) with no batch API to speak of;
+    /// `DynamoDbFoodRepository` overrides this with a real
+    /// `BatchWriteItem` call. Called by `FoodService::create_foods_batch`
+    /// once per chunk, with multiple chunks in flight at a configurable
+    /// concurrency.
+    async fn put_foods_batch(&self, foods: &[Food], tenant_id: Option<&str>) -> ApiResult<()> {
+        for food in foods {
+            self.put_food(food, tenant_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns foods changed since `since`, for `GET
+    /// /api/admin/foods/changes`. Implemented here as a filtered
+    /// `list_foods`, since a true DynamoDB deployment would back this with a
+    /// GSI on `updated_at` instead of a full scan; overridden by
+    /// `DynamoDbFoodRepository` to apply the result cap and reuse the
+    /// regional read failover.
+    async fn list_foods_updated_since(
+        &self,
+        since: DateTime<Utc>,
+        tenant_id: Option<&str>,
+    ) -> ApiResult<Vec<Food>> {
+        let mut changed: Vec<Food> = self
+            .list_foods(tenant_id)
+            .await?
+            .into_iter()
+            .filter(|food| food.updated_at > since)
+            .collect();
+        changed.truncate(CHANGES_SINCE_LIMIT);
+        Ok(changed)
+    }
+
+    /// Looks up every id in `ids`, returning only the ones that exist —
+    /// missing ids are silently omitted rather than erroring, so a caller
+    /// like `CartService::cart_response` rendering a cart can skip a food
+    /// that was deleted out from under it instead of failing the whole
+    /// render. Defaults to one `get_food` per id for repositories (e.g. the
+    /// in-memory test double) with no batch read API to speak of;
+    /// `DynamoDbFoodRepository` overrides this with a real `BatchGetItem`
+    /// call, chunked into groups of [`BATCH_GET_LIMIT`].
+    async fn find_by_ids(&self, ids: &[String], tenant_id: Option<&str>) -> ApiResult<HashMap<String, Food>> {
+        let mut found = HashMap::new();
+        for id in ids {
+            if let Some(food) = self.get_food(id, tenant_id).await? {
+                found.insert(id.clone(), food);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Same as `list_foods`, but enforces `budget` (when given) against
+    /// DynamoDB's reported `ConsumedCapacity` for the scan, aborting with
+    /// `ApiError::BudgetExceeded` once this request's cumulative RCU spend
+    /// exceeds the budget's cap — a backstop against a single deeply
+    /// filtered `GET /api/foods` scan blowing through the table's capacity.
+    /// Defaults to an unmetered `list_foods` for repositories (e.g. the
+    /// in-memory test double) that don't report DynamoDB capacity.
+    async fn list_foods_within_budget(&self, tenant_id: Option<&str>, budget: Option<&CapacityBudget>) -> ApiResult<Vec<Food>> {
+        let _ = budget;
+        self.list_foods(tenant_id).await
+    }
+}
+
+pub struct DynamoDbFoodRepository {
+    /// Region clients tried in order for reads; index 0 is the primary and
+    /// is also the only client writes go to, so a dirty write never lands on
+    /// a replica.
+    read_clients: Vec<DynamoDbClient>,
+    base_table_name: String,
+    multi_tenant_tables_enabled: bool,
+}
+
+impl DynamoDbFoodRepository {
+    pub fn new(client: DynamoDbClient, base_table_name: String, multi_tenant_tables_enabled: bool) -> Self {
+        Self::with_replica_clients(client, Vec::new(), base_table_name, multi_tenant_tables_enabled)
+    }
+
+    /// `replica_clients` are secondary region clients for global-table
+    /// replicas, tried in order only after the primary (`client`) errors on
+    /// a read.
+    pub fn with_replica_clients(
+        client: DynamoDbClient,
+        replica_clients: Vec<DynamoDbClient>,
+        base_table_name: String,
+        multi_tenant_tables_enabled: bool,
+    ) -> Self {
+        let mut read_clients = vec![client];
+        read_clients.extend(replica_clients);
+        Self {
+            read_clients,
+            base_table_name,
+            multi_tenant_tables_enabled,
+        }
+    }
+
+    fn primary_client(&self) -> &DynamoDbClient {
+        &self.read_clients[0]
+    }
+
+    fn table_name(&self, tenant_id: Option<&str>) -> String {
+        resolve_table_name(&self.base_table_name, tenant_id, self.multi_tenant_tables_enabled)
+    }
+}
+
+/// Tries each attempt in order, returning the first success. An attempt
+/// succeeding after an earlier one failed is a failover onto a secondary
+/// region replica: it's logged and counted so operators can see it
+/// happening. Returns the last error if every attempt fails.
+async fn read_with_failover<T, F, Fut>(attempts: Vec<F>) -> ApiResult<T>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = ApiResult<T>>,
+{
+    let mut last_error = ApiError::Internal("no read clients configured".to_string());
+    for (index, attempt) in attempts.into_iter().enumerate() {
+        match attempt().await {
+            Ok(value) => {
+                if index > 0 {
+                    tracing::warn!(replica_index = index, "DynamoDB read failed over to a secondary region replica");
+                    metrics::observe_dynamodb_read_failover();
+                }
+                return Ok(value);
+            }
+            Err(err) => last_error = err,
+        }
+    }
+    Err(last_error)
+}
+
+fn food_to_item(food: &Food) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert("food_id".to_string(), AttributeValue::S(food.food_id.clone()));
+    item.insert("name".to_string(), AttributeValue::S(food.name.clone()));
+    item.insert(
+        "description".to_string(),
+        AttributeValue::S(food.description.clone()),
+    );
+    item.insert(
+        "ingredients".to_string(),
+        AttributeValue::Ss(food.ingredients.clone()),
+    );
+    item.insert(
+        "price_cents".to_string(),
+        AttributeValue::N(food.price_cents.to_string()),
+    );
+    item.insert(
+        "stock_quantity".to_string(),
+        AttributeValue::N(food.stock_quantity.to_string()),
+    );
+    item.insert(
+        "availability_status".to_string(),
+        AttributeValue::S(match food.availability_status {
+            AvailabilityStatus::InStock => "in_stock".to_string(),
+            AvailabilityStatus::OutOfStock => "out_of_stock".to_string(),
+        }),
+    );
+    item.insert(
+        "pet_type".to_string(),
+        AttributeValue::S(format!("{:?}", food.pet_type)),
+    );
+    item.insert(
+        "food_type".to_string(),
+        AttributeValue::S(format!("{:?}", food.food_type)),
+    );
+    item.insert(
+        "image_path".to_string(),
+        AttributeValue::S(food.image_path.clone()),
+    );
+    if !food.categories.is_empty() {
+        item.insert(
+            "categories".to_string(),
+            AttributeValue::Ss(food.categories.clone()),
+        );
+    }
+    if !food.prices.is_empty() {
+        item.insert(
+            "prices".to_string(),
+            AttributeValue::M(
+                food.prices
+                    .iter()
+                    .map(|(currency, cents)| (currency.clone(), AttributeValue::N(cents.to_string())))
+                    .collect(),
+            ),
+        );
+    }
+    item.insert(
+        "updated_at".to_string(),
+        AttributeValue::S(food.updated_at.to_rfc3339()),
+    );
+    item
+}
+
+fn item_to_food(item: &HashMap<String, AttributeValue>) -> ApiResult<Food> {
+    let get_s = |key: &str| -> ApiResult<String> {
+        item.get(key)
+            .and_then(|v| v.as_s().ok())
+            .map(|s| s.to_string())
+            .ok_or_else(|| ApiError::Internal(format!("missing attribute {key}")))
+    };
+    let get_n = |key: &str| -> ApiResult<i64> {
+        item.get(key)
+            .and_then(|v| v.as_n().ok())
+            .and_then(|s| s.parse::<i64>().ok())
+            .ok_or_else(|| ApiError::Internal(format!("missing attribute {key}")))
+    };
+
+    let pet_type = match get_s("pet_type")?.as_str() {
+        "Dog" => PetType::Dog,
+        "Cat" => PetType::Cat,
+        "Bird" => PetType::Bird,
+        "Fish" => PetType::Fish,
+        _ => PetType::Other,
+    };
+    let food_type = match get_s("food_type")?.as_str() {
+        "Wet" => FoodType::Wet,
+        "Treat" => FoodType::Treat,
+        "Supplement" => FoodType::Supplement,
+        _ => FoodType::Dry,
+    };
+    let availability_status = match get_s("availability_status")?.as_str() {
+        "out_of_stock" => AvailabilityStatus::OutOfStock,
+        _ => AvailabilityStatus::InStock,
+    };
+
+    Ok(Food {
+        food_id: get_s("food_id")?,
+        name: get_s("name")?,
+        description: get_s("description")?,
+        ingredients: item
+            .get("ingredients")
+            .and_then(|v| v.as_ss().ok())
+            .cloned()
+            .unwrap_or_default(),
+        price_cents: get_n("price_cents")?,
+        stock_quantity: get_n("stock_quantity")? as u32,
+        availability_status,
+        pet_type,
+        food_type,
+        image_path: get_s("image_path")?,
+        categories: item
+            .get("categories")
+            .and_then(|v| v.as_ss().ok())
+            .cloned()
+            .unwrap_or_default(),
+        prices: item
+            .get("prices")
+            .and_then(|v| v.as_m().ok())
+            .map(|m| {
+                m.iter()
+                    .filter_map(|(currency, value)| {
+                        let cents = value.as_n().ok()?.parse().ok()?;
+                        Some((currency.clone(), cents))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        updated_at: item
+            .get("updated_at")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now),
+        // Items written before optimistic locking existed won't carry a
+        // `version` attribute — treat those as version 0 so the first
+        // write after upgrading still succeeds its condition check.
+        version: item
+            .get("version")
+            .and_then(|v| v.as_n().ok())
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0),
+    })
+}
+
+#[async_trait::async_trait]
+impl FoodRepository for DynamoDbFoodRepository {
+    async fn get_food(&self, food_id: &str, tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+        let table_name = self.table_name(tenant_id);
+        let attempts = self
+            .read_clients
+            .iter()
+            .map(|client| {
+                let table_name = table_name.clone();
+                let span = dynamodb_span("get_item", &table_name, &client_region(client));
+                move || {
+                    async move {
+                        let output = client
+                            .get_item()
+                            .table_name(table_name)
+                            .key("food_id", AttributeValue::S(food_id.to_string()))
+                            .send()
+                            .await
+                            .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+                        output.item.as_ref().map(item_to_food).transpose()
+                    }
+                    .instrument(span)
+                }
+            })
+            .collect();
+
+        read_with_failover(attempts).await
+    }
+
+    /// Reads only the primary client, with `ConsistentRead` set, and skips
+    /// replica failover entirely — a global table's replicas are eventually
+    /// consistent by construction, so falling back to one here would defeat
+    /// the point of asking for a consistent read in the first place.
+    async fn get_food_consistent(&self, food_id: &str, tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+        let table_name = self.table_name(tenant_id);
+        let span = dynamodb_span("get_item", &table_name, &client_region(self.primary_client()));
+        async {
+            let output = self
+                .primary_client()
+                .get_item()
+                .table_name(table_name)
+                .key("food_id", AttributeValue::S(food_id.to_string()))
+                .consistent_read(true)
+                .send()
+                .await
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+            output.item.as_ref().map(item_to_food).transpose()
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn list_foods(&self, tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+        let table_name = self.table_name(tenant_id);
+        let attempts = self
+            .read_clients
+            .iter()
+            .map(|client| {
+                let table_name = table_name.clone();
+                let span = dynamodb_span("scan", &table_name, &client_region(client));
+                move || {
+                    async move {
+                        let output = client
+                            .scan()
+                            .table_name(table_name)
+                            .send()
+                            .await
+                            .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+                        output
+                            .items
+                            .unwrap_or_default()
+                            .iter()
+                            .map(item_to_food)
+                            .collect()
+                    }
+                    .instrument(span)
+                }
+            })
+            .collect();
+
+        read_with_failover(attempts).await
+    }
+
+    /// Reads only the primary client, same rationale as
+    /// `get_food_consistent`: failing over to a replica mid-budget would
+    /// make the cumulative RCU total meaningless, since each replica starts
+    /// its own count from zero.
+    async fn list_foods_within_budget(&self, tenant_id: Option<&str>, budget: Option<&CapacityBudget>) -> ApiResult<Vec<Food>> {
+        let table_name = self.table_name(tenant_id);
+        let span = dynamodb_span("scan", &table_name, &client_region(self.primary_client()));
+        async {
+            let output = self
+                .primary_client()
+                .scan()
+                .table_name(table_name)
+                .return_consumed_capacity(ReturnConsumedCapacity::Total)
+                .send()
+                .await
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+            if let Some(budget) = budget {
+                let units = output.consumed_capacity().and_then(|c| c.capacity_units()).unwrap_or(0.0);
+                budget.record(units)?;
+            }
+
+            output.items.unwrap_or_default().iter().map(item_to_food).collect()
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn put_food(&self, food: &Food, tenant_id: Option<&str>) -> ApiResult<()> {
+        let table_name = self.table_name(tenant_id);
+        let span = dynamodb_span("put_item", &table_name, &client_region(self.primary_client()));
+        async {
+            let mut item = food_to_item(food);
+            item.insert("version".to_string(), AttributeValue::N((food.version + 1).to_string()));
+
+            self.primary_client()
+                .put_item()
+                .table_name(table_name)
+                .set_item(Some(item))
+                .condition_expression("attribute_not_exists(food_id) OR version = :expected_version")
+                .expression_attribute_values(":expected_version", AttributeValue::N(food.version.to_string()))
+                .send()
+                .await
+                .map_err(|e| {
+                    if e.as_service_error().is_some_and(|e| e.is_conditional_check_failed_exception()) {
+                        ApiError::Conflict(format!("food {} was modified concurrently", food.food_id))
+                    } else {
+                        ApiError::Internal(e.to_string())
+                    }
+                })?;
+            Ok(())
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn delete_food(&self, food_id: &str, tenant_id: Option<&str>) -> ApiResult<()> {
+        let table_name = self.table_name(tenant_id);
+        let span = dynamodb_span("delete_item", &table_name, &client_region(self.primary_client()));
+        async {
+            self.primary_client()
+                .delete_item()
+                .table_name(table_name)
+                .key("food_id", AttributeValue::S(food_id.to_string()))
+                .send()
+                .await
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+            Ok(())
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// `foods` must fit within [`BATCH_WRITE_LIMIT`] — chunking is the
+    /// caller's responsibility (`FoodService::create_foods_batch`).
+    /// Resubmits `UnprocessedItems` up to `BATCH_WRITE_MAX_ATTEMPTS` times,
+    /// since a `BatchWriteItem` response doesn't fail the whole call for
+    /// items DynamoDB throttled — it just leaves them unprocessed.
+    async fn put_foods_batch(&self, foods: &[Food], tenant_id: Option<&str>) -> ApiResult<()> {
+        let table_name = self.table_name(tenant_id);
+        let mut requests: Vec<WriteRequest> = foods
+            .iter()
+            .map(|food| {
+                let put_request = PutRequest::builder()
+                    .set_item(Some(food_to_item(food)))
+                    .build()
+                    .expect("item is always set");
+                WriteRequest::builder().put_request(put_request).build()
+            })
+            .collect();
+
+        for attempt in 0..BATCH_WRITE_MAX_ATTEMPTS {
+            if requests.is_empty() {
+                return Ok(());
+            }
+
+            let span = dynamodb_span("batch_write_item", &table_name, &client_region(self.primary_client()));
+            let output = self
+                .primary_client()
+                .batch_write_item()
+                .request_items(table_name.clone(), requests.clone())
+                .send()
+                .instrument(span)
+                .await
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+            requests = output
+                .unprocessed_items
+                .unwrap_or_default()
+                .remove(&table_name)
+                .unwrap_or_default();
+
+            if !requests.is_empty() && attempt + 1 < BATCH_WRITE_MAX_ATTEMPTS {
+                tracing::warn!(
+                    unprocessed = requests.len(),
+                    attempt = attempt + 1,
+                    "BatchWriteItem left unprocessed items, retrying"
+                );
+            }
+        }
+
+        if requests.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::Internal(format!(
+                "BatchWriteItem left {} unprocessed items after {BATCH_WRITE_MAX_ATTEMPTS} attempts",
+                requests.len()
+            )))
+        }
+    }
+
+    /// Chunks `ids` into groups of [`BATCH_GET_LIMIT`] and issues one
+    /// `BatchGetItem` per chunk against the primary client, resubmitting
+    /// `UnprocessedKeys` up to [`BATCH_GET_MAX_ATTEMPTS`] times with a short
+    /// backoff between attempts — a `BatchGetItem` response doesn't fail
+    /// the whole call for keys DynamoDB throttled, it just leaves them
+    /// unprocessed.
+    async fn find_by_ids(&self, ids: &[String], tenant_id: Option<&str>) -> ApiResult<HashMap<String, Food>> {
+        let table_name = self.table_name(tenant_id);
+        let mut found = HashMap::new();
+
+        for chunk in ids.chunks(BATCH_GET_LIMIT) {
+            let mut keys: Vec<HashMap<String, AttributeValue>> = chunk
+                .iter()
+                .map(|id| HashMap::from([("food_id".to_string(), AttributeValue::S(id.clone()))]))
+                .collect();
+
+            for attempt in 0..BATCH_GET_MAX_ATTEMPTS {
+                if keys.is_empty() {
+                    break;
+                }
+
+                let request_items = KeysAndAttributes::builder().set_keys(Some(keys.clone())).build().map_err(|e| ApiError::Internal(e.to_string()))?;
+                let span = dynamodb_span("batch_get_item", &table_name, &client_region(self.primary_client()));
+                let output = self
+                    .primary_client()
+                    .batch_get_item()
+                    .request_items(table_name.clone(), request_items)
+                    .send()
+                    .instrument(span)
+                    .await
+                    .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+                for item in output.responses.unwrap_or_default().remove(&table_name).unwrap_or_default() {
+                    let food = item_to_food(&item)?;
+                    found.insert(food.food_id.clone(), food);
+                }
+
+                keys = output
+                    .unprocessed_keys
+                    .unwrap_or_default()
+                    .remove(&table_name)
+                    .map(|k| k.keys)
+                    .unwrap_or_default();
+
+                if !keys.is_empty() && attempt + 1 < BATCH_GET_MAX_ATTEMPTS {
+                    tracing::warn!(unprocessed = keys.len(), attempt = attempt + 1, "BatchGetItem left unprocessed keys, retrying");
+                    tokio::time::sleep(std::time::Duration::from_millis(50 * (attempt as u64 + 1))).await;
+                }
+            }
+
+            if !keys.is_empty() {
+                return Err(ApiError::Internal(format!(
+                    "BatchGetItem left {} unprocessed keys after {BATCH_GET_MAX_ATTEMPTS} attempts",
+                    keys.len()
+                )));
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Filters server-side with a `FilterExpression` rather than the default
+    /// method's client-side filtering, so unchanged rows don't cross the
+    /// wire; still a scan under the hood until this table has a GSI on
+    /// `updated_at`.
+    async fn list_foods_updated_since(
+        &self,
+        since: DateTime<Utc>,
+        tenant_id: Option<&str>,
+    ) -> ApiResult<Vec<Food>> {
+        let table_name = self.table_name(tenant_id);
+        let since = since.to_rfc3339();
+        let attempts = self
+            .read_clients
+            .iter()
+            .map(|client| {
+                let table_name = table_name.clone();
+                let since = since.clone();
+                let span = dynamodb_span("scan", &table_name, &client_region(client));
+                move || {
+                    async move {
+                        let output = client
+                            .scan()
+                            .table_name(table_name)
+                            .filter_expression("updated_at > :since")
+                            .expression_attribute_values(":since", AttributeValue::S(since))
+                            .send()
+                            .await
+                            .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+                        let mut changed = output
+                            .items
+                            .unwrap_or_default()
+                            .iter()
+                            .map(item_to_food)
+                            .collect::<ApiResult<Vec<Food>>>()?;
+                        changed.truncate(CHANGES_SINCE_LIMIT);
+                        Ok(changed)
+                    }
+                    .instrument(span)
+                }
+            })
+            .collect();
+
+        read_with_failover(attempts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+
+    use super::*;
+
+    type BoxedAttempt = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ApiResult<i32>>>>>;
+
+    #[tokio::test]
+    async fn returns_the_primary_result_when_it_succeeds() {
+        let attempts: Vec<BoxedAttempt> = vec![
+            Box::new(|| Box::pin(async { Ok(1) })),
+            Box::new(|| Box::pin(async { panic!("secondary should not be tried when the primary succeeds") })),
+        ];
+
+        assert_eq!(read_with_failover(attempts).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn falls_through_to_the_secondary_when_the_primary_errors() {
+        let attempts: Vec<BoxedAttempt> = vec![
+            Box::new(|| Box::pin(async { Err(ApiError::Internal("primary region unavailable".to_string())) })),
+            Box::new(|| Box::pin(async { Ok(2) })),
+        ];
+
+        assert_eq!(read_with_failover(attempts).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn returns_the_last_error_when_every_replica_fails() {
+        let attempts: Vec<BoxedAttempt> = vec![
+            Box::new(|| Box::pin(async { Err(ApiError::Internal("primary down".to_string())) })),
+            Box::new(|| Box::pin(async { Err(ApiError::Internal("secondary down".to_string())) })),
+        ];
+
+        let result = read_with_failover(attempts).await;
+
+        assert!(matches!(result, Err(ApiError::Internal(ref msg)) if msg == "secondary down"));
+    }
+
+    fn sample_food(categories: Vec<String>) -> Food {
+        Food {
+            food_id: "dog-food".to_string(),
+            name: "Dog Food".to_string(),
+            description: "Crunchy kibble".to_string(),
+            ingredients: vec!["chicken".to_string()],
+            price_cents: 1299,
+            stock_quantity: 10,
+            availability_status: AvailabilityStatus::InStock,
+            pet_type: PetType::Dog,
+            food_type: FoodType::Dry,
+            image_path: "/images/dog-food.png".to_string(),
+            categories,
+            prices: HashMap::new(),
+            updated_at: chrono::Utc::now(),
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn food_to_item_and_item_to_food_round_trip_categories() {
+        let food = sample_food(vec!["grain-free".to_string(), "senior".to_string()]);
+
+        let round_tripped = item_to_food(&food_to_item(&food)).unwrap();
+
+        assert_eq!(round_tripped.categories, vec!["grain-free".to_string(), "senior".to_string()]);
+    }
+
+    #[test]
+    fn item_to_food_defaults_categories_to_empty_when_absent() {
+        let food = sample_food(Vec::new());
+
+        let round_tripped = item_to_food(&food_to_item(&food)).unwrap();
+
+        assert!(round_tripped.categories.is_empty());
+    }
+
+    #[test]
+    fn food_to_item_and_item_to_food_round_trip_prices() {
+        let mut food = sample_food(Vec::new());
+        food.prices = HashMap::from([("EUR".to_string(), 1199), ("GBP".to_string(), 999)]);
+
+        let round_tripped = item_to_food(&food_to_item(&food)).unwrap();
+
+        assert_eq!(round_tripped.prices, food.prices);
+    }
+
+    #[test]
+    fn item_to_food_defaults_prices_to_empty_when_absent() {
+        let food = sample_food(Vec::new());
+
+        let round_tripped = item_to_food(&food_to_item(&food)).unwrap();
+
+        assert!(round_tripped.prices.is_empty());
+    }
+
+    struct GetOnlyFoodRepository(HashMap<String, Food>);
+
+    #[async_trait::async_trait]
+    impl FoodRepository for GetOnlyFoodRepository {
+        async fn get_food(&self, food_id: &str, _tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            Ok(self.0.get(food_id).cloned())
+        }
+
+        async fn list_foods(&self, _tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            Ok(self.0.values().cloned().collect())
+        }
+
+        async fn put_food(&self, _food: &Food, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+
+        async fn delete_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn default_find_by_ids_returns_only_the_ids_that_exist() {
+        let repository = GetOnlyFoodRepository(HashMap::from([("dog-food".to_string(), sample_food(Vec::new()))]));
+
+        let found = repository
+            .find_by_ids(&["dog-food".to_string(), "missing-food".to_string()], None)
+            .await
+            .unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert!(found.contains_key("dog-food"));
+    }
+}
@@ -0,0 +1,12 @@
+mod audit_repository;
+mod cart_repository;
+mod discount_repository;
+mod food_repository;
+mod order_repository;
+pub(crate) mod tracing;
+
+pub use audit_repository::{AuditRepository, DynamoDbAuditRepository};
+pub use cart_repository::{CartRepository, DynamoDbCartRepository};
+pub use discount_repository::{DiscountRepository, DynamoDbDiscountRepository};
+pub use food_repository::{DynamoDbFoodRepository, FoodRepository, BATCH_WRITE_LIMIT};
+pub use order_repository::{DynamoDbOrderRepository, OrderRepository};
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::Order;
+
+#[async_trait::async_trait]
+pub trait OrderRepository: Send + Sync {
+    async fn put_order(&self, order: &Order) -> ApiResult<()>;
+}
+
+pub struct DynamoDbOrderRepository {
+    client: DynamoDbClient,
+    table_name: String,
+}
+
+impl DynamoDbOrderRepository {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+}
+
+#[async_trait::async_trait]
+impl OrderRepository for DynamoDbOrderRepository {
+    async fn put_order(&self, order: &Order) -> ApiResult<()> {
+        let items_json: String = serde_json::to_string(&order.items)
+            .map_err(|e| ApiError::Internal(format!("failed to serialize order items: {e}")))?;
+
+        let mut item: HashMap<String, AttributeValue> = HashMap::new();
+        item.insert("order_id".to_string(), AttributeValue::S(order.order_id.clone()));
+        item.insert("user_id".to_string(), AttributeValue::S(order.user_id.clone()));
+        item.insert("items".to_string(), AttributeValue::S(items_json));
+        item.insert(
+            "subtotal_cents".to_string(),
+            AttributeValue::N(order.subtotal_cents.to_string()),
+        );
+        item.insert(
+            "discount_cents".to_string(),
+            AttributeValue::N(order.discount_cents.to_string()),
+        );
+        item.insert(
+            "total_cents".to_string(),
+            AttributeValue::N(order.total_cents.to_string()),
+        );
+        if let Some(code) = &order.applied_coupon {
+            item.insert("applied_coupon".to_string(), AttributeValue::S(code.clone()));
+        }
+
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+        Ok(())
+    }
+}
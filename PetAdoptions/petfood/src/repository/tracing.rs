@@ -0,0 +1,59 @@
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+
+/// The region a client was configured with, or `"unknown"` if none was set
+/// (e.g. a client built without an explicit region in a test).
+pub fn client_region(client: &DynamoDbClient) -> String {
+    client
+        .config()
+        .region()
+        .map(|region| region.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Builds the span every repository method enters for the single DynamoDB
+/// call it makes, carrying the attributes an X-Ray DynamoDB subsegment
+/// expects (service, operation, table, region, db.system) so each call
+/// shows up as a first-class node in the service map rather than being
+/// folded into its caller's span. Shared by both `DynamoDbFoodRepository`
+/// and `DynamoDbCartRepository` so the attribute set can't drift between
+/// the two.
+pub fn dynamodb_span(operation: &'static str, table_name: &str, region: &str) -> tracing::Span {
+    tracing::info_span!(
+        "dynamodb_operation",
+        service = "dynamodb",
+        operation,
+        table = %table_name,
+        region = %region,
+        "db.system" = "dynamodb",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_names(span: &tracing::Span) -> Vec<&'static str> {
+        span.metadata().unwrap().fields().iter().map(|f| f.name()).collect()
+    }
+
+    #[test]
+    fn dynamodb_span_carries_the_expected_attributes() {
+        let span = dynamodb_span("get_item", "carts", "us-west-2");
+
+        assert_eq!(span.metadata().unwrap().name(), "dynamodb_operation");
+        let fields = field_names(&span);
+        assert!(fields.contains(&"service"));
+        assert!(fields.contains(&"operation"));
+        assert!(fields.contains(&"table"));
+        assert!(fields.contains(&"region"));
+        assert!(fields.contains(&"db.system"));
+    }
+
+    #[test]
+    fn dynamodb_span_has_identical_attribute_sets_for_equivalent_operations_on_either_table() {
+        let food_span = dynamodb_span("get_item", "foods", "us-east-1");
+        let cart_span = dynamodb_span("get_item", "carts", "us-east-1");
+
+        assert_eq!(field_names(&food_span), field_names(&cart_span));
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use crate::error::ApiResult;
+use crate::models::AuditEntry;
+use crate::repository::AuditRepository;
+
+/// Records food field changes for `GET /api/admin/foods/:food_id/history`.
+/// Write failures are logged rather than propagated, the same stance
+/// `EventEmitter` takes: losing an audit row should never fail the request
+/// that triggered the underlying change.
+pub struct AuditLogger {
+    repository: Arc<dyn AuditRepository>,
+}
+
+impl AuditLogger {
+    pub fn new(repository: Arc<dyn AuditRepository>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn record_price_change(
+        &self,
+        food_id: &str,
+        old_price_cents: i64,
+        new_price_cents: i64,
+        correlation_id: Option<String>,
+    ) {
+        let entry = AuditEntry::price_change(food_id.to_string(), old_price_cents, new_price_cents, correlation_id);
+        if let Err(err) = self.repository.put_entry(&entry).await {
+            tracing::error!(error = %err, food_id, "failed to record audit entry");
+        }
+    }
+
+    /// Newest-first edit history for a food. An unknown `food_id` simply has
+    /// no entries, so this returns an empty list rather than `NotFound`.
+    pub async fn history_for(&self, food_id: &str) -> ApiResult<Vec<AuditEntry>> {
+        self.repository.history_for(food_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct InMemoryAuditRepository(Mutex<Vec<AuditEntry>>);
+
+    #[async_trait::async_trait]
+    impl AuditRepository for InMemoryAuditRepository {
+        async fn put_entry(&self, entry: &AuditEntry) -> ApiResult<()> {
+            self.0.lock().unwrap().push(entry.clone());
+            Ok(())
+        }
+
+        async fn history_for(&self, food_id: &str) -> ApiResult<Vec<AuditEntry>> {
+            let mut entries: Vec<AuditEntry> = self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|entry| entry.food_id == food_id)
+                .cloned()
+                .collect();
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.changed_at));
+            Ok(entries)
+        }
+    }
+
+    #[tokio::test]
+    async fn history_for_returns_multiple_edits_newest_first() {
+        let logger = AuditLogger::new(Arc::new(InMemoryAuditRepository::default()));
+
+        logger.record_price_change("food-1", 1000, 900, None).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        logger.record_price_change("food-1", 900, 800, None).await;
+
+        let history = logger.history_for("food-1").await.unwrap();
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].new_value, "800");
+        assert_eq!(history[1].new_value, "900");
+    }
+
+    #[tokio::test]
+    async fn history_for_an_unknown_food_is_empty() {
+        let logger = AuditLogger::new(Arc::new(InMemoryAuditRepository::default()));
+
+        let history = logger.history_for("missing").await.unwrap();
+
+        assert!(history.is_empty());
+    }
+}
@@ -0,0 +1,1303 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt};
+use uuid::Uuid;
+
+use crate::capacity_budget::CapacityBudget;
+use crate::error::{ApiError, ApiResult};
+use crate::events::{EventEmitter, FoodEvent};
+use crate::models::{
+    seed_quality_errors, AuditEntry, AvailabilityStatus, CreateFoodRequest, CreationSource, Food, FoodSearchResult, IngredientCount,
+    SeedRejection,
+};
+use crate::repository::{FoodRepository, BATCH_WRITE_LIMIT};
+use crate::service::AuditLogger;
+
+/// How many times `update_price`/`adjust_stock` re-read and retry a write
+/// after a concurrent modification conflicts with their optimistic lock,
+/// before surfacing the conflict to the caller. A small bound is enough to
+/// ride out a race with another writer rather than fail the request
+/// outright.
+const MAX_FOOD_SAVE_RETRIES: usize = 3;
+
+/// How `FoodService::adjust_stock` should change `stock_quantity`: a
+/// relative change (`Delta`, which may be negative) or an absolute value
+/// (`Set`).
+#[derive(Debug, Clone, Copy)]
+pub enum StockAdjustment {
+    Delta(i32),
+    Set(u32),
+}
+
+/// `FoodService::create_foods_batch`'s outcome: how many records were
+/// written, and every record the content-quality gate rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchSeedResult {
+    pub created: usize,
+    pub rejected: Vec<SeedRejection>,
+}
+
+/// `FoodService::bulk_create_foods`'s outcome: every record that passed
+/// validation and was written, and every one that failed validation along
+/// with why. Unlike `BatchSeedResult`, `created` holds the written `Food`
+/// records themselves (not just a count), since a direct admin write needs
+/// the generated `food_id`s back.
+#[derive(Debug, Clone)]
+pub struct BulkCreateResult {
+    pub created: Vec<Food>,
+    pub failed: Vec<SeedRejection>,
+}
+
+pub struct FoodService {
+    repository: Arc<dyn FoodRepository>,
+    event_emitter: Arc<EventEmitter>,
+    audit_logger: Arc<AuditLogger>,
+    /// `PETFOOD_MISSING_IMAGE_EMIT_WINDOW_MS`: when `Some`, `get_food` and
+    /// `list_foods_within_budget` fire `FoodEvent::missing_image_viewed` for
+    /// an image-less food at most once per food within this window, so
+    /// repeated reads of the same image-less food don't flood the event
+    /// pipeline with duplicates. `None` disables the check.
+    missing_image_emit_window: Option<Duration>,
+    recent_missing_image_emits: Mutex<HashMap<String, Instant>>,
+    /// `PETFOOD_ANALYTICS_EVENTS`: when set, `get_food` fires
+    /// `FoodEvent::food_viewed` on a successful read, fire-and-forget, for a
+    /// funnel-analytics demo. `None` disables the feature entirely.
+    analytics_emitter: Option<Arc<EventEmitter>>,
+}
+
+impl FoodService {
+    pub fn new(
+        repository: Arc<dyn FoodRepository>,
+        event_emitter: Arc<EventEmitter>,
+        audit_logger: Arc<AuditLogger>,
+    ) -> Self {
+        Self {
+            repository,
+            event_emitter,
+            audit_logger,
+            missing_image_emit_window: None,
+            recent_missing_image_emits: Mutex::new(HashMap::new()),
+            analytics_emitter: None,
+        }
+    }
+
+    /// Sets `PETFOOD_MISSING_IMAGE_EMIT_WINDOW_MS` — see
+    /// `missing_image_emit_window`.
+    pub fn with_missing_image_emit_window(mut self, missing_image_emit_window: Option<Duration>) -> Self {
+        self.missing_image_emit_window = missing_image_emit_window;
+        self
+    }
+
+    /// Sets `PETFOOD_ANALYTICS_EVENTS` — see `analytics_emitter`.
+    pub fn with_analytics_emitter(mut self, analytics_emitter: Option<Arc<EventEmitter>>) -> Self {
+        self.analytics_emitter = analytics_emitter;
+        self
+    }
+
+    pub async fn get_food(
+        &self,
+        food_id: &str,
+        tenant_id: Option<&str>,
+        correlation_id: Option<String>,
+    ) -> ApiResult<Option<Food>> {
+        let food = self.repository.get_food(food_id, tenant_id).await?;
+        if let Some(food) = &food {
+            self.emit_missing_image_event_if_due(food).await;
+            self.emit_food_viewed_analytics_event(food, correlation_id).await;
+        }
+        Ok(food)
+    }
+
+    /// Fires `FoodEvent::food_viewed` to `analytics_emitter` when configured
+    /// — a no-op when analytics events are disabled. Errors are logged, not
+    /// propagated, matching `emit_missing_image_event_if_due`: losing an
+    /// analytics event should never fail the read that triggered it.
+    async fn emit_food_viewed_analytics_event(&self, food: &Food, correlation_id: Option<String>) {
+        let Some(emitter) = &self.analytics_emitter else {
+            return;
+        };
+        let event = FoodEvent::food_viewed(food.food_id.clone(), correlation_id);
+        if let Err(err) = emitter.emit_event(&event).await {
+            tracing::error!(error = %err, food_id = %food.food_id, "failed to emit food viewed analytics event");
+        }
+    }
+
+    /// Looks up every id in `ids` in as few repository round trips as
+    /// `FoodRepository::find_by_ids` allows, returning only the ones that
+    /// exist. Deliberately skips the missing-image event `get_food` emits —
+    /// a batch lookup of many foods at once shouldn't be able to flood the
+    /// event pipeline the way a burst of individual `get_food` calls could.
+    pub async fn get_foods_batch(&self, ids: &[String], tenant_id: Option<&str>) -> ApiResult<HashMap<String, Food>> {
+        self.repository.find_by_ids(ids, tenant_id).await
+    }
+
+    /// Lists the catalog, aborting with `ApiError::BudgetExceeded` once
+    /// `budget` (when given) reports this request's cumulative RCU spend
+    /// over its cap — see `FoodRepository::list_foods_within_budget`.
+    pub async fn list_foods_within_budget(
+        &self,
+        tenant_id: Option<&str>,
+        budget: Option<&CapacityBudget>,
+    ) -> ApiResult<Vec<Food>> {
+        let foods = self.repository.list_foods_within_budget(tenant_id, budget).await?;
+        for food in &foods {
+            crate::metrics::observe_food_listed(&food.pet_type.to_string(), &food.food_type.to_string());
+            self.emit_missing_image_event_if_due(food).await;
+        }
+        Ok(foods)
+    }
+
+    /// Fires `FoodEvent::missing_image_viewed` for `food` if it has no
+    /// `image_path` and hasn't already fired one within
+    /// `missing_image_emit_window`. A no-op when the throttle is disabled or
+    /// `food` has an image.
+    async fn emit_missing_image_event_if_due(&self, food: &Food) {
+        let Some(window) = self.missing_image_emit_window else {
+            return;
+        };
+        if !food.image_path.is_empty() {
+            return;
+        }
+
+        {
+            let mut recent_emits = self.recent_missing_image_emits.lock().unwrap();
+            if let Some(emitted_at) = recent_emits.get(&food.food_id) {
+                if emitted_at.elapsed() < window {
+                    return;
+                }
+            }
+            recent_emits.insert(food.food_id.clone(), Instant::now());
+        }
+
+        crate::metrics::observe_missing_image_viewed();
+        if let Err(err) = self
+            .event_emitter
+            .emit_event(&FoodEvent::missing_image_viewed(food.food_id.clone(), None))
+            .await
+        {
+            tracing::error!(error = %err, food_id = %food.food_id, "failed to emit missing image viewed event");
+        }
+    }
+
+    /// Distinct ingredients across the catalog, ranked by how many foods
+    /// list each one and capped at `limit` entries, for `GET
+    /// /api/foods/ingredients` — the filter UI only needs the most common
+    /// ones, not an unbounded scan result.
+    pub async fn list_ingredients(&self, tenant_id: Option<&str>, limit: usize) -> ApiResult<Vec<IngredientCount>> {
+        let foods = self.repository.list_foods(tenant_id).await?;
+        Ok(crate::models::top_ingredients(&foods, Some(limit)))
+    }
+
+    /// Case-insensitive relevance search across the catalog — see
+    /// `models::search_foods_ranked` for how name/ingredient/description
+    /// matches are scored. Candidates still come from the repository's full
+    /// scan (the same one `contains()` filtering relied on); only the
+    /// ranking itself happens in-service.
+    pub async fn search_foods_ranked(&self, tenant_id: Option<&str>, term: &str) -> ApiResult<Vec<FoodSearchResult>> {
+        let foods = self.repository.list_foods(tenant_id).await?;
+        Ok(crate::models::search_foods_ranked(&foods, term))
+    }
+
+    /// `source` and `trust_seed` together decide whether field validation
+    /// runs: it's skipped only for `CreationSource::Seeding` when
+    /// `trust_seed` (`PETFOOD_TRUST_SEED`) is set, relying on the built-in
+    /// seed data's correctness instead of re-checking it on every seed.
+    /// `CreationSource::Api` always validates, regardless of `trust_seed`.
+    pub async fn create_food(
+        &self,
+        req: CreateFoodRequest,
+        tenant_id: Option<&str>,
+        allowed_image_domains: &[String],
+        source: CreationSource,
+        trust_seed: bool,
+    ) -> ApiResult<Food> {
+        let skip_validation = source == CreationSource::Seeding && trust_seed;
+        if !skip_validation {
+            let errors = req.validate(allowed_image_domains);
+            if !errors.is_empty() {
+                return Err(ApiError::Validation(errors.join("; ")));
+            }
+        }
+        let food = Food::from_create_request(Uuid::new_v4().to_string(), Utc::now(), req);
+        self.repository.put_food(&food, tenant_id).await?;
+        Ok(food)
+    }
+
+    /// Removes `food_id` from the catalog. When `prevent_empty_catalog` is
+    /// set, refuses to remove the last remaining food rather than leaving
+    /// an empty storefront — a demo-environment safety net, not a
+    /// correctness requirement, so it's opt-in.
+    ///
+    /// When `catalog_size_alert_drop_threshold_percent` is set and this one
+    /// deletion drops the active food count by more than that percentage,
+    /// fires `FoodEvent::catalog_size_alert` so ops can catch an accidental
+    /// mass-deletion before the storefront is empty.
+    pub async fn delete_food(
+        &self,
+        food_id: &str,
+        tenant_id: Option<&str>,
+        prevent_empty_catalog: bool,
+        catalog_size_alert_drop_threshold_percent: Option<f64>,
+        correlation_id: Option<String>,
+    ) -> ApiResult<()> {
+        if self.repository.get_food(food_id, tenant_id).await?.is_none() {
+            return Err(ApiError::NotFound(format!("food {food_id} not found")));
+        }
+
+        let count_before = self.repository.count_foods(tenant_id).await?;
+
+        if prevent_empty_catalog && count_before <= 1 {
+            return Err(ApiError::Conflict(
+                "refusing to delete the last remaining food; disable PETFOOD_PREVENT_EMPTY_CATALOG to allow this".to_string(),
+            ));
+        }
+
+        self.repository.delete_food(food_id, tenant_id).await?;
+
+        if let Some(threshold) = catalog_size_alert_drop_threshold_percent {
+            if let Some(event) = Self::catalog_size_alert_event(count_before, threshold, correlation_id) {
+                crate::metrics::observe_catalog_size_alert();
+                if let Err(err) = self.event_emitter.emit_event(&event).await {
+                    tracing::error!(error = %err, "failed to emit catalog size alert event");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the `FoodEvent` for a single deletion out of a catalog of
+    /// `count_before` foods, or `None` when the resulting drop doesn't
+    /// exceed `threshold_percent`. A `count_before` of 0 can't happen via
+    /// `delete_food`, which already confirmed the food it just removed
+    /// existed.
+    fn catalog_size_alert_event(
+        count_before: usize,
+        threshold_percent: f64,
+        correlation_id: Option<String>,
+    ) -> Option<FoodEvent> {
+        let count_after = count_before.saturating_sub(1);
+        let drop_percentage = (count_before - count_after) as f64 / count_before as f64 * 100.0;
+        if drop_percentage <= threshold_percent {
+            return None;
+        }
+        Some(FoodEvent::catalog_size_alert(count_before, count_after, drop_percentage, correlation_id))
+    }
+
+    /// Runs the same field-level validation as `create_food`, without
+    /// touching the repository, for `POST /api/admin/foods/validate` — an
+    /// admin UI can surface every problem inline before the caller submits.
+    pub fn validate_create_food(req: &CreateFoodRequest, allowed_image_domains: &[String]) -> Vec<String> {
+        req.validate(allowed_image_domains)
+    }
+
+    /// Bulk-creates `requests`, chunked into `BatchWriteItem`-sized groups
+    /// (`BATCH_WRITE_LIMIT` items each) and written with up to
+    /// `concurrency` chunks in flight at once via `buffer_unordered`, for
+    /// seeding a large catalog without serializing every chunk. Before
+    /// writing, each request is checked against `seed_quality_errors`
+    /// (`min_description_length`, `banned_placeholder_substrings`); records
+    /// that trip it are never written and are reported back in
+    /// `BatchSeedResult::rejected` instead, since an external seed file can
+    /// carry placeholder junk that field-level `validate` wouldn't catch.
+    /// Fails on the first chunk of accepted records that couldn't be
+    /// written (including after the repository's own unprocessed-item
+    /// retries are exhausted).
+    pub async fn create_foods_batch(
+        &self,
+        requests: Vec<CreateFoodRequest>,
+        tenant_id: Option<&str>,
+        concurrency: usize,
+        min_description_length: usize,
+        banned_placeholder_substrings: &[String],
+    ) -> ApiResult<BatchSeedResult> {
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
+        for req in requests {
+            let errors = seed_quality_errors(&req, min_description_length, banned_placeholder_substrings);
+            if errors.is_empty() {
+                accepted.push(req);
+            } else {
+                rejected.push(SeedRejection { name: req.name.clone(), errors });
+            }
+        }
+
+        let foods: Vec<Food> = accepted
+            .into_iter()
+            .map(|req| Food::from_create_request(Uuid::new_v4().to_string(), Utc::now(), req))
+            .collect();
+        let created = foods.len();
+
+        let chunks: Vec<Vec<Food>> = foods.chunks(BATCH_WRITE_LIMIT).map(<[Food]>::to_vec).collect();
+
+        let results: Vec<ApiResult<()>> = stream::iter(chunks)
+            .map(|chunk| {
+                let repository = self.repository.clone();
+                let tenant_id = tenant_id.map(str::to_string);
+                async move { repository.put_foods_batch(&chunk, tenant_id.as_deref()).await }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        for result in results {
+            result?;
+        }
+
+        Ok(BatchSeedResult { created, rejected })
+    }
+
+    /// Bulk-creates `requests` for `POST /api/admin/foods/bulk`, validating
+    /// each with the same field-level rules `create_food` applies (not
+    /// `create_foods_batch`'s looser seed-quality gate, since this is a
+    /// direct admin write of caller-supplied records, not catalog seeding).
+    /// Invalid records are never written and come back in `failed` instead
+    /// of failing the whole request. Valid records are chunked into
+    /// `BatchWriteItem`-sized groups (`BATCH_WRITE_LIMIT` items each) and
+    /// written via `put_foods_batch`; fails on the first chunk that
+    /// couldn't be written (including after the repository's own
+    /// unprocessed-item retries are exhausted).
+    pub async fn bulk_create_foods(
+        &self,
+        requests: Vec<CreateFoodRequest>,
+        tenant_id: Option<&str>,
+        allowed_image_domains: &[String],
+    ) -> ApiResult<BulkCreateResult> {
+        let mut accepted = Vec::new();
+        let mut failed = Vec::new();
+        for req in requests {
+            let errors = req.validate(allowed_image_domains);
+            if errors.is_empty() {
+                accepted.push(req);
+            } else {
+                failed.push(SeedRejection { name: req.name.clone(), errors });
+            }
+        }
+
+        let created: Vec<Food> = accepted
+            .into_iter()
+            .map(|req| Food::from_create_request(Uuid::new_v4().to_string(), Utc::now(), req))
+            .collect();
+
+        for chunk in created.chunks(BATCH_WRITE_LIMIT) {
+            self.repository.put_foods_batch(chunk, tenant_id).await?;
+        }
+
+        Ok(BulkCreateResult { created, failed })
+    }
+
+    /// Supports incremental sync to external systems: returns catalog
+    /// entries changed since `since`, for `GET /api/admin/foods/changes`.
+    pub async fn list_changes_since(&self, since: DateTime<Utc>, tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+        self.repository.list_foods_updated_since(since, tenant_id).await
+    }
+
+    /// Updates a food's price, emitting `FoodEvent::price_changed` only when
+    /// the price actually moves — a no-op update (new price equal to the
+    /// current one) is silent.
+    pub async fn update_price(
+        &self,
+        food_id: &str,
+        new_price_cents: i64,
+        tenant_id: Option<&str>,
+        correlation_id: Option<String>,
+    ) -> ApiResult<Food> {
+        let mut food = self
+            .repository
+            .get_food(food_id, tenant_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("food {food_id} not found")))?;
+
+        let old_price_cents = food.price_cents;
+        let Some(event) = Self::price_change_event(food_id, old_price_cents, new_price_cents, correlation_id) else {
+            return Ok(food);
+        };
+
+        let mut attempt = 0;
+        loop {
+            food.price_cents = new_price_cents;
+            food.updated_at = Utc::now();
+            match self.repository.put_food(&food, tenant_id).await {
+                Ok(()) => {
+                    food.version += 1;
+                    break;
+                }
+                Err(ApiError::Conflict(_)) if attempt < MAX_FOOD_SAVE_RETRIES => {
+                    attempt += 1;
+                    food = self
+                        .repository
+                        .get_food(food_id, tenant_id)
+                        .await?
+                        .ok_or_else(|| ApiError::NotFound(format!("food {food_id} not found")))?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        if let Err(err) = self.event_emitter.emit_event(&event).await {
+            tracing::error!(error = %err, food_id = %food.food_id, "failed to emit food price changed event");
+        }
+
+        self.audit_logger
+            .record_price_change(food_id, old_price_cents, new_price_cents, event.correlation_id.clone())
+            .await;
+
+        Ok(food)
+    }
+
+    /// Adjusts a food's `stock_quantity` and updates `availability_status`
+    /// to match the result (`OutOfStock` at zero, `InStock` otherwise), for
+    /// `PATCH /api/admin/foods/:food_id/stock`. A `Delta` that would take
+    /// stock negative is rejected with `ApiError::Validation` rather than
+    /// clamping to zero. Uses the same read-modify-write-retry loop as
+    /// `update_price`: `put_food`'s version-conditioned write is this
+    /// repository's only optimistic-concurrency primitive, so a concurrent
+    /// conflict here is handled the same way a concurrent price change is.
+    pub async fn adjust_stock(&self, food_id: &str, adjustment: StockAdjustment, tenant_id: Option<&str>) -> ApiResult<Food> {
+        let mut food = self
+            .repository
+            .get_food(food_id, tenant_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("food {food_id} not found")))?;
+
+        let mut attempt = 0;
+        loop {
+            let new_quantity = match adjustment {
+                StockAdjustment::Delta(delta) => {
+                    let result = food.stock_quantity as i64 + delta as i64;
+                    if result < 0 {
+                        return Err(ApiError::Validation(format!(
+                            "stock adjustment of {delta} would take food {food_id} below zero (currently {})",
+                            food.stock_quantity
+                        )));
+                    }
+                    result as u32
+                }
+                StockAdjustment::Set(quantity) => quantity,
+            };
+
+            food.stock_quantity = new_quantity;
+            food.availability_status = if new_quantity == 0 { AvailabilityStatus::OutOfStock } else { AvailabilityStatus::InStock };
+            food.updated_at = Utc::now();
+
+            match self.repository.put_food(&food, tenant_id).await {
+                Ok(()) => {
+                    food.version += 1;
+                    return Ok(food);
+                }
+                Err(ApiError::Conflict(_)) if attempt < MAX_FOOD_SAVE_RETRIES => {
+                    attempt += 1;
+                    food = self
+                        .repository
+                        .get_food(food_id, tenant_id)
+                        .await?
+                        .ok_or_else(|| ApiError::NotFound(format!("food {food_id} not found")))?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Newest-first edit history for `GET /api/admin/foods/:food_id/history`.
+    pub async fn history_for(&self, food_id: &str) -> ApiResult<Vec<AuditEntry>> {
+        self.audit_logger.history_for(food_id).await
+    }
+
+    /// Builds the `FoodEvent` for a price update, or `None` when the new
+    /// price matches the old one — a no-op update should never emit.
+    fn price_change_event(
+        food_id: &str,
+        old_price_cents: i64,
+        new_price_cents: i64,
+        correlation_id: Option<String>,
+    ) -> Option<FoodEvent> {
+        if old_price_cents == new_price_cents {
+            return None;
+        }
+        Some(FoodEvent::price_changed(
+            food_id.to_string(),
+            old_price_cents,
+            new_price_cents,
+            correlation_id,
+        ))
+    }
+
+    /// Applies `update_price` to each `(food_id, new_price_cents)` pair in
+    /// turn, so a single caller-initiated bulk update emits one
+    /// `FoodEvent::price_changed` per food whose price actually changed.
+    pub async fn bulk_update_prices(
+        &self,
+        updates: Vec<(String, i64)>,
+        tenant_id: Option<&str>,
+        correlation_id: Option<String>,
+    ) -> ApiResult<Vec<Food>> {
+        let mut updated = Vec::with_capacity(updates.len());
+        for (food_id, new_price_cents) in updates {
+            updated.push(
+                self.update_price(&food_id, new_price_cents, tenant_id, correlation_id.clone())
+                    .await?,
+            );
+        }
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration as StdDuration;
+
+    use chrono::Duration;
+
+    use super::*;
+    use crate::models::{AvailabilityStatus, FoodType, PetType};
+
+    #[derive(Default)]
+    struct InMemoryFoodRepository(Mutex<HashMap<String, Food>>);
+
+    #[async_trait::async_trait]
+    impl FoodRepository for InMemoryFoodRepository {
+        async fn get_food(&self, food_id: &str, _tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            Ok(self.0.lock().unwrap().get(food_id).cloned())
+        }
+
+        async fn list_foods(&self, _tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            Ok(self.0.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn put_food(&self, food: &Food, _tenant_id: Option<&str>) -> ApiResult<()> {
+            let mut foods = self.0.lock().unwrap();
+            if let Some(stored) = foods.get(&food.food_id) {
+                if stored.version != food.version {
+                    return Err(ApiError::Conflict(format!("food {} was modified concurrently", food.food_id)));
+                }
+            }
+
+            let mut saved = food.clone();
+            saved.version += 1;
+            foods.insert(food.food_id.clone(), saved);
+            Ok(())
+        }
+
+        async fn delete_food(&self, food_id: &str, _tenant_id: Option<&str>) -> ApiResult<()> {
+            self.0.lock().unwrap().remove(food_id);
+            Ok(())
+        }
+    }
+
+    /// Fails the first `put_food` for a given food with a simulated
+    /// optimistic lock conflict, then delegates normally — standing in for
+    /// another writer racing `update_price` between its read and its write.
+    #[derive(Default)]
+    struct ConflictOnceFoodRepository {
+        inner: InMemoryFoodRepository,
+        already_conflicted: Mutex<std::collections::HashSet<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl FoodRepository for ConflictOnceFoodRepository {
+        async fn get_food(&self, food_id: &str, tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            self.inner.get_food(food_id, tenant_id).await
+        }
+
+        async fn list_foods(&self, tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            self.inner.list_foods(tenant_id).await
+        }
+
+        async fn put_food(&self, food: &Food, tenant_id: Option<&str>) -> ApiResult<()> {
+            let is_first_attempt = self.already_conflicted.lock().unwrap().insert(food.food_id.clone());
+            if is_first_attempt {
+                return Err(ApiError::Conflict(format!("food {} was modified concurrently", food.food_id)));
+            }
+            self.inner.put_food(food, tenant_id).await
+        }
+
+        async fn delete_food(&self, food_id: &str, tenant_id: Option<&str>) -> ApiResult<()> {
+            self.inner.delete_food(food_id, tenant_id).await
+        }
+    }
+
+    /// Reports a fixed `consumed_capacity_units` on every
+    /// `list_foods_within_budget` call, standing in for
+    /// `DynamoDbFoodRepository`'s real `ConsumedCapacity` reporting so a
+    /// test can drive the budget check without a live table.
+    struct MeteredFoodRepository {
+        foods: Vec<Food>,
+        consumed_capacity_units: f64,
+    }
+
+    #[async_trait::async_trait]
+    impl FoodRepository for MeteredFoodRepository {
+        async fn get_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            Ok(None)
+        }
+
+        async fn list_foods(&self, _tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            Ok(self.foods.clone())
+        }
+
+        async fn put_food(&self, _food: &Food, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+
+        async fn delete_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+
+        async fn list_foods_within_budget(&self, tenant_id: Option<&str>, budget: Option<&CapacityBudget>) -> ApiResult<Vec<Food>> {
+            if let Some(budget) = budget {
+                budget.record(self.consumed_capacity_units)?;
+            }
+            self.list_foods(tenant_id).await
+        }
+    }
+
+    /// Tracks how many `put_foods_batch` calls are in flight at once, so a
+    /// test can assert `create_foods_batch` never exceeds its configured
+    /// concurrency.
+    #[derive(Default)]
+    struct ConcurrencyTrackingFoodRepository {
+        in_flight: AtomicUsize,
+        max_observed: AtomicUsize,
+        items_written: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl FoodRepository for ConcurrencyTrackingFoodRepository {
+        async fn get_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            Ok(None)
+        }
+
+        async fn list_foods(&self, _tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            Ok(Vec::new())
+        }
+
+        async fn put_food(&self, _food: &Food, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+
+        async fn delete_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+
+        async fn put_foods_batch(&self, foods: &[Food], _tenant_id: Option<&str>) -> ApiResult<()> {
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_observed.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(StdDuration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.items_written.fetch_add(foods.len(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn dummy_event_emitter() -> Arc<EventEmitter> {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        Arc::new(EventEmitter::with_concurrency_limit(
+            aws_sdk_eventbridge::Client::new(&sdk_config),
+            "test-bus".to_string(),
+            None,
+            false,
+        ))
+    }
+
+    fn dummy_audit_logger() -> Arc<AuditLogger> {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        Arc::new(AuditLogger::new(Arc::new(crate::repository::DynamoDbAuditRepository::new(
+            aws_sdk_dynamodb::Client::new(&sdk_config),
+            "test-audit".to_string(),
+        ))))
+    }
+
+    fn food_updated_at(food_id: &str, updated_at: DateTime<Utc>) -> Food {
+        Food {
+            food_id: food_id.to_string(),
+            name: "Kibble".to_string(),
+            description: "Crunchy kibble".to_string(),
+            ingredients: vec!["chicken".to_string()],
+            price_cents: 999,
+            stock_quantity: 10,
+            availability_status: AvailabilityStatus::InStock,
+            pet_type: PetType::Dog,
+            food_type: FoodType::Dry,
+            image_path: "/images/kibble.png".to_string(),
+            categories: Vec::new(),
+            prices: HashMap::new(),
+            updated_at,
+            version: 0,
+        }
+    }
+
+    fn sample_create_request(name: &str) -> CreateFoodRequest {
+        CreateFoodRequest {
+            name: name.to_string(),
+            description: "Crunchy kibble".to_string(),
+            ingredients: vec!["chicken".to_string()],
+            price_cents: 999,
+            stock_quantity: 10,
+            pet_type: PetType::Dog,
+            food_type: FoodType::Dry,
+            image_path: String::new(),
+            categories: Vec::new(),
+            prices: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_foods_batch_never_exceeds_the_configured_concurrency() {
+        let repository = Arc::new(ConcurrencyTrackingFoodRepository::default());
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+        let requests: Vec<CreateFoodRequest> = (0..(BATCH_WRITE_LIMIT * 6))
+            .map(|i| sample_create_request(&format!("food-{i}")))
+            .collect();
+        let total_requested = requests.len();
+
+        let result = service.create_foods_batch(requests, None, 2, 0, &[]).await.unwrap();
+
+        assert_eq!(result.created, total_requested);
+        assert!(result.rejected.is_empty());
+        assert_eq!(repository.items_written.load(Ordering::SeqCst), total_requested);
+        assert!(repository.max_observed.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn create_foods_batch_rejects_records_that_trip_the_quality_gate() {
+        let repository = Arc::new(ConcurrencyTrackingFoodRepository::default());
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+        let mut too_short = sample_create_request("short-description");
+        too_short.description = "ok".to_string();
+        let mut placeholder = sample_create_request("placeholder-description");
+        placeholder.description = "TODO: write a real description".to_string();
+        let clean = sample_create_request("clean-description");
+
+        let result = service
+            .create_foods_batch(vec![too_short, placeholder, clean], None, 2, 10, &["TODO".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(result.created, 1);
+        assert_eq!(repository.items_written.load(Ordering::SeqCst), 1);
+        assert_eq!(result.rejected.len(), 2);
+        assert_eq!(result.rejected[0].name, "short-description");
+        assert_eq!(result.rejected[1].name, "placeholder-description");
+    }
+
+    #[tokio::test]
+    async fn bulk_create_foods_writes_the_valid_records_and_reports_the_invalid_ones() {
+        let repository = Arc::new(ConcurrencyTrackingFoodRepository::default());
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+        let mut blank_name = sample_create_request("");
+        blank_name.name = String::new();
+        let negative_price = CreateFoodRequest { price_cents: -1, ..sample_create_request("negative-price") };
+        let clean = sample_create_request("clean-record");
+
+        let result = service.bulk_create_foods(vec![blank_name, negative_price, clean], None, &[]).await.unwrap();
+
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.created[0].name, "clean-record");
+        assert_eq!(repository.items_written.load(Ordering::SeqCst), 1);
+        assert_eq!(result.failed.len(), 2);
+        assert_eq!(result.failed[0].name, "");
+        assert_eq!(result.failed[0].errors, vec!["name cannot be blank".to_string()]);
+        assert_eq!(result.failed[1].name, "negative-price");
+        assert_eq!(result.failed[1].errors, vec!["price_cents cannot be negative".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn delete_food_is_allowed_when_more_than_one_food_remains() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        repository.put_food(&food_updated_at("food-2", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        service.delete_food("food-1", None, true, None, None).await.unwrap();
+
+        assert!(repository.get_food("food-1", None).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_food_refuses_to_remove_the_last_food_when_the_guard_is_enabled() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let result = service.delete_food("food-1", None, true, None, None).await;
+
+        assert!(matches!(result, Err(ApiError::Conflict(_))));
+        assert!(repository.get_food("food-1", None).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn delete_food_removes_the_last_food_when_the_guard_is_disabled() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        service.delete_food("food-1", None, false, None, None).await.unwrap();
+
+        assert!(repository.get_food("food-1", None).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn list_changes_since_excludes_foods_updated_before_the_cutoff() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let cutoff = Utc::now();
+        repository
+            .put_food(&food_updated_at("old", cutoff - Duration::hours(1)), None)
+            .await
+            .unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let changes = service.list_changes_since(cutoff, None).await.unwrap();
+
+        assert!(changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn list_changes_since_includes_foods_updated_after_the_cutoff() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let cutoff = Utc::now();
+        repository
+            .put_food(&food_updated_at("new", cutoff + Duration::hours(1)), None)
+            .await
+            .unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let changes = service.list_changes_since(cutoff, None).await.unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].food_id, "new");
+    }
+
+    #[tokio::test]
+    async fn list_ingredients_ranks_by_occurrence_count_and_respects_the_limit() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let mut chicken_rice = food_updated_at("food-1", Utc::now());
+        chicken_rice.ingredients = vec!["chicken".to_string(), "rice".to_string()];
+        let mut chicken_corn = food_updated_at("food-2", Utc::now());
+        chicken_corn.ingredients = vec!["chicken".to_string(), "corn".to_string()];
+        repository.put_food(&chicken_rice, None).await.unwrap();
+        repository.put_food(&chicken_corn, None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let top_one = service.list_ingredients(None, 1).await.unwrap();
+
+        assert_eq!(top_one, vec![IngredientCount { ingredient: "chicken".to_string(), count: 2 }]);
+    }
+
+    #[tokio::test]
+    async fn search_foods_ranked_delegates_scoring_to_the_models_layer() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let mut name_match = food_updated_at("food-1", Utc::now());
+        name_match.name = "Chicken Delight".to_string();
+        name_match.ingredients = vec![];
+        let mut description_only = food_updated_at("food-2", Utc::now());
+        description_only.name = "Salmon Bites".to_string();
+        description_only.description = "a hit with chicken-loving dogs too".to_string();
+        description_only.ingredients = vec![];
+        repository.put_food(&name_match, None).await.unwrap();
+        repository.put_food(&description_only, None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let results = service.search_foods_ranked(None, "chicken").await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].food.food_id, "food-1");
+        assert_eq!(results[1].food.food_id, "food-2");
+    }
+
+    #[tokio::test]
+    async fn list_foods_within_budget_returns_results_when_under_the_cap() {
+        let repository = Arc::new(MeteredFoodRepository {
+            foods: vec![food_updated_at("food-1", Utc::now())],
+            consumed_capacity_units: 4.0,
+        });
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+        let budget = CapacityBudget::new(10.0);
+
+        let foods = service.list_foods_within_budget(None, Some(&budget)).await.unwrap();
+
+        assert_eq!(foods.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn list_foods_within_budget_aborts_an_over_budget_scan() {
+        let repository = Arc::new(MeteredFoodRepository {
+            foods: vec![food_updated_at("food-1", Utc::now())],
+            consumed_capacity_units: 40.0,
+        });
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+        let budget = CapacityBudget::new(10.0);
+
+        let result = service.list_foods_within_budget(None, Some(&budget)).await;
+
+        assert!(matches!(result, Err(ApiError::BudgetExceeded(_))));
+    }
+
+    #[test]
+    fn price_change_event_fires_on_a_real_price_change() {
+        let event = FoodService::price_change_event("food-1", 1000, 900, None);
+
+        assert!(event.is_some());
+        assert_eq!(event.unwrap().new_price_cents, Some(900));
+    }
+
+    #[test]
+    fn price_change_event_does_not_fire_when_the_price_is_unchanged() {
+        let event = FoodService::price_change_event("food-1", 1000, 1000, None);
+
+        assert!(event.is_none());
+    }
+
+    #[test]
+    fn catalog_size_alert_event_fires_on_a_large_drop() {
+        // Deleting 1 of 2 foods is a 50% drop, well past a 25% threshold.
+        let event = FoodService::catalog_size_alert_event(2, 25.0, None);
+
+        assert!(event.is_some());
+        let event = event.unwrap();
+        assert_eq!(event.event_type, "CatalogSizeAlert");
+        assert_eq!(event.catalog_count_before, Some(2));
+        assert_eq!(event.catalog_count_after, Some(1));
+        assert_eq!(event.catalog_drop_percentage, Some(50.0));
+    }
+
+    #[test]
+    fn catalog_size_alert_event_does_not_fire_on_a_small_drop() {
+        // Deleting 1 of 100 foods is a 1% drop, well under a 25% threshold.
+        let event = FoodService::catalog_size_alert_event(100, 25.0, None);
+
+        assert!(event.is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_food_emits_a_catalog_size_alert_event_on_a_large_drop() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        repository.put_food(&food_updated_at("food-2", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let before = crate::metrics::CATALOG_SIZE_ALERTS_TOTAL.get();
+        service.delete_food("food-1", None, false, Some(25.0), None).await.unwrap();
+
+        assert_eq!(crate::metrics::CATALOG_SIZE_ALERTS_TOTAL.get(), before + 1);
+    }
+
+    #[tokio::test]
+    async fn delete_food_does_not_emit_a_catalog_size_alert_event_on_a_small_drop() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        for i in 0..100 {
+            repository
+                .put_food(&food_updated_at(&format!("food-{i}"), Utc::now()), None)
+                .await
+                .unwrap();
+        }
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let before = crate::metrics::CATALOG_SIZE_ALERTS_TOTAL.get();
+        service.delete_food("food-0", None, false, Some(25.0), None).await.unwrap();
+
+        assert_eq!(crate::metrics::CATALOG_SIZE_ALERTS_TOTAL.get(), before);
+    }
+
+    #[tokio::test]
+    async fn update_price_persists_a_real_price_change() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let updated = service.update_price("food-1", 499, None, None).await.unwrap();
+
+        assert_eq!(updated.price_cents, 499);
+        let stored = repository.get_food("food-1", None).await.unwrap().unwrap();
+        assert_eq!(stored.price_cents, 499);
+        assert_eq!(updated.version, stored.version, "the returned version must match what was persisted");
+    }
+
+    #[tokio::test]
+    async fn update_price_is_a_no_op_when_the_price_is_unchanged() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let original = food_updated_at("food-1", Utc::now());
+        let original_updated_at = original.updated_at;
+        repository.put_food(&original, None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let updated = service.update_price("food-1", 999, None, None).await.unwrap();
+
+        assert_eq!(updated.updated_at, original_updated_at);
+    }
+
+    #[tokio::test]
+    async fn update_price_retries_after_a_simulated_optimistic_lock_conflict() {
+        let repository = Arc::new(ConflictOnceFoodRepository {
+            inner: InMemoryFoodRepository(Mutex::new(HashMap::from([(
+                "food-1".to_string(),
+                food_updated_at("food-1", Utc::now()),
+            )]))),
+            already_conflicted: Mutex::new(std::collections::HashSet::new()),
+        });
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let updated = service.update_price("food-1", 499, None, None).await.unwrap();
+
+        assert_eq!(updated.price_cents, 499, "the retry should have applied the update once the conflict cleared");
+        let stored = repository.get_food("food-1", None).await.unwrap().unwrap();
+        assert_eq!(stored.price_cents, 499);
+        assert_eq!(stored.version, 1, "the successful retry's write should be the only one that landed");
+        assert_eq!(updated.version, stored.version, "the returned version must match what was persisted");
+    }
+
+    #[tokio::test]
+    async fn adjust_stock_applies_a_positive_delta_and_stays_in_stock() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let updated = service.adjust_stock("food-1", StockAdjustment::Delta(5), None).await.unwrap();
+
+        assert_eq!(updated.stock_quantity, 15);
+        assert_eq!(updated.availability_status, AvailabilityStatus::InStock);
+        let stored = repository.get_food("food-1", None).await.unwrap().unwrap();
+        assert_eq!(updated.version, stored.version, "the returned version must match what was persisted");
+    }
+
+    #[tokio::test]
+    async fn adjust_stock_flips_to_out_of_stock_when_depleted_to_zero() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let updated = service.adjust_stock("food-1", StockAdjustment::Delta(-10), None).await.unwrap();
+
+        assert_eq!(updated.stock_quantity, 0);
+        assert_eq!(updated.availability_status, AvailabilityStatus::OutOfStock);
+    }
+
+    #[tokio::test]
+    async fn adjust_stock_set_flips_back_to_in_stock_once_restocked() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let mut out_of_stock = food_updated_at("food-1", Utc::now());
+        out_of_stock.stock_quantity = 0;
+        out_of_stock.availability_status = AvailabilityStatus::OutOfStock;
+        repository.put_food(&out_of_stock, None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let updated = service.adjust_stock("food-1", StockAdjustment::Set(20), None).await.unwrap();
+
+        assert_eq!(updated.stock_quantity, 20);
+        assert_eq!(updated.availability_status, AvailabilityStatus::InStock);
+    }
+
+    #[tokio::test]
+    async fn adjust_stock_rejects_a_delta_that_would_go_negative() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let result = service.adjust_stock("food-1", StockAdjustment::Delta(-11), None).await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+        assert_eq!(repository.get_food("food-1", None).await.unwrap().unwrap().stock_quantity, 10);
+    }
+
+    #[tokio::test]
+    async fn adjust_stock_retries_after_a_simulated_optimistic_lock_conflict() {
+        let repository = Arc::new(ConflictOnceFoodRepository {
+            inner: InMemoryFoodRepository(Mutex::new(HashMap::from([(
+                "food-1".to_string(),
+                food_updated_at("food-1", Utc::now()),
+            )]))),
+            already_conflicted: Mutex::new(std::collections::HashSet::new()),
+        });
+        let service = FoodService::new(repository.clone(), dummy_event_emitter(), dummy_audit_logger());
+
+        let updated = service.adjust_stock("food-1", StockAdjustment::Delta(5), None).await.unwrap();
+
+        assert_eq!(updated.stock_quantity, 15, "the retry should have applied the update once the conflict cleared");
+        let stored = repository.get_food("food-1", None).await.unwrap().unwrap();
+        assert_eq!(stored.stock_quantity, 15);
+        assert_eq!(stored.version, 1, "the successful retry's write should be the only one that landed");
+        assert_eq!(updated.version, stored.version, "the returned version must match what was persisted");
+    }
+
+    #[tokio::test]
+    async fn create_food_rejects_an_invalid_request_from_the_api_even_when_trust_seed_is_enabled() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+        let mut invalid = sample_create_request("");
+        invalid.name = String::new();
+
+        let result = service.create_food(invalid, None, &[], CreationSource::Api, true).await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn create_food_validates_a_seeded_request_when_trust_seed_is_disabled() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+        let mut invalid = sample_create_request("");
+        invalid.name = String::new();
+
+        let result = service.create_food(invalid, None, &[], CreationSource::Seeding, false).await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn create_food_skips_validation_for_a_seeded_request_when_trust_seed_is_enabled() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+        let mut invalid = sample_create_request("");
+        invalid.name = String::new();
+
+        let food = service
+            .create_food(invalid, None, &[], CreationSource::Seeding, true)
+            .await
+            .unwrap();
+
+        assert_eq!(food.name, "");
+    }
+
+    #[tokio::test]
+    async fn bulk_update_prices_updates_every_food_in_order() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        repository.put_food(&food_updated_at("food-2", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let updated = service
+            .bulk_update_prices(
+                vec![("food-1".to_string(), 100), ("food-2".to_string(), 200)],
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let prices: Vec<i64> = updated.iter().map(|food| food.price_cents).collect();
+        assert_eq!(prices, vec![100, 200]);
+    }
+
+    fn image_less_food(food_id: &str) -> Food {
+        Food { image_path: String::new(), ..food_updated_at(food_id, Utc::now()) }
+    }
+
+    #[tokio::test]
+    async fn get_food_does_not_emit_a_missing_image_event_when_the_throttle_is_disabled() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&image_less_food("food-1"), None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let before = crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get();
+        service.get_food("food-1", None, None).await.unwrap();
+
+        assert_eq!(crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get(), before);
+    }
+
+    #[tokio::test]
+    async fn get_food_does_not_emit_a_missing_image_event_for_a_food_with_an_image() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger())
+            .with_missing_image_emit_window(Some(StdDuration::from_secs(60)));
+
+        let before = crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get();
+        service.get_food("food-1", None, None).await.unwrap();
+
+        assert_eq!(crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get(), before);
+    }
+
+    #[tokio::test]
+    async fn get_food_emits_a_food_viewed_analytics_event_when_an_analytics_emitter_is_configured() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger())
+            .with_analytics_emitter(Some(dummy_event_emitter()));
+
+        let before = crate::metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count();
+        service.get_food("food-1", None, Some("req-123".to_string())).await.unwrap();
+
+        assert_eq!(crate::metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count(), before + 1);
+    }
+
+    #[tokio::test]
+    async fn get_food_does_not_emit_an_analytics_event_when_no_analytics_emitter_is_configured() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let before = crate::metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count();
+        service.get_food("food-1", None, None).await.unwrap();
+
+        assert_eq!(crate::metrics::EVENT_EMIT_AGE_SECONDS.get_sample_count(), before);
+    }
+
+    #[tokio::test]
+    async fn get_foods_batch_returns_only_the_ids_that_exist() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&food_updated_at("food-1", Utc::now()), None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger());
+
+        let found = service
+            .get_foods_batch(&["food-1".to_string(), "missing-food".to_string()], None)
+            .await
+            .unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert!(found.contains_key("food-1"));
+    }
+
+    #[tokio::test]
+    async fn repeated_reads_of_the_same_image_less_food_within_the_window_emit_only_once() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&image_less_food("food-1"), None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger())
+            .with_missing_image_emit_window(Some(StdDuration::from_secs(60)));
+
+        let before = crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get();
+        service.get_food("food-1", None, None).await.unwrap();
+        service.get_food("food-1", None, None).await.unwrap();
+        service.get_food("food-1", None, None).await.unwrap();
+
+        assert_eq!(crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get(), before + 1);
+    }
+
+    #[tokio::test]
+    async fn a_read_after_the_window_elapses_emits_again() {
+        let repository = Arc::new(InMemoryFoodRepository::default());
+        repository.put_food(&image_less_food("food-1"), None).await.unwrap();
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger())
+            .with_missing_image_emit_window(Some(StdDuration::from_millis(10)));
+
+        let before = crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get();
+        service.get_food("food-1", None, None).await.unwrap();
+        tokio::time::sleep(StdDuration::from_millis(20)).await;
+        service.get_food("food-1", None, None).await.unwrap();
+
+        assert_eq!(crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get(), before + 2);
+    }
+
+    #[tokio::test]
+    async fn list_foods_within_budget_emits_a_missing_image_event_per_image_less_food() {
+        let foods = vec![image_less_food("food-1"), food_updated_at("food-2", Utc::now())];
+        let repository = Arc::new(MeteredFoodRepository { foods, consumed_capacity_units: 0.0 });
+        let service = FoodService::new(repository, dummy_event_emitter(), dummy_audit_logger())
+            .with_missing_image_emit_window(Some(StdDuration::from_secs(60)));
+
+        let before = crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get();
+        service.list_foods_within_budget(None, None).await.unwrap();
+
+        assert_eq!(crate::metrics::MISSING_IMAGE_VIEWS_TOTAL.get(), before + 1);
+    }
+}
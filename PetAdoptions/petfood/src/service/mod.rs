@@ -0,0 +1,11 @@
+mod audit_logger;
+mod cart_service;
+mod food_service;
+mod recommendation_service;
+mod recommendation_weights;
+
+pub use audit_logger::AuditLogger;
+pub use cart_service::CartService;
+pub use food_service::{BatchSeedResult, BulkCreateResult, FoodService, StockAdjustment};
+pub use recommendation_service::RecommendationService;
+pub use recommendation_weights::RecommendationWeights;
@@ -0,0 +1,689 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures_util::stream::{self, StreamExt};
+
+use super::RecommendationWeights;
+use crate::error::ApiResult;
+use crate::models::{
+    AvailabilityStatus, EmptyRecommendationReason, Food, PetType, RecommendationStats, RecommendationStatsForAllPetTypes, RecommendationsResponse,
+    SortOrder,
+};
+use crate::repository::FoodRepository;
+
+/// Identifies one cached `recommend` result: the inputs that actually
+/// affect the computed list. `default_sort` and `include_empty_reason` are
+/// fixed per-service, so they don't need to be part of the key.
+type CacheKey = (PetType, SortOrder, Option<String>, Option<String>);
+
+pub struct RecommendationService {
+    food_repository: Arc<dyn FoodRepository>,
+    default_sort: SortOrder,
+    weights: RecommendationWeights,
+    /// When enabled, an empty recommendation result carries a `reason`
+    /// explaining why instead of a bare empty array.
+    include_empty_reason: bool,
+    /// How long a cached `recommend` result stays valid. `None` disables
+    /// caching entirely — every call recomputes against the repository.
+    cache_ttl: Option<Duration>,
+    cache: Mutex<HashMap<CacheKey, (Instant, RecommendationsResponse)>>,
+    /// How many `get_recommendation_stats_for_all_pet_types` per-pet-type
+    /// queries run concurrently via `buffer_unordered`.
+    stats_fanout_concurrency: usize,
+    /// Total time `get_recommendation_stats_for_all_pet_types` allows
+    /// itself before returning early with `partial: true`. `None` disables
+    /// the budget.
+    stats_time_budget: Option<Duration>,
+}
+
+impl RecommendationService {
+    pub fn new(
+        food_repository: Arc<dyn FoodRepository>,
+        default_sort: SortOrder,
+        include_empty_reason: bool,
+        cache_ttl: Option<Duration>,
+    ) -> Self {
+        Self {
+            food_repository,
+            default_sort,
+            weights: RecommendationWeights::default(),
+            include_empty_reason,
+            cache_ttl,
+            cache: Mutex::new(HashMap::new()),
+            stats_fanout_concurrency: 4,
+            stats_time_budget: None,
+        }
+    }
+
+    /// Sets `PETFOOD_RECOMMENDATION_STATS_FANOUT_CONCURRENCY` — see
+    /// `stats_fanout_concurrency`.
+    pub fn with_stats_fanout_concurrency(mut self, stats_fanout_concurrency: usize) -> Self {
+        self.stats_fanout_concurrency = stats_fanout_concurrency;
+        self
+    }
+
+    /// Sets `PETFOOD_RECOMMENDATION_STATS_TIME_BUDGET_MS` — see
+    /// `stats_time_budget`.
+    pub fn with_stats_time_budget(mut self, stats_time_budget: Option<Duration>) -> Self {
+        self.stats_time_budget = stats_time_budget;
+        self
+    }
+
+    /// Drops every cached result. Callers invalidate this after a catalog
+    /// write (create, price update, delete, seed) since a cached list could
+    /// otherwise keep serving stale foods for up to `cache_ttl`.
+    pub fn invalidate_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// `user_id`, when provided, seeds the tie-break order for foods that
+    /// score equally, so a given user sees a stable order across requests
+    /// while different users can see a different (but each individually
+    /// stable) order. Without a `user_id`, ties keep the repository's
+    /// listing order, which can vary request to request.
+    pub async fn recommend(
+        &self,
+        pet_type: PetType,
+        sort: Option<SortOrder>,
+        tenant_id: Option<&str>,
+        user_id: Option<&str>,
+    ) -> ApiResult<RecommendationsResponse> {
+        let sort = sort.unwrap_or(self.default_sort);
+        let key: CacheKey = (pet_type, sort, tenant_id.map(str::to_string), user_id.map(str::to_string));
+
+        if let Some(ttl) = self.cache_ttl {
+            let cache = self.cache.lock().unwrap();
+            if let Some((cached_at, response)) = cache.get(&key) {
+                if cached_at.elapsed() < ttl {
+                    return Ok(response.clone());
+                }
+            }
+        }
+
+        let all_foods = self.food_repository.list_foods(tenant_id).await?;
+        let catalog_is_empty = all_foods.is_empty();
+        let has_matching_pet_type = all_foods.iter().any(|food| food.pet_type == pet_type);
+
+        let seed = user_id.map(seed_for_user);
+        let recommended = apply_recommendation_logic(all_foods, pet_type, &self.weights, seed);
+        let sorted = apply_sort(recommended, sort);
+
+        let response = if sorted.is_empty() && self.include_empty_reason {
+            let reason = derive_empty_reason(catalog_is_empty, has_matching_pet_type);
+            RecommendationsResponse::EmptyWithReason { foods: sorted, reason }
+        } else {
+            RecommendationsResponse::Foods(sorted)
+        };
+
+        if self.cache_ttl.is_some() {
+            self.cache.lock().unwrap().insert(key, (Instant::now(), response.clone()));
+        }
+
+        Ok(response)
+    }
+
+    /// Average price across the catalog's foods matching `pet_type`, rounded
+    /// to 2 decimal places so long decimal tails never reach the API.
+    pub async fn get_recommendation_stats(
+        &self,
+        pet_type: PetType,
+        tenant_id: Option<&str>,
+    ) -> ApiResult<RecommendationStats> {
+        let foods = self.food_repository.list_foods(tenant_id).await?;
+        let price_cents: Vec<i64> = foods
+            .into_iter()
+            .filter(|f| f.pet_type == pet_type)
+            .map(|f| f.price_cents)
+            .collect();
+        Ok(RecommendationStats::from_price_cents(&price_cents))
+    }
+
+    /// Stats for every `PetType` at once, for a dashboard that would
+    /// otherwise need one round-trip per pet type. Fan-out is bounded by
+    /// `stats_fanout_concurrency` via `buffer_unordered`, so this can't push
+    /// more than that many catalog listings onto the repository at a time.
+    /// When `stats_time_budget` is set and runs out before every pet type
+    /// has reported in, returns whichever finished in time with
+    /// `partial: true` rather than waiting indefinitely on a slow query.
+    pub async fn get_recommendation_stats_for_all_pet_types(
+        &self,
+        tenant_id: Option<&str>,
+    ) -> ApiResult<RecommendationStatsForAllPetTypes> {
+        let mut results = stream::iter(PetType::ALL.iter().copied())
+            .map(|pet_type| async move { self.get_recommendation_stats(pet_type, tenant_id).await.map(|stats| (pet_type, stats)) })
+            .buffer_unordered(self.stats_fanout_concurrency.max(1));
+
+        let deadline = self.stats_time_budget.map(|budget| Instant::now() + budget);
+        let mut stats = HashMap::new();
+        let mut partial = false;
+
+        loop {
+            let next = match deadline {
+                Some(deadline) => {
+                    let Some(remaining) = deadline.checked_duration_since(Instant::now()) else {
+                        partial = true;
+                        break;
+                    };
+                    match tokio::time::timeout(remaining, results.next()).await {
+                        Ok(next) => next,
+                        Err(_) => {
+                            partial = true;
+                            break;
+                        }
+                    }
+                }
+                None => results.next().await,
+            };
+
+            match next {
+                Some(result) => {
+                    let (pet_type, pet_type_stats) = result?;
+                    stats.insert(pet_type, pet_type_stats);
+                }
+                None => break,
+            }
+        }
+
+        Ok(RecommendationStatsForAllPetTypes { stats, partial })
+    }
+}
+
+/// Drops out-of-stock foods, then prioritizes foods matching the requested
+/// pet type, keeping the rest as a fallback so the caller always gets a
+/// non-empty list when the catalog has any stock at all. The boost applied
+/// to a match comes from `weights` rather than a hardcoded match, so scoring
+/// a pet type that isn't in the weights table (including ones added after
+/// this code was written) still works via its configured default.
+///
+/// Foods that tie on score break ties by `seed` (see `seed_for_user`) when
+/// given, so the order among equally-scored foods is stable for a given
+/// user instead of following whatever order the repository happened to
+/// return them in.
+pub fn apply_recommendation_logic(foods: Vec<Food>, pet_type: PetType, weights: &RecommendationWeights, seed: Option<u64>) -> Vec<Food> {
+    let score = |food: &Food| -> f64 {
+        if food.pet_type == pet_type {
+            weights.weight_for(pet_type)
+        } else {
+            0.0
+        }
+    };
+    let mut foods: Vec<Food> = foods
+        .into_iter()
+        .filter(|food| food.availability_status == AvailabilityStatus::InStock)
+        .collect();
+    match seed {
+        Some(seed) => foods.sort_by(|a, b| {
+            score(b)
+                .partial_cmp(&score(a))
+                .unwrap()
+                .then_with(|| tie_break_key(seed, &a.food_id).cmp(&tie_break_key(seed, &b.food_id)))
+        }),
+        None => foods.sort_by(|a, b| score(b).partial_cmp(&score(a)).unwrap()),
+    }
+    foods
+}
+
+/// Hashes `user_id` into a tie-break seed for `apply_recommendation_logic`.
+fn seed_for_user(user_id: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    user_id.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Combines `seed` with a food's id into a per-food ordering key, so sorting
+/// by this key yields the same permutation across calls for the same seed
+/// while different seeds yield different permutations.
+fn tie_break_key(seed: u64, food_id: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    food_id.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Used when a recommendation result is empty and the caller opted into
+/// explanations: distinguishes an empty catalog, a catalog with nothing for
+/// this pet type, and a pet type whose foods are all out of stock.
+fn derive_empty_reason(catalog_is_empty: bool, has_matching_pet_type: bool) -> EmptyRecommendationReason {
+    if catalog_is_empty {
+        EmptyRecommendationReason::NoCatalog
+    } else if !has_matching_pet_type {
+        EmptyRecommendationReason::NoActiveFoods
+    } else {
+        EmptyRecommendationReason::OutOfStock
+    }
+}
+
+/// Applied after `apply_recommendation_logic`, letting callers opt into a
+/// price-ascending order instead of the pet-type-prioritized one.
+fn apply_sort(mut foods: Vec<Food>, sort: SortOrder) -> Vec<Food> {
+    match sort {
+        SortOrder::None => foods,
+        SortOrder::PriceAsc => {
+            foods.sort_by_key(|f| f.price_cents);
+            foods
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::models::{AvailabilityStatus, FoodType};
+
+    fn food(food_id: &str, pet_type: PetType, price_cents: i64) -> Food {
+        Food {
+            food_id: food_id.to_string(),
+            name: food_id.to_string(),
+            description: String::new(),
+            ingredients: Vec::new(),
+            price_cents,
+            stock_quantity: 5,
+            availability_status: AvailabilityStatus::InStock,
+            pet_type,
+            food_type: FoodType::Dry,
+            image_path: String::new(),
+            categories: Vec::new(),
+            prices: std::collections::HashMap::new(),
+            updated_at: chrono::Utc::now(),
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn default_sort_none_preserves_pet_type_priority_order() {
+        let foods = vec![
+            food("cat-food", PetType::Cat, 100),
+            food("dog-food-a", PetType::Dog, 900),
+            food("dog-food-b", PetType::Dog, 200),
+        ];
+
+        let weights = RecommendationWeights::default();
+        let recommended = apply_recommendation_logic(foods, PetType::Dog, &weights, None);
+        let sorted = apply_sort(recommended, SortOrder::None);
+
+        let ids: Vec<&str> = sorted.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["dog-food-a", "dog-food-b", "cat-food"]);
+    }
+
+    #[test]
+    fn price_asc_sort_overrides_pet_type_priority_order() {
+        let foods = vec![
+            food("cat-food", PetType::Cat, 100),
+            food("dog-food-a", PetType::Dog, 900),
+            food("dog-food-b", PetType::Dog, 200),
+        ];
+
+        let weights = RecommendationWeights::default();
+        let recommended = apply_recommendation_logic(foods, PetType::Dog, &weights, None);
+        let sorted = apply_sort(recommended, SortOrder::PriceAsc);
+
+        let ids: Vec<&str> = sorted.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["cat-food", "dog-food-b", "dog-food-a"]);
+    }
+
+    #[test]
+    fn pet_type_with_no_explicit_weight_still_gets_boosted_via_the_default() {
+        let foods = vec![
+            food("cat-food", PetType::Cat, 100),
+            food("bird-food", PetType::Bird, 900),
+        ];
+
+        // Bird has no explicit entry, only a fallback default.
+        let weights = RecommendationWeights::new(HashMap::from([(PetType::Cat, 1.0)]), 0.5);
+        let recommended = apply_recommendation_logic(foods, PetType::Bird, &weights, None);
+
+        let ids: Vec<&str> = recommended.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["bird-food", "cat-food"]);
+    }
+
+    #[test]
+    fn apply_recommendation_logic_drops_out_of_stock_foods() {
+        let mut out_of_stock = food("dog-food-out", PetType::Dog, 100);
+        out_of_stock.availability_status = AvailabilityStatus::OutOfStock;
+        let foods = vec![out_of_stock, food("dog-food-in", PetType::Dog, 200)];
+
+        let weights = RecommendationWeights::default();
+        let recommended = apply_recommendation_logic(foods, PetType::Dog, &weights, None);
+
+        let ids: Vec<&str> = recommended.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(ids, vec!["dog-food-in"]);
+    }
+
+    #[test]
+    fn apply_recommendation_logic_breaks_ties_deterministically_for_the_same_seed() {
+        let foods = vec![
+            food("dog-food-a", PetType::Dog, 100),
+            food("dog-food-b", PetType::Dog, 200),
+            food("dog-food-c", PetType::Dog, 300),
+        ];
+        let weights = RecommendationWeights::default();
+
+        let first = apply_recommendation_logic(foods.clone(), PetType::Dog, &weights, Some(42));
+        let second = apply_recommendation_logic(foods, PetType::Dog, &weights, Some(42));
+
+        let first_ids: Vec<&str> = first.iter().map(|f| f.food_id.as_str()).collect();
+        let second_ids: Vec<&str> = second.iter().map(|f| f.food_id.as_str()).collect();
+        assert_eq!(first_ids, second_ids);
+    }
+
+    #[test]
+    fn apply_recommendation_logic_can_order_ties_differently_for_different_seeds() {
+        let foods = vec![
+            food("dog-food-a", PetType::Dog, 100),
+            food("dog-food-b", PetType::Dog, 200),
+            food("dog-food-c", PetType::Dog, 300),
+        ];
+        let weights = RecommendationWeights::default();
+
+        let orderings: std::collections::HashSet<Vec<String>> = (0..20u64)
+            .map(|seed| {
+                apply_recommendation_logic(foods.clone(), PetType::Dog, &weights, Some(seed))
+                    .into_iter()
+                    .map(|f| f.food_id)
+                    .collect()
+            })
+            .collect();
+
+        assert!(orderings.len() > 1, "expected at least two distinct tie-break orderings across seeds");
+    }
+
+    #[test]
+    fn derive_empty_reason_covers_each_cause() {
+        assert_eq!(derive_empty_reason(true, false), EmptyRecommendationReason::NoCatalog);
+        assert_eq!(
+            derive_empty_reason(false, false),
+            EmptyRecommendationReason::NoActiveFoods
+        );
+        assert_eq!(derive_empty_reason(false, true), EmptyRecommendationReason::OutOfStock);
+    }
+
+    #[tokio::test]
+    async fn get_recommendation_stats_for_all_pet_types_covers_every_pet_type() {
+        let repository = Arc::new(InMemoryFoodRepository(vec![
+            food("dog-food", PetType::Dog, 1000),
+            food("cat-food-a", PetType::Cat, 200),
+            food("cat-food-b", PetType::Cat, 400),
+            food("bird-food", PetType::Bird, 300),
+        ]));
+        let service = RecommendationService::new(repository, SortOrder::None, false, None);
+
+        let result = service.get_recommendation_stats_for_all_pet_types(None).await.unwrap();
+        let stats = result.stats;
+
+        assert!(!result.partial);
+        assert_eq!(stats.len(), PetType::ALL.len());
+        assert_eq!(stats[&PetType::Dog].count, 1);
+        assert_eq!(stats[&PetType::Dog].average_price, 10.0);
+        assert_eq!(stats[&PetType::Cat].count, 2);
+        assert_eq!(stats[&PetType::Cat].average_price, 3.0);
+        assert_eq!(stats[&PetType::Bird].count, 1);
+        assert_eq!(stats[&PetType::Fish].count, 0);
+        assert_eq!(stats[&PetType::Other].count, 0);
+    }
+
+    struct SlowFoodRepository {
+        foods: Vec<Food>,
+        delay: Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl FoodRepository for SlowFoodRepository {
+        async fn get_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn list_foods(&self, _tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            tokio::time::sleep(self.delay).await;
+            Ok(self.foods.clone())
+        }
+
+        async fn put_food(&self, _food: &Food, _tenant_id: Option<&str>) -> ApiResult<()> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn delete_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<()> {
+            unimplemented!("not exercised by these tests")
+        }
+    }
+
+    #[tokio::test]
+    async fn get_recommendation_stats_for_all_pet_types_returns_partial_results_when_the_time_budget_expires() {
+        let repository = Arc::new(SlowFoodRepository { foods: vec![food("dog-food", PetType::Dog, 1000)], delay: Duration::from_millis(200) });
+        let service = RecommendationService::new(repository, SortOrder::None, false, None)
+            .with_stats_fanout_concurrency(1)
+            .with_stats_time_budget(Some(Duration::from_millis(20)));
+
+        let result = service.get_recommendation_stats_for_all_pet_types(None).await.unwrap();
+
+        assert!(result.partial, "a query stuck well past the time budget should be reported as partial");
+        assert!(
+            result.stats.len() < PetType::ALL.len(),
+            "the slow queries that hadn't finished by the deadline should be missing from the results"
+        );
+    }
+
+    #[tokio::test]
+    async fn recommend_orders_tied_foods_identically_across_calls_for_the_same_user_id() {
+        let repository = Arc::new(InMemoryFoodRepository(vec![
+            food("dog-food-a", PetType::Dog, 100),
+            food("dog-food-b", PetType::Dog, 200),
+            food("dog-food-c", PetType::Dog, 300),
+        ]));
+        let service = RecommendationService::new(repository, SortOrder::None, false, None);
+
+        let first = service.recommend(PetType::Dog, None, None, Some("user-1")).await.unwrap();
+        let second = service.recommend(PetType::Dog, None, None, Some("user-1")).await.unwrap();
+
+        assert_eq!(response_food_ids(&first), response_food_ids(&second));
+    }
+
+    #[tokio::test]
+    async fn recommend_can_order_tied_foods_differently_for_different_user_ids() {
+        let repository = Arc::new(InMemoryFoodRepository(vec![
+            food("dog-food-a", PetType::Dog, 100),
+            food("dog-food-b", PetType::Dog, 200),
+            food("dog-food-c", PetType::Dog, 300),
+        ]));
+        let service = RecommendationService::new(repository, SortOrder::None, false, None);
+
+        let mut distinct = std::collections::HashSet::new();
+        for i in 0..20 {
+            let response = service.recommend(PetType::Dog, None, None, Some(&format!("user-{i}"))).await.unwrap();
+            distinct.insert(response_food_ids(&response));
+        }
+
+        assert!(distinct.len() > 1, "expected at least two distinct orderings across different user ids");
+    }
+
+    fn response_food_ids(response: &RecommendationsResponse) -> Vec<String> {
+        match response {
+            RecommendationsResponse::Foods(foods) => foods.iter().map(|f| f.food_id.clone()).collect(),
+            RecommendationsResponse::EmptyWithReason { foods, .. } => foods.iter().map(|f| f.food_id.clone()).collect(),
+        }
+    }
+
+    struct InMemoryFoodRepository(Vec<Food>);
+
+    #[async_trait::async_trait]
+    impl FoodRepository for InMemoryFoodRepository {
+        async fn get_food(&self, food_id: &str, _tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            Ok(self.0.iter().find(|f| f.food_id == food_id).cloned())
+        }
+
+        async fn list_foods(&self, _tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            Ok(self.0.clone())
+        }
+
+        async fn put_food(&self, _food: &Food, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+
+        async fn delete_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn recommend_returns_a_bare_array_when_the_reason_feature_is_disabled() {
+        let repository = Arc::new(InMemoryFoodRepository(Vec::new()));
+        let service = RecommendationService::new(repository, SortOrder::None, false, None);
+
+        let response = service.recommend(PetType::Dog, None, None, None).await.unwrap();
+
+        assert!(matches!(response, RecommendationsResponse::Foods(foods) if foods.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn recommend_reports_no_catalog_when_the_catalog_is_empty() {
+        let repository = Arc::new(InMemoryFoodRepository(Vec::new()));
+        let service = RecommendationService::new(repository, SortOrder::None, true, None);
+
+        let response = service.recommend(PetType::Dog, None, None, None).await.unwrap();
+
+        assert!(matches!(
+            response,
+            RecommendationsResponse::EmptyWithReason {
+                reason: EmptyRecommendationReason::NoCatalog,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn recommend_reports_no_active_foods_when_no_food_matches_the_pet_type() {
+        // An in-stock food of a different pet type would otherwise surface
+        // as a fallback recommendation, so this has to be out of stock too
+        // for the result to actually come back empty.
+        let mut cat_food = food("cat-food", PetType::Cat, 100);
+        cat_food.availability_status = AvailabilityStatus::OutOfStock;
+        let repository = Arc::new(InMemoryFoodRepository(vec![cat_food]));
+        let service = RecommendationService::new(repository, SortOrder::None, true, None);
+
+        let response = service.recommend(PetType::Dog, None, None, None).await.unwrap();
+
+        assert!(matches!(
+            response,
+            RecommendationsResponse::EmptyWithReason {
+                reason: EmptyRecommendationReason::NoActiveFoods,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn recommend_reports_out_of_stock_when_every_matching_food_is_unavailable() {
+        let mut out_of_stock = food("dog-food-out", PetType::Dog, 100);
+        out_of_stock.availability_status = AvailabilityStatus::OutOfStock;
+        let repository = Arc::new(InMemoryFoodRepository(vec![out_of_stock]));
+        let service = RecommendationService::new(repository, SortOrder::None, true, None);
+
+        let response = service.recommend(PetType::Dog, None, None, None).await.unwrap();
+
+        assert!(matches!(
+            response,
+            RecommendationsResponse::EmptyWithReason {
+                reason: EmptyRecommendationReason::OutOfStock,
+                ..
+            }
+        ));
+    }
+
+    /// Unlike `InMemoryFoodRepository`, tracks how many times `list_foods`
+    /// was actually called, and allows pushing new foods after
+    /// construction — needed to exercise cache hits (a second `recommend`
+    /// that shouldn't re-query) and invalidation (a write that should force
+    /// the next `recommend` to re-query).
+    #[derive(Default)]
+    struct CountingFoodRepository {
+        foods: Mutex<Vec<Food>>,
+        list_calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl FoodRepository for CountingFoodRepository {
+        async fn get_food(&self, food_id: &str, _tenant_id: Option<&str>) -> ApiResult<Option<Food>> {
+            Ok(self.foods.lock().unwrap().iter().find(|f| f.food_id == food_id).cloned())
+        }
+
+        async fn list_foods(&self, _tenant_id: Option<&str>) -> ApiResult<Vec<Food>> {
+            self.list_calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(self.foods.lock().unwrap().clone())
+        }
+
+        async fn put_food(&self, food: &Food, _tenant_id: Option<&str>) -> ApiResult<()> {
+            self.foods.lock().unwrap().push(food.clone());
+            Ok(())
+        }
+
+        async fn delete_food(&self, _food_id: &str, _tenant_id: Option<&str>) -> ApiResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn recommend_serves_a_cache_hit_without_re_querying_the_repository() {
+        let repository = Arc::new(CountingFoodRepository {
+            foods: Mutex::new(vec![food("dog-food", PetType::Dog, 500)]),
+            ..Default::default()
+        });
+        let service = RecommendationService::new(
+            repository.clone(),
+            SortOrder::None,
+            false,
+            Some(Duration::from_secs(60)),
+        );
+
+        service.recommend(PetType::Dog, None, None, None).await.unwrap();
+        service.recommend(PetType::Dog, None, None, None).await.unwrap();
+
+        assert_eq!(repository.list_calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn recommend_re_queries_once_the_cache_entry_has_expired() {
+        let repository = Arc::new(CountingFoodRepository {
+            foods: Mutex::new(vec![food("dog-food", PetType::Dog, 500)]),
+            ..Default::default()
+        });
+        let service = RecommendationService::new(
+            repository.clone(),
+            SortOrder::None,
+            false,
+            Some(Duration::from_millis(10)),
+        );
+
+        service.recommend(PetType::Dog, None, None, None).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        service.recommend(PetType::Dog, None, None, None).await.unwrap();
+
+        assert_eq!(repository.list_calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn invalidate_cache_forces_a_re_query_on_the_next_call() {
+        let repository = Arc::new(CountingFoodRepository::default());
+        let service = RecommendationService::new(
+            repository.clone(),
+            SortOrder::None,
+            false,
+            Some(Duration::from_secs(60)),
+        );
+
+        service.recommend(PetType::Dog, None, None, None).await.unwrap();
+        repository
+            .put_food(&food("dog-food", PetType::Dog, 500), None)
+            .await
+            .unwrap();
+        service.invalidate_cache();
+
+        let response = service.recommend(PetType::Dog, None, None, None).await.unwrap();
+
+        assert_eq!(repository.list_calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(matches!(response, RecommendationsResponse::Foods(foods) if foods.len() == 1));
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use crate::models::PetType;
+
+/// Per-pet-type boost applied when scoring recommendations. Looked up by
+/// value rather than matched exhaustively, so a `PetType` with no entry here
+/// (including any variant added after this table was written) falls back to
+/// `default_weight` instead of failing to compile or panicking.
+pub struct RecommendationWeights {
+    weights: HashMap<PetType, f64>,
+    default_weight: f64,
+}
+
+impl RecommendationWeights {
+    pub fn new(weights: HashMap<PetType, f64>, default_weight: f64) -> Self {
+        Self { weights, default_weight }
+    }
+
+    pub fn weight_for(&self, pet_type: PetType) -> f64 {
+        self.weights.get(&pet_type).copied().unwrap_or(self.default_weight)
+    }
+}
+
+impl Default for RecommendationWeights {
+    fn default() -> Self {
+        Self::new(
+            HashMap::from([
+                (PetType::Dog, 1.0),
+                (PetType::Cat, 1.0),
+                (PetType::Bird, 1.0),
+                (PetType::Fish, 1.0),
+                (PetType::Other, 1.0),
+            ]),
+            1.0,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weight_for_an_unconfigured_pet_type_falls_back_to_the_default() {
+        let weights = RecommendationWeights::new(HashMap::from([(PetType::Dog, 1.0)]), 0.3);
+
+        assert_eq!(weights.weight_for(PetType::Dog), 1.0);
+        assert_eq!(weights.weight_for(PetType::Bird), 0.3);
+    }
+}
@@ -0,0 +1,168 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::extract::connect_info::IntoMakeServiceWithConnectInfo;
+use axum::Router;
+use tokio::net::TcpListener;
+
+use crate::middleware::in_flight_request_count;
+
+/// Resolves on SIGTERM (what an orchestrator sends when scaling down or
+/// replacing a task) or Ctrl+C (local runs), whichever comes first.
+pub async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Serves `make_service` until `shutdown_signal` resolves, then stops
+/// accepting new connections and gives in-flight handlers up to
+/// `drain_timeout` to finish before returning anyway. `axum::serve`'s own
+/// graceful shutdown has no timeout of its own, so a handler stuck past
+/// `drain_timeout` would otherwise block shutdown forever; logs how many
+/// requests (per [`in_flight_request_count`]) were still active when that
+/// happens.
+pub async fn serve_with_graceful_shutdown<S>(
+    listener: TcpListener,
+    make_service: IntoMakeServiceWithConnectInfo<Router, SocketAddr>,
+    drain_timeout: Duration,
+    shutdown_signal: S,
+) where
+    S: Future<Output = ()> + Send + 'static,
+{
+    let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
+
+    let serve_task = tokio::spawn(async move {
+        axum::serve(listener, make_service)
+            .with_graceful_shutdown(async move {
+                let _ = shutdown_rx.changed().await;
+            })
+            .await
+    });
+
+    shutdown_signal.await;
+    tracing::info!(
+        active_requests = in_flight_request_count(),
+        "shutdown signal received; draining in-flight requests"
+    );
+    let _ = shutdown_tx.send(true);
+
+    match tokio::time::timeout(drain_timeout, serve_task).await {
+        Ok(Ok(Ok(()))) => tracing::info!("all in-flight requests drained before shutdown"),
+        Ok(Ok(Err(err))) => tracing::error!(error = %err, "server error during shutdown"),
+        Ok(Err(join_err)) => tracing::error!(error = %join_err, "server task panicked during shutdown"),
+        Err(_) => tracing::warn!(
+            active_requests = in_flight_request_count(),
+            drain_timeout_secs = drain_timeout.as_secs(),
+            "drain timeout elapsed with requests still in flight; dropping them"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::routing::get;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::sync::{oneshot, Notify};
+
+    use super::*;
+
+    async fn get_response(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    /// Drives a real in-flight request through a real `TcpListener`, rather
+    /// than `tower::ServiceExt::oneshot`, since the behavior under test is
+    /// axum's graceful shutdown actually letting that connection finish.
+    #[tokio::test]
+    async fn an_in_flight_request_completes_instead_of_being_aborted_at_shutdown() {
+        let started = Arc::new(Notify::new());
+        let started_for_handler = started.clone();
+        let app = Router::new().route(
+            "/slow",
+            get(move || {
+                let started = started_for_handler.clone();
+                async move {
+                    started.notify_one();
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    "done"
+                }
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let serve_handle = tokio::spawn(serve_with_graceful_shutdown(
+            listener,
+            make_service,
+            Duration::from_secs(5),
+            async move {
+                let _ = shutdown_rx.await;
+            },
+        ));
+
+        let request = tokio::spawn(async move { get_response(addr, "/slow").await });
+
+        started.notified().await;
+        shutdown_tx.send(()).unwrap();
+
+        let response = request.await.unwrap();
+        assert!(response.ends_with("done"), "expected the slow handler's body, got: {response}");
+
+        serve_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn shutdown_returns_promptly_when_nothing_is_in_flight() {
+        let app = Router::new().route("/health", get(|| async { "ok" }));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let serve_handle = tokio::spawn(serve_with_graceful_shutdown(
+            listener,
+            make_service,
+            Duration::from_secs(5),
+            async move {
+                let _ = shutdown_rx.await;
+            },
+        ));
+
+        shutdown_tx.send(()).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), serve_handle)
+            .await
+            .expect("shutdown should not wait out the drain timeout with nothing in flight")
+            .unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use aws_sdk_ssm::Client as SsmClient;
+use tokio::sync::Mutex;
+
+#[derive(Debug, thiserror::Error)]
+#[error("failed to fetch SSM parameter {parameter_name:?}: {message}")]
+pub struct SsmParameterCacheError {
+    parameter_name: String,
+    message: String,
+}
+
+/// In-process TTL cache in front of `GetParameter`, so looking up the same
+/// parameter repeatedly (e.g. from several concurrent callers) doesn't
+/// repeat the same network round trip. Not yet consulted by anything —
+/// `ServerConfig` resolves its `/petfood/*` parameters from pre-substituted
+/// environment variables rather than a live SSM call, so this is ready for
+/// whichever call site ends up needing a live parameter lookup.
+///
+/// The single lock is held across a cache miss's fetch, which both protects
+/// the map and means two concurrent callers racing on the same parameter
+/// coalesce into one in-flight `GetParameter` call rather than each making
+/// their own — the second caller simply finds the first caller's now-fresh
+/// entry once it acquires the lock. Callers asking for different parameters
+/// each make their own call, one after another.
+#[allow(dead_code)]
+pub struct SsmParameterCache {
+    client: SsmClient,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+#[allow(dead_code)]
+impl SsmParameterCache {
+    pub fn new(client: SsmClient, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns `name`'s current value, serving a cached value younger than
+    /// `ttl` without calling SSM.
+    pub async fn get_parameter(&self, name: &str) -> Result<String, SsmParameterCacheError> {
+        let mut entries = self.entries.lock().await;
+
+        if let Some((fetched_at, value)) = entries.get(name) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(value.clone());
+            }
+        }
+
+        let response = self
+            .client
+            .get_parameter()
+            .name(name)
+            .with_decryption(true)
+            .send()
+            .await
+            .map_err(|err| SsmParameterCacheError {
+                parameter_name: name.to_string(),
+                message: err.to_string(),
+            })?;
+        let value = response.parameter().and_then(|p| p.value()).unwrap_or_default().to_string();
+
+        entries.insert(name.to_string(), (Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    /// Drops every cached entry, so the next `get_parameter` call for each
+    /// hits SSM again regardless of its age.
+    pub async fn refresh(&self) {
+        self.entries.lock().await.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unreachable_cache(ttl: Duration) -> SsmParameterCache {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .endpoint_url("http://127.0.0.1:1")
+            .build();
+        SsmParameterCache::new(SsmClient::new(&sdk_config), ttl)
+    }
+
+    #[tokio::test]
+    async fn a_lookup_with_nothing_cached_hits_ssm_and_fails_against_an_unreachable_endpoint() {
+        let cache = unreachable_cache(Duration::from_secs(300));
+
+        let result = cache.get_parameter("/petfood/example").await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn a_second_lookup_within_the_ttl_is_served_from_cache_without_hitting_ssm_again() {
+        let cache = unreachable_cache(Duration::from_secs(300));
+        cache
+            .entries
+            .lock()
+            .await
+            .insert("/petfood/example".to_string(), (Instant::now(), "cached-value".to_string()));
+
+        // The client points at an unreachable endpoint, so an Ok result here
+        // proves the lookup was served from cache rather than attempting a
+        // live SSM call.
+        let result = cache.get_parameter("/petfood/example").await;
+
+        assert_eq!(result.unwrap(), "cached-value");
+    }
+
+    #[tokio::test]
+    async fn a_lookup_past_the_ttl_re_fetches_and_fails_against_an_unreachable_endpoint() {
+        let cache = unreachable_cache(Duration::from_millis(1));
+        cache
+            .entries
+            .lock()
+            .await
+            .insert("/petfood/example".to_string(), (Instant::now(), "stale-value".to_string()));
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let result = cache.get_parameter("/petfood/example").await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn refresh_clears_cached_entries_so_the_next_lookup_hits_ssm_again() {
+        let cache = unreachable_cache(Duration::from_secs(300));
+        cache
+            .entries
+            .lock()
+            .await
+            .insert("/petfood/example".to_string(), (Instant::now(), "cached-value".to_string()));
+
+        cache.refresh().await;
+        let result = cache.get_parameter("/petfood/example").await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use std::future::Future;
+use std::time::Duration;
+
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use aws_sdk_ssm::Client as SsmClient;
+
+/// Issues a cheap SSM call and a `describe_table` call, each bounded by
+/// `timeout`, so a hung or misconfigured endpoint fails the probe quickly
+/// instead of riding the SDK's 60s global operation timeout and delaying
+/// boot. Failures are logged, never fatal — the same "never block startup"
+/// stance as [`crate::warmup::warm_connections`].
+pub async fn run_startup_probes(
+    ssm_client: &SsmClient,
+    dynamodb_client: &DynamoDbClient,
+    foods_table_name: &str,
+    timeout: Duration,
+) {
+    if let Err(err) = probe_ssm(ssm_client, timeout).await {
+        tracing::warn!(error = %err, "SSM startup connectivity probe failed");
+    }
+    if let Err(err) = probe_dynamodb(dynamodb_client, foods_table_name, timeout).await {
+        tracing::warn!(error = %err, "DynamoDB startup connectivity probe failed");
+    }
+}
+
+async fn probe_ssm(client: &SsmClient, timeout: Duration) -> Result<(), StartupProbeError> {
+    run_probe("SSM", timeout, client.describe_parameters().send()).await
+}
+
+async fn probe_dynamodb(client: &DynamoDbClient, table_name: &str, timeout: Duration) -> Result<(), StartupProbeError> {
+    run_probe("DynamoDB", timeout, client.describe_table().table_name(table_name).send()).await
+}
+
+#[derive(Debug, thiserror::Error)]
+enum StartupProbeError {
+    #[error("{probe} probe timed out after {timeout_ms}ms")]
+    TimedOut { probe: &'static str, timeout_ms: u64 },
+    #[error("{probe} probe failed: {message}")]
+    Failed { probe: &'static str, message: String },
+}
+
+async fn run_probe<F, T, E>(probe: &'static str, timeout: Duration, call: F) -> Result<(), StartupProbeError>
+where
+    F: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    match tokio::time::timeout(timeout, call).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(err)) => Err(StartupProbeError::Failed { probe, message: err.to_string() }),
+        Err(_) => Err(StartupProbeError::TimedOut { probe, timeout_ms: timeout.as_millis() as u64 }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn run_probe_times_out_when_the_call_exceeds_the_budget() {
+        let result = run_probe("test", Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            Ok::<(), std::convert::Infallible>(())
+        })
+        .await;
+
+        assert!(matches!(result, Err(StartupProbeError::TimedOut { probe: "test", .. })));
+    }
+
+    #[tokio::test]
+    async fn run_probe_succeeds_when_the_call_completes_within_the_budget() {
+        let result = run_probe("test", Duration::from_millis(200), async { Ok::<(), std::convert::Infallible>(()) }).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn run_probe_reports_the_inner_error_when_the_call_fails_promptly() {
+        let result = run_probe("test", Duration::from_millis(200), async { Err::<(), _>("access denied") }).await;
+
+        assert!(matches!(result, Err(StartupProbeError::Failed { probe: "test", .. })));
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+/// Accumulates named phase durations during boot (config load, AWS client
+/// init, table warm-up, ...) so `main` can log and export a single
+/// breakdown instead of threading timing through every phase by hand.
+#[derive(Debug, Default)]
+pub struct StartupTimings {
+    phases: Vec<(String, Duration)>,
+}
+
+impl StartupTimings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, phase: &str, duration: Duration) {
+        self.phases.push((phase.to_string(), duration));
+    }
+
+    /// Sum of every recorded phase, i.e. the cold-start duration covered by
+    /// `main`'s instrumented boot phases.
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    /// The duration recorded for `phase`, or `None` if it was never
+    /// recorded (e.g. a phase that's skipped by configuration).
+    pub fn phase_seconds(&self, phase: &str) -> Option<f64> {
+        self.phases
+            .iter()
+            .find(|(recorded, _)| recorded == phase)
+            .map(|(_, duration)| duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_sums_every_recorded_phase() {
+        let mut timings = StartupTimings::new();
+        timings.record("config_load", Duration::from_millis(10));
+        timings.record("aws_client_init", Duration::from_millis(25));
+        timings.record("table_warmup", Duration::from_millis(5));
+
+        assert_eq!(timings.total(), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn total_is_zero_when_nothing_was_recorded() {
+        assert_eq!(StartupTimings::new().total(), Duration::ZERO);
+    }
+
+    #[test]
+    fn phase_seconds_finds_a_recorded_phase() {
+        let mut timings = StartupTimings::new();
+        timings.record("config_load", Duration::from_millis(250));
+
+        assert_eq!(timings.phase_seconds("config_load"), Some(0.25));
+    }
+
+    #[test]
+    fn phase_seconds_is_none_for_a_phase_that_was_never_recorded() {
+        let timings = StartupTimings::new();
+
+        assert_eq!(timings.phase_seconds("table_warmup"), None);
+    }
+}
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::admin::AdminState;
+use crate::config::ServerConfig;
+use crate::events::EventEmitter;
+use crate::readiness::ReadinessChecker;
+use crate::service::{CartService, FoodService, RecommendationService};
+
+#[derive(Clone)]
+pub struct AppState {
+    pub food_service: Arc<FoodService>,
+    pub cart_service: Arc<CartService>,
+    pub recommendation_service: Arc<RecommendationService>,
+    pub config: Arc<ServerConfig>,
+    pub admin_state: Arc<AdminState>,
+    pub readiness_checker: Arc<ReadinessChecker>,
+    /// Shared with `food_service`/`cart_service`'s domain-event emitter, so
+    /// `GET /health/ready`'s `events` field reports the same instance's
+    /// health rather than a separate one.
+    pub event_emitter: Arc<EventEmitter>,
+}
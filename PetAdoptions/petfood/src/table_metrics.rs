@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use aws_sdk_dynamodb::operation::describe_table::DescribeTableOutput;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use once_cell::sync::Lazy;
+use prometheus::{IntGauge, Opts};
+use tokio::sync::oneshot;
+
+use crate::metrics::REGISTRY;
+
+/// DynamoDB only recomputes `ItemCount` roughly every six hours, so these
+/// gauges are a rough catalog-size indicator for dashboards, never an
+/// accurate point-in-time count.
+pub static FOODS_TABLE_ITEM_COUNT: Lazy<IntGauge> = Lazy::new(|| {
+    let gauge = IntGauge::with_opts(Opts::new(
+        "petfood_foods_table_item_count",
+        "Approximate item count for the foods table, from DynamoDB's periodically-updated ItemCount",
+    ))
+    .unwrap();
+    REGISTRY.register(Box::new(gauge.clone())).expect("metric already registered");
+    gauge
+});
+
+pub static CARTS_TABLE_ITEM_COUNT: Lazy<IntGauge> = Lazy::new(|| {
+    let gauge = IntGauge::with_opts(Opts::new(
+        "petfood_carts_table_item_count",
+        "Approximate item count for the carts table, from DynamoDB's periodically-updated ItemCount",
+    ))
+    .unwrap();
+    REGISTRY.register(Box::new(gauge.clone())).expect("metric already registered");
+    gauge
+});
+
+/// Extracts the `ItemCount` a `describe_table` response reports, defaulting
+/// to 0 when the table description or count is missing rather than leaving
+/// the gauge stuck on a stale value.
+pub fn item_count_from_describe_table(output: &DescribeTableOutput) -> i64 {
+    output.table().and_then(|table| table.item_count()).unwrap_or(0)
+}
+
+/// Polls both tables' `ItemCount` once per `interval` and publishes them as
+/// gauges, until `shutdown` resolves. A `describe_table` failure for one
+/// table is logged and skipped rather than aborting the task, since a
+/// transient API error shouldn't kill background reporting for the rest of
+/// the process.
+pub async fn run_table_item_count_reporter(
+    client: DynamoDbClient,
+    foods_table_name: String,
+    carts_table_name: String,
+    interval: Duration,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                report_once(&client, &foods_table_name, &FOODS_TABLE_ITEM_COUNT).await;
+                report_once(&client, &carts_table_name, &CARTS_TABLE_ITEM_COUNT).await;
+            }
+            _ = &mut shutdown => {
+                tracing::info!("table item count reporter shutting down");
+                break;
+            }
+        }
+    }
+}
+
+async fn report_once(client: &DynamoDbClient, table_name: &str, gauge: &IntGauge) {
+    match client.describe_table().table_name(table_name).send().await {
+        Ok(output) => gauge.set(item_count_from_describe_table(&output)),
+        Err(err) => tracing::warn!(error = %err, table_name, "failed to describe table for item count metric"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::types::TableDescription;
+
+    use super::*;
+
+    #[test]
+    fn item_count_from_describe_table_reads_the_reported_count() {
+        let output = DescribeTableOutput::builder()
+            .table(TableDescription::builder().item_count(42).build())
+            .build();
+
+        assert_eq!(item_count_from_describe_table(&output), 42);
+    }
+
+    #[test]
+    fn item_count_from_describe_table_defaults_to_zero_when_the_table_is_missing() {
+        let output = DescribeTableOutput::builder().build();
+
+        assert_eq!(item_count_from_describe_table(&output), 0);
+    }
+
+    #[test]
+    fn item_count_from_describe_table_defaults_to_zero_when_the_count_is_missing() {
+        let output = DescribeTableOutput::builder()
+            .table(TableDescription::builder().build())
+            .build();
+
+        assert_eq!(item_count_from_describe_table(&output), 0);
+    }
+
+    #[tokio::test]
+    async fn run_table_item_count_reporter_shuts_down_when_signaled() {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        let client = DynamoDbClient::new(&sdk_config);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+        let handle = tokio::spawn(run_table_item_count_reporter(
+            client,
+            "test-foods".to_string(),
+            "test-carts".to_string(),
+            Duration::from_secs(3600),
+            shutdown_rx,
+        ));
+
+        shutdown_tx.send(()).unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), handle).await;
+        assert!(result.is_ok(), "reporter task should shut down promptly once signaled");
+    }
+}
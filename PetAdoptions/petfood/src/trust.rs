@@ -0,0 +1,90 @@
+use std::net::Ipv4Addr;
+
+/// CIDR allow-list used to decide whether an inbound request's forwarded
+/// headers (e.g. `X-Forwarded-For`, consulted by `middleware::client_ip`)
+/// come from a trusted upstream proxy. Callers outside the allow-list
+/// should have their headers ignored and fall back to the TCP peer address
+/// instead of trusting an arbitrary caller-supplied one.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxyAllowList {
+    cidrs: Vec<(Ipv4Addr, u32)>,
+}
+
+impl TrustedProxyAllowList {
+    /// Parses a list of `a.b.c.d/n` entries. An empty list trusts nothing,
+    /// which is the safe default: every inbound header is treated as
+    /// untrusted until an allow-list is explicitly configured.
+    pub fn parse(entries: &[String]) -> Result<Self, String> {
+        let mut cidrs = Vec::with_capacity(entries.len());
+        for entry in entries {
+            cidrs.push(parse_cidr(entry)?);
+        }
+        Ok(Self { cidrs })
+    }
+
+    pub fn is_trusted(&self, addr: Ipv4Addr) -> bool {
+        self.cidrs.iter().any(|(network, prefix_len)| in_network(addr, *network, *prefix_len))
+    }
+}
+
+fn parse_cidr(entry: &str) -> Result<(Ipv4Addr, u32), String> {
+    let (address, prefix_len) = entry
+        .split_once('/')
+        .ok_or_else(|| format!("invalid CIDR entry '{entry}': missing prefix length"))?;
+
+    let address: Ipv4Addr = address
+        .parse()
+        .map_err(|_| format!("invalid CIDR entry '{entry}': '{address}' is not an IPv4 address"))?;
+    let prefix_len: u32 = prefix_len
+        .parse()
+        .map_err(|_| format!("invalid CIDR entry '{entry}': '{prefix_len}' is not a prefix length"))?;
+    if prefix_len > 32 {
+        return Err(format!("invalid CIDR entry '{entry}': prefix length must be 0-32"));
+    }
+
+    Ok((address, prefix_len))
+}
+
+fn in_network(addr: Ipv4Addr, network: Ipv4Addr, prefix_len: u32) -> bool {
+    let mask = if prefix_len == 0 { 0 } else { u32::MAX << (32 - prefix_len) };
+    u32::from(addr) & mask == u32::from(network) & mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trusts_an_address_inside_an_allow_listed_cidr() {
+        let allow_list = TrustedProxyAllowList::parse(&["10.0.0.0/8".to_string()]).unwrap();
+
+        assert!(allow_list.is_trusted("10.1.2.3".parse().unwrap()));
+    }
+
+    #[test]
+    fn rejects_an_address_outside_every_allow_listed_cidr() {
+        let allow_list = TrustedProxyAllowList::parse(&["10.0.0.0/8".to_string()]).unwrap();
+
+        assert!(!allow_list.is_trusted("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn empty_allow_list_trusts_nothing() {
+        let allow_list = TrustedProxyAllowList::default();
+
+        assert!(!allow_list.is_trusted("127.0.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn exact_host_match_requires_a_32_bit_prefix() {
+        let allow_list = TrustedProxyAllowList::parse(&["192.168.1.5/32".to_string()]).unwrap();
+
+        assert!(allow_list.is_trusted("192.168.1.5".parse().unwrap()));
+        assert!(!allow_list.is_trusted("192.168.1.6".parse().unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_a_malformed_entry() {
+        assert!(TrustedProxyAllowList::parse(&["not-a-cidr".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Used by `DynamoDbCartRepository::put_cart` and `DynamoDbIdempotencyStore::try_claim`
+/// to compute `expires_at`/`ttl`, so a clock-skewed instance in the fleet
+/// can't hand back an expiry in the past (the record expires the instant
+/// it's written) or one so far in the future it effectively never does.
+/// Centralizing the clamp here means every TTL computation gets the same
+/// skew handling instead of reinventing it.
+pub fn compute_expiry(now: DateTime<Utc>, raw_ttl: Duration, min_ttl: Duration, max_ttl: Duration) -> DateTime<Utc> {
+    now + clamp_ttl(raw_ttl, min_ttl, max_ttl)
+}
+
+/// Clamps a TTL duration computed against a possibly clock-skewed instant
+/// to `[min_ttl, max_ttl]`, logging a warning when clamping actually
+/// changed the value — that's the signal the input looked skewed rather
+/// than just being an unusually short or long but legitimate TTL.
+fn clamp_ttl(raw_ttl: Duration, min_ttl: Duration, max_ttl: Duration) -> Duration {
+    let clamped = raw_ttl.clamp(min_ttl, max_ttl);
+    if clamped != raw_ttl {
+        tracing::warn!(
+            raw_ttl_seconds = raw_ttl.num_seconds(),
+            clamped_ttl_seconds = clamped.num_seconds(),
+            "TTL computation looks clock-skewed; clamped to the configured window"
+        );
+    }
+    clamped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-08-08T00:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn a_normal_ttl_within_the_window_passes_through_unchanged() {
+        let expiry = compute_expiry(now(), Duration::hours(1), Duration::minutes(1), Duration::days(7));
+
+        assert_eq!(expiry, now() + Duration::hours(1));
+    }
+
+    #[test]
+    fn a_past_skewed_ttl_is_clamped_up_to_the_minimum() {
+        let expiry = compute_expiry(now(), Duration::seconds(-60), Duration::minutes(1), Duration::days(7));
+
+        assert_eq!(expiry, now() + Duration::minutes(1));
+    }
+
+    #[test]
+    fn an_excessive_future_ttl_is_clamped_down_to_the_maximum() {
+        let expiry = compute_expiry(now(), Duration::days(365), Duration::minutes(1), Duration::days(7));
+
+        assert_eq!(expiry, now() + Duration::days(7));
+    }
+}
@@ -0,0 +1,47 @@
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use aws_sdk_eventbridge::Client as EventBridgeClient;
+
+/// Issues one cheap call per AWS client to prime DNS resolution and the TLS
+/// handshake before the server starts accepting traffic, so the first real
+/// request doesn't pay that cost. Gated behind `PETFOOD_WARM_CONNECTIONS`
+/// since it adds a fixed delay to startup. Failures are logged, never fatal
+/// — a cold first request is an acceptable fallback.
+pub async fn warm_connections(dynamodb: &DynamoDbClient, eventbridge: &EventBridgeClient, foods_table_name: &str) {
+    if let Err(err) = dynamodb.describe_table().table_name(foods_table_name).send().await {
+        tracing::warn!(error = %err, "DynamoDB connection warm-up failed");
+    }
+    if let Err(err) = eventbridge.list_event_buses().send().await {
+        tracing::warn!(error = %err, "EventBridge connection warm-up failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn dummy_clients() -> (DynamoDbClient, EventBridgeClient) {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        (
+            DynamoDbClient::new(&sdk_config),
+            EventBridgeClient::new(&sdk_config),
+        )
+    }
+
+    #[tokio::test]
+    async fn warm_connections_issues_both_calls_and_returns_without_panicking() {
+        let (dynamodb, eventbridge) = dummy_clients();
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            warm_connections(&dynamodb, &eventbridge, "test-foods"),
+        )
+        .await;
+
+        assert!(result.is_ok(), "warm_connections should complete promptly");
+    }
+}